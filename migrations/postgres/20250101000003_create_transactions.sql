@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS transactions (
+    id           TEXT PRIMARY KEY,
+    owner_id     TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    account      TEXT NOT NULL,
+    amount_minor BIGINT NOT NULL,
+    currency     CHAR(3) NOT NULL,
+    booked_on    DATE NOT NULL,
+    value_on     DATE NOT NULL,
+    payee        TEXT NOT NULL,
+    memo         TEXT,
+    category     TEXT NOT NULL,
+    tags         TEXT[] NOT NULL DEFAULT '{}',
+    created_at   TIMESTAMPTZ NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_owner_booked_on ON transactions (owner_id, booked_on);
@@ -0,0 +1,5 @@
+-- Accounts created before email verification existed are treated as
+-- verified, so existing users can still sign in.
+ALTER TABLE users ADD COLUMN email_verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE users SET email_verified = TRUE;
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS transactions (
+    id           TEXT PRIMARY KEY NOT NULL,
+    owner_id     TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    account      TEXT NOT NULL,
+    amount_minor INTEGER NOT NULL,
+    currency     TEXT NOT NULL,
+    booked_on    TEXT NOT NULL,
+    value_on     TEXT NOT NULL,
+    payee        TEXT NOT NULL,
+    memo         TEXT,
+    category     TEXT NOT NULL,
+    -- JSON array of tag strings
+    tags         TEXT NOT NULL DEFAULT '[]',
+    created_at   TEXT NOT NULL,
+    updated_at   TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_owner_booked_on ON transactions (owner_id, booked_on);
@@ -0,0 +1,6 @@
+-- Enforce one account per email in the store itself, so concurrent
+-- registrations cannot both succeed. Fails if duplicates already exist;
+-- remove them before upgrading.
+DROP INDEX IF EXISTS idx_users_email;
+
+CREATE UNIQUE INDEX idx_users_email ON users (email);
@@ -0,0 +1,5 @@
+-- Accounts created before email verification existed are treated as
+-- verified, so existing users can still sign in.
+ALTER TABLE users ADD COLUMN email_verified INTEGER NOT NULL DEFAULT 0;
+
+UPDATE users SET email_verified = 1;
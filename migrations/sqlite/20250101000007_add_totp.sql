@@ -0,0 +1,5 @@
+-- Authenticator (TOTP) used as a second sign-in factor. A secret without
+-- totp_enabled is an enrollment that has not been confirmed yet.
+ALTER TABLE users ADD COLUMN totp_secret TEXT;
+ALTER TABLE users ADD COLUMN totp_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN totp_last_step INTEGER;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS login_attempts (
+    id             TEXT PRIMARY KEY NOT NULL,
+    failed_count   INTEGER NOT NULL,
+    last_failed_at TEXT NOT NULL,
+    locked_until   TEXT
+);
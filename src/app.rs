@@ -1,4 +1,9 @@
+use crate::components::access_tokens::AccessTokensPage;
 use crate::components::login::AuthPage;
+use crate::components::password_reset::{ForgotPasswordPage, ResetPasswordPage};
+use crate::components::sessions::SessionsPage;
+use crate::components::verify_email::VerifyEmailPage;
+use crate::domain::value_objects::{Currency, Locale, Money};
 use leptos::prelude::*;
 use leptos_meta::{Meta, MetaTags, Stylesheet, Title, provide_meta_context};
 use leptos_router::components::{Route, Router, Routes};
@@ -40,6 +45,11 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/") view=AuthPage />
                     <Route path=path!("/login") view=AuthPage />
                     <Route path=path!("/dashboard") view=Dashboard />
+                    <Route path=path!("/verify-email") view=VerifyEmailPage />
+                    <Route path=path!("/forgot-password") view=ForgotPasswordPage />
+                    <Route path=path!("/reset-password") view=ResetPasswordPage />
+                    <Route path=path!("/settings/tokens") view=AccessTokensPage />
+                    <Route path=path!("/settings/sessions") view=SessionsPage />
                 </Routes>
             </main>
         </Router>
@@ -181,8 +191,24 @@ fn DashboardHeader() -> impl IntoView {
     }
 }
 
+/// Formats basis points (1/100 of a percent) as a percentage with one decimal, e.g. `28.4%`.
+fn format_basis_points(basis_points: i64) -> String {
+    let sign = if basis_points < 0 { "-" } else { "" };
+    let tenths = (basis_points.unsigned_abs() + 5) / 10;
+    format!("{sign}{}.{}%", tenths / 10, tenths % 10)
+}
+
 #[component]
 fn DashboardContent() -> impl IntoView {
+    let locale = Locale::EnUs;
+    let total_balance = Money::from_minor(1_242_650, Currency::USD);
+    let monthly_income = Money::from_minor(485_000, Currency::USD);
+    let monthly_expenses = Money::from_minor(234_075, Currency::USD);
+    let savings_rate = monthly_income
+        .checked_sub(&monthly_expenses)
+        .and_then(|saved| saved.basis_points_of(&monthly_income))
+        .map_or_else(|_| "—".to_string(), format_basis_points);
+
     view! {
         <div class="space-y-6">
             // Welcome section
@@ -212,7 +238,7 @@ fn DashboardContent() -> impl IntoView {
             <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-6">
                 <StatCard
                     title="Total Balance"
-                    value="$12,426.50"
+                    value=total_balance.format(locale)
                     change="+2.5%"
                     change_type="positive"
                     icon=view! {
@@ -228,7 +254,7 @@ fn DashboardContent() -> impl IntoView {
                 />
                 <StatCard
                     title="Monthly Income"
-                    value="$4,850.00"
+                    value=monthly_income.format(locale)
                     change="+12.3%"
                     change_type="positive"
                     icon=view! {
@@ -244,7 +270,7 @@ fn DashboardContent() -> impl IntoView {
                 />
                 <StatCard
                     title="Monthly Expenses"
-                    value="$2,340.75"
+                    value=monthly_expenses.format(locale)
                     change="-5.2%"
                     change_type="negative"
                     icon=view! {
@@ -260,7 +286,7 @@ fn DashboardContent() -> impl IntoView {
                 />
                 <StatCard
                     title="Savings Rate"
-                    value="28.4%"
+                    value=savings_rate
                     change="+3.1%"
                     change_type="positive"
                     icon=view! {
@@ -290,28 +316,28 @@ fn DashboardContent() -> impl IntoView {
                         <TransactionItem
                             name="Grocery Store"
                             category="Food"
-                            amount="-$89.50"
+                            amount=Money::from_minor(-8950, Currency::USD).format_signed(locale)
                             date="Today"
                             is_expense=true
                         />
                         <TransactionItem
                             name="Salary Deposit"
                             category="Income"
-                            amount="+$2,500.00"
+                            amount=Money::from_minor(250_000, Currency::USD).format_signed(locale)
                             date="Yesterday"
                             is_expense=false
                         />
                         <TransactionItem
                             name="Netflix"
                             category="Entertainment"
-                            amount="-$15.99"
+                            amount=Money::from_minor(-1599, Currency::USD).format_signed(locale)
                             date="2 days ago"
                             is_expense=true
                         />
                         <TransactionItem
                             name="Coffee Shop"
                             category="Food"
-                            amount="-$4.50"
+                            amount=Money::from_minor(-450, Currency::USD).format_signed(locale)
                             date="3 days ago"
                             is_expense=true
                         />
@@ -408,7 +434,7 @@ fn DashboardContent() -> impl IntoView {
 #[component]
 fn StatCard(
     title: &'static str,
-    value: &'static str,
+    value: String,
     change: &'static str,
     change_type: &'static str,
     icon: impl IntoView,
@@ -442,7 +468,7 @@ fn StatCard(
 fn TransactionItem(
     name: &'static str,
     category: &'static str,
-    amount: &'static str,
+    amount: String,
     date: &'static str,
     is_expense: bool,
 ) -> impl IntoView {
@@ -1 +1,2 @@
+pub mod transaction_service_errors;
 pub mod user_service_errors;
@@ -0,0 +1,49 @@
+use crate::domain::errors::transaction_errors::TransactionDomainError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransactionServiceError {
+    // Business logic errors
+    #[error("Transaction '{id}' not found")]
+    NotFound { id: String },
+
+    #[error("Invalid transaction query: {message}")]
+    InvalidQuery { message: String },
+
+    // Domain validation errors
+    #[error("Domain validation failed")]
+    DomainValidation { source: TransactionDomainError },
+
+    // Infrastructure errors
+    #[error("Repository operation failed: {operation}")]
+    RepositoryError {
+        operation: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl TransactionServiceError {
+    /// Check if error is caused by user input
+    #[must_use]
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            TransactionServiceError::NotFound { .. }
+                | TransactionServiceError::InvalidQuery { .. }
+                | TransactionServiceError::DomainValidation { .. }
+        )
+    }
+
+    /// Check if error is transient and retry might work
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(self, TransactionServiceError::RepositoryError { .. })
+    }
+}
+
+impl From<TransactionDomainError> for TransactionServiceError {
+    fn from(error: TransactionDomainError) -> Self {
+        TransactionServiceError::DomainValidation { source: error }
+    }
+}
@@ -7,12 +7,61 @@ pub enum UserServiceError {
     #[error("User with email '{email}' already exists")]
     UserAlreadyExists { email: String },
 
-    #[error("User with email '{email}' not found")]
-    UserNotFound { email: String },
-
     #[error("Invalid credentials provided")]
     InvalidCredentials,
 
+    #[error("Account '{user_id}' not found")]
+    AccountNotFound { user_id: String },
+
+    /// The password given to confirm a sensitive change was wrong. `field`
+    /// names the request field that carried it.
+    #[error("Incorrect password")]
+    IncorrectPassword { field: &'static str },
+
+    #[error("Email address has not been verified")]
+    EmailNotVerified,
+
+    /// The verification token is malformed, expired, already used, or was
+    /// issued for an address the account no longer has.
+    #[error("Invalid or expired verification token")]
+    InvalidVerificationToken,
+
+    /// The reset token is unknown, expired or already used.
+    #[error("Invalid or expired password reset token")]
+    InvalidResetToken,
+
+    #[error("Two-factor authentication is already enabled")]
+    MfaAlreadyEnabled,
+
+    #[error("Two-factor authentication is not set up")]
+    MfaNotEnrolled,
+
+    /// Neither a current authenticator code nor an unused recovery code.
+    #[error("Invalid two-factor authentication code")]
+    InvalidMfaCode,
+
+    /// The challenge token from the password step is invalid or expired.
+    #[error("Invalid or expired MFA challenge")]
+    InvalidMfaChallenge,
+
+    /// Too many failed sign-in attempts for the account or the client
+    /// address; sign-in is refused until the lockout expires.
+    #[error("Sign-in locked for {retry_after_seconds} seconds")]
+    AccountLocked { retry_after_seconds: u64 },
+
+    /// The personal access token is malformed, unknown, revoked or expired.
+    #[error("Invalid or expired personal access token")]
+    InvalidAccessToken,
+
+    #[error("Personal access token '{token_id}' not found")]
+    AccessTokenNotFound { token_id: String },
+
+    #[error("A user may hold at most {limit} personal access tokens")]
+    AccessTokenLimitReached { limit: usize },
+
+    #[error("Session '{session_id}' not found")]
+    SessionNotFound { session_id: String },
+
     #[error("User registration failed")]
     RegistrationFailed,
 
@@ -37,6 +86,12 @@ pub enum UserServiceError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
 
+    #[error("Failed to send email")]
+    MailDeliveryFailed {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     // System errors
     #[error("Service temporarily unavailable")]
     ServiceUnavailable,
@@ -53,6 +108,19 @@ impl UserServiceError {
             self,
             UserServiceError::UserAlreadyExists { .. }
                 | UserServiceError::InvalidCredentials
+                | UserServiceError::IncorrectPassword { .. }
+                | UserServiceError::EmailNotVerified
+                | UserServiceError::InvalidVerificationToken
+                | UserServiceError::InvalidResetToken
+                | UserServiceError::MfaAlreadyEnabled
+                | UserServiceError::MfaNotEnrolled
+                | UserServiceError::InvalidMfaCode
+                | UserServiceError::InvalidMfaChallenge
+                | UserServiceError::AccountLocked { .. }
+                | UserServiceError::InvalidAccessToken
+                | UserServiceError::AccessTokenNotFound { .. }
+                | UserServiceError::AccessTokenLimitReached { .. }
+                | UserServiceError::SessionNotFound { .. }
                 | UserServiceError::DomainValidation { .. }
         )
     }
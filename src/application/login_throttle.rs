@@ -0,0 +1,109 @@
+// Throttling of failed sign-in attempts
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::net::IpAddr;
+        use std::sync::Arc;
+        use chrono::{Duration, Utc};
+        use color_eyre::Result;
+        use sha2::{Digest, Sha256};
+        use crate::domain::models::login_throttle::LoginThrottlePolicy;
+        use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+
+        /// Counts failed sign-in attempts per account and per client address
+        /// and locks out keys that exceed the policy's limits.
+        ///
+        /// Keys are hashed, so the store never holds email or IP addresses
+        /// in the clear, and an unknown email is throttled exactly like a
+        /// registered one.
+        #[derive(Clone)]
+        pub struct LoginThrottle {
+            store: Arc<dyn LoginAttemptRepository>,
+            policy: LoginThrottlePolicy,
+        }
+
+        impl LoginThrottle {
+            #[must_use]
+            pub fn new(store: Arc<dyn LoginAttemptRepository>, policy: LoginThrottlePolicy) -> Self {
+                Self { store, policy }
+            }
+
+            fn account_key(email: &str) -> String {
+                let normalized = email.trim().to_lowercase();
+                format!("account:{}", hex::encode(Sha256::digest(normalized.as_bytes())))
+            }
+
+            fn ip_key(ip: IpAddr) -> String {
+                format!("ip:{}", hex::encode(Sha256::digest(ip.to_string().as_bytes())))
+            }
+
+            /// The keys a sign-in attempt is counted under, each with its
+            /// failure limit.
+            fn keys(&self, email: &str, ip: Option<IpAddr>) -> Vec<(String, u32)> {
+                let mut keys = vec![(Self::account_key(email), self.policy.max_failures_per_account)];
+                keys.extend(ip.map(|ip| (Self::ip_key(ip), self.policy.max_failures_per_ip)));
+                keys
+            }
+
+            /// Time left on the longest lockout covering the account or the
+            /// client address, if either is locked.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the store cannot be read.
+            pub async fn check(&self, email: &str, ip: Option<IpAddr>) -> Result<Option<Duration>> {
+                let now = Utc::now();
+                let mut remaining: Option<Duration> = None;
+                for (key, _) in self.keys(email, ip) {
+                    let lockout = self
+                        .store
+                        .get_login_attempts(&key)
+                        .await?
+                        .and_then(|attempts| attempts.remaining_lockout(now));
+                    remaining = remaining.max(lockout);
+                }
+                Ok(remaining)
+            }
+
+            /// Counts a failed attempt against the account and the client
+            /// address, locking whichever has gone over its limit.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the store cannot be updated.
+            pub async fn record_failure(&self, email: &str, ip: Option<IpAddr>) -> Result<()> {
+                let now = Utc::now();
+                let window_start = self.policy.window_start(now);
+                for (key, limit) in self.keys(email, ip) {
+                    let attempts = self.store.record_failed_login(&key, now, window_start).await?;
+                    if let Some(lockout) = self.policy.lockout_after(attempts.failed_count(), limit) {
+                        self.store.lock_login(&key, now + lockout).await?;
+                    }
+                }
+                Ok(())
+            }
+
+            /// Forgets the account's failures after a successful sign-in. The
+            /// client address keeps its count, so one valid account cannot be
+            /// used to reset an address that is guessing at others.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the store cannot be updated.
+            pub async fn record_success(&self, email: &str) -> Result<()> {
+                self.forget_account(email).await
+            }
+
+            /// Deletes everything recorded about the account's sign-ins.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the store cannot be updated.
+            pub async fn forget_account(&self, email: &str) -> Result<()> {
+                self.store.clear_login_attempts(&Self::account_key(email)).await
+            }
+        }
+    }
+}
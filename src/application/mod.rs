@@ -1,2 +1,4 @@
 pub mod errors;
+pub mod login_throttle;
+pub mod transaction_service;
 pub mod user_service;
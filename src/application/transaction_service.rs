@@ -0,0 +1,159 @@
+// Application services/use cases
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::application::errors::transaction_service_errors::TransactionServiceError;
+        use crate::domain::models::transaction::{CreateTransactionRequest, Transaction};
+        use crate::domain::repositories::transaction_repository::{
+            TransactionPage, TransactionQuery, TransactionRepository,
+        };
+        use crate::domain::value_objects::{Currency, Money, TransactionId, UserId};
+        use crate::domain::errors::transaction_errors::TransactionDomainError;
+        use async_trait::async_trait;
+        use leptos::logging;
+        use std::sync::Arc;
+
+        #[async_trait]
+        pub trait TransactionService: Send + Sync + 'static {
+            async fn create_transaction(&self, owner_id: &UserId, request: CreateTransactionRequest) -> Result<Transaction, TransactionServiceError>;
+            async fn get_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<Transaction, TransactionServiceError>;
+            async fn update_transaction(&self, owner_id: &UserId, id: &TransactionId, request: CreateTransactionRequest) -> Result<Transaction, TransactionServiceError>;
+            async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<(), TransactionServiceError>;
+            async fn list_transactions(&self, owner_id: &UserId, query: TransactionQuery) -> Result<TransactionPage, TransactionServiceError>;
+        }
+
+        pub struct TransactionServiceImpl {
+            transaction_repository: Arc<dyn TransactionRepository>,
+        }
+
+        impl TransactionServiceImpl {
+            #[must_use]
+            pub fn new(transaction_repository: Arc<dyn TransactionRepository>) -> Self {
+                Self { transaction_repository }
+            }
+
+            fn parse_amount(request: &CreateTransactionRequest) -> Result<Money, TransactionDomainError> {
+                let currency = Currency::from_code(&request.currency)?;
+                Ok(Money::from_minor(request.amount_minor, currency))
+            }
+
+            fn repository_error(operation: &str, e: &color_eyre::Report) -> TransactionServiceError {
+                TransactionServiceError::RepositoryError {
+                    operation: operation.to_string(),
+                    source: Box::new(std::io::Error::other(e.to_string())),
+                }
+            }
+
+            fn validate_query(query: &TransactionQuery) -> Result<(), TransactionServiceError> {
+                query
+                    .cursor()
+                    .map_err(|e| TransactionServiceError::InvalidQuery { message: e.to_string() })?;
+
+                if let (Some(from), Some(to)) = (query.booked_from, query.booked_to)
+                    && from > to
+                {
+                    return Err(TransactionServiceError::InvalidQuery {
+                        message: "'from' must not be after 'to'".to_string(),
+                    });
+                }
+
+                if let (Some(min), Some(max)) = (query.min_amount_minor, query.max_amount_minor)
+                    && min > max
+                {
+                    return Err(TransactionServiceError::InvalidQuery {
+                        message: "'min_amount' must not be greater than 'max_amount'".to_string(),
+                    });
+                }
+
+                Ok(())
+            }
+        }
+
+        #[async_trait]
+        impl TransactionService for TransactionServiceImpl {
+            async fn create_transaction(&self, owner_id: &UserId, request: CreateTransactionRequest) -> Result<Transaction, TransactionServiceError> {
+                let transaction = Transaction::new(
+                    owner_id.clone(),
+                    &request.account,
+                    Self::parse_amount(&request)?,
+                    request.booked_on,
+                    request.value_on,
+                    &request.payee,
+                    request.memo.as_deref(),
+                    &request.category,
+                    &request.tags,
+                )?;
+
+                self.transaction_repository
+                    .create_transaction(transaction.clone())
+                    .await
+                    .map_err(|e| Self::repository_error("create_transaction", &e))?;
+
+                logging::log!("Transaction {} created for user {}", transaction.id(), owner_id);
+                Ok(transaction)
+            }
+
+            async fn get_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<Transaction, TransactionServiceError> {
+                self.transaction_repository
+                    .get_transaction(owner_id, id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_transaction", &e))?
+                    .ok_or_else(|| TransactionServiceError::NotFound { id: id.to_string() })
+            }
+
+            async fn update_transaction(&self, owner_id: &UserId, id: &TransactionId, request: CreateTransactionRequest) -> Result<Transaction, TransactionServiceError> {
+                let mut transaction = self.get_transaction(owner_id, id).await?;
+
+                transaction.update(
+                    &request.account,
+                    Self::parse_amount(&request)?,
+                    request.booked_on,
+                    request.value_on,
+                    &request.payee,
+                    request.memo.as_deref(),
+                    &request.category,
+                    &request.tags,
+                )?;
+
+                self.transaction_repository
+                    .update_transaction(transaction.clone())
+                    .await
+                    .map_err(|e| Self::repository_error("update_transaction", &e))?;
+
+                Ok(transaction)
+            }
+
+            async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<(), TransactionServiceError> {
+                let deleted = self.transaction_repository
+                    .delete_transaction(owner_id, id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_transaction", &e))?;
+
+                if deleted {
+                    Ok(())
+                } else {
+                    Err(TransactionServiceError::NotFound { id: id.to_string() })
+                }
+            }
+
+            async fn list_transactions(&self, owner_id: &UserId, query: TransactionQuery) -> Result<TransactionPage, TransactionServiceError> {
+                Self::validate_query(&query)?;
+
+                self.transaction_repository
+                    .list_transactions(owner_id, &query)
+                    .await
+                    .map_err(|e| Self::repository_error("list_transactions", &e))
+            }
+        }
+
+        impl Clone for TransactionServiceImpl {
+            fn clone(&self) -> Self {
+                Self {
+                    transaction_repository: Arc::clone(&self.transaction_repository),
+                }
+            }
+        }
+    }
+}
@@ -4,60 +4,552 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
-        use crate::infrastructure::persistence::user_repository_cosmosdb::CosmosDbUserRepository;
+        use chrono::{Duration, Utc};
+        use rand::RngCore;
+        use subtle::ConstantTimeEq;
         use color_eyre::Result;
-        use std::sync::Arc;
+        use std::net::IpAddr;
+        use std::sync::{Arc, OnceLock};
+        use crate::application::login_throttle::LoginThrottle;
         use crate::domain::models::user::User;
-        use crate::domain::repositories::user_repository::UserRepository;
+        use crate::domain::repositories::user_repository::{DuplicateEmail, UserRepository};
         use async_trait::async_trait;
         use crate::infrastructure::auth::jwt_service::AuthService;
         use crate::domain::models::user::CreateUserRequest;
-        use crate::domain::models::user::AuthResponse;
+        use crate::domain::models::user::{AuthResponse, LoginResponse, RegisterResponse};
+        use crate::domain::models::mfa::{
+            ConfirmTotpRequest, DisableMfaRequest, MfaLoginRequest, RecoveryCodesResponse,
+            RegenerateRecoveryCodesRequest, StartTotpEnrollmentRequest, TotpEnrollmentResponse,
+        };
         use axum::Json;
         use leptos::logging;
         use crate::application::errors::user_service_errors::UserServiceError;
         use crate::domain::models::user::UserProfile;
+        use crate::domain::models::user::{
+            ChangeEmailRequest, ChangePasswordRequest, DeleteAccountRequest, ResetPasswordRequest,
+            UpdateProfileRequest,
+        };
+        use crate::domain::errors::common::FieldError;
+        use crate::domain::errors::user_errors::UserDomainError;
+        use crate::domain::repositories::transaction_repository::TransactionRepository;
+        use crate::domain::models::one_time_token::TokenPurpose;
+        use crate::domain::models::personal_access_token::{
+            AccessTokenSummary, CreateAccessTokenRequest, CreatedAccessTokenResponse,
+            PersonalAccessToken,
+        };
+        use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+        use crate::domain::models::refresh_token::RefreshTokenRecord;
+        use crate::domain::models::session::{Session, SessionSummary};
+        use crate::domain::repositories::session_repository::SessionRepository;
+        use crate::domain::repositories::one_time_token_repository::OneTimeTokenRepository;
+        use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+        use crate::domain::value_objects::{Email, UserId};
+        use crate::domain::models::password_policy::PasswordPolicy;
+        use crate::infrastructure::config::app_config::PasswordHashingConfig;
+        use crate::infrastructure::auth::jwt_service::TokenPair;
+        use crate::infrastructure::auth::mfa_service::MfaService;
+        use crate::infrastructure::mail::mail_sender::{MailMessage, MailSender};
+        use crate::infrastructure::web::middleware::client_info::ClientInfo;
+        use crate::infrastructure::web::websocket::SharedConnectionManager;
 
 
         #[async_trait]
-        pub trait UserService<'a>: UserRepository + Send + Sync + 'static {
-            async fn register_user(&self, user: CreateUserRequest, secret: &str) -> Result<Json<AuthResponse>, UserServiceError>;
-            async fn authenticate_user(&self, email: &str, password: &str, secret: &str) -> Result<Json<AuthResponse>, UserServiceError>;
+        pub trait UserService<'a>: UserRepository + RefreshTokenRepository + SessionRepository + Send + Sync + 'static {
+            /// Creates the account and sends a verification email. The user
+            /// is only signed in right away when verification is not required.
+            async fn register_user(&self, user: CreateUserRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<RegisterResponse>, UserServiceError>;
+            /// Checks the password. Accounts with two-factor authentication
+            /// get a challenge token to complete with [`Self::complete_mfa_login`]
+            /// instead of a token pair. Failed attempts are throttled per
+            /// account and per client address.
+            async fn authenticate_user(&self, email: &str, password: &str, client: &ClientInfo, auth: &AuthService) -> Result<Json<LoginResponse>, UserServiceError>;
+            /// Finishes a two-factor sign-in with an authenticator code or a
+            /// recovery code. Wrong codes count as failed sign-in attempts.
+            async fn complete_mfa_login(&self, request: MfaLoginRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError>;
+            /// Redeems an emailed verification token and marks the address
+            /// it was issued for as verified.
+            async fn verify_email(&self, token: &str, auth: &AuthService) -> Result<UserProfile, UserServiceError>;
+            /// Sends a new verification email if `email` belongs to an
+            /// unverified account. Succeeds either way, so callers cannot
+            /// probe for accounts.
+            async fn resend_verification_email(&self, email: &str, auth: &AuthService) -> Result<(), UserServiceError>;
+            /// Emails a password reset link if `email` belongs to an account.
+            /// The lookup and delivery run in the background, so neither the
+            /// result nor the response time reveals whether it does.
+            async fn request_password_reset(&self, email: &str);
+            /// Sets a new password using an emailed reset token and signs out
+            /// every session.
+            async fn reset_password(&self, request: ResetPasswordRequest) -> Result<(), UserServiceError>;
+            async fn get_profile(&self, user_id: &UserId) -> Result<UserProfile, UserServiceError>;
+            async fn update_profile(&self, user_id: &UserId, request: UpdateProfileRequest) -> Result<UserProfile, UserServiceError>;
+            /// Changes the password and signs out every other session. The
+            /// caller receives a fresh token pair.
+            async fn change_password(&self, user_id: &UserId, request: ChangePasswordRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError>;
+            /// Changes the email and signs out every other session. The
+            /// caller receives a fresh token pair carrying the new email.
+            async fn change_email(&self, user_id: &UserId, request: ChangeEmailRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError>;
+            /// Deletes the account together with its transactions, tokens,
+            /// sessions and recorded sign-in failures, and closes its open
+            /// connections.
+            async fn delete_account(&self, user_id: &UserId, request: DeleteAccountRequest) -> Result<(), UserServiceError>;
+            /// Generates an authenticator secret. It only takes effect once
+            /// confirmed with [`Self::confirm_totp_enrollment`].
+            async fn start_totp_enrollment(&self, user_id: &UserId, request: StartTotpEnrollmentRequest) -> Result<TotpEnrollmentResponse, UserServiceError>;
+            /// Enables two-factor authentication once the user proves their
+            /// authenticator works, and returns their recovery codes.
+            async fn confirm_totp_enrollment(&self, user_id: &UserId, request: ConfirmTotpRequest) -> Result<RecoveryCodesResponse, UserServiceError>;
+            /// Replaces all recovery codes with a new set.
+            async fn regenerate_recovery_codes(&self, user_id: &UserId, request: RegenerateRecoveryCodesRequest) -> Result<RecoveryCodesResponse, UserServiceError>;
+            /// Removes the authenticator and recovery codes. An enabled
+            /// authenticator can only be removed with one of its codes or a
+            /// recovery code; wrong codes count as failed sign-in attempts.
+            async fn disable_mfa(&self, user_id: &UserId, request: DisableMfaRequest) -> Result<(), UserServiceError>;
+            /// Creates a personal access token. The response is the only
+            /// place the token itself ever appears.
+            async fn create_access_token(&self, user_id: &UserId, request: CreateAccessTokenRequest) -> Result<CreatedAccessTokenResponse, UserServiceError>;
+            async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<AccessTokenSummary>, UserServiceError>;
+            async fn revoke_access_token(&self, user_id: &UserId, token_id: &str) -> Result<(), UserServiceError>;
+            /// Resolves a personal access token presented on an API request
+            /// to its owner and record, and notes when it was last used.
+            async fn authenticate_access_token(&self, token: &str) -> Result<(User, PersonalAccessToken), UserServiceError>;
+            /// The user's signed-in sessions that can still be resumed, with
+            /// `current_session_id` marked as the caller's own.
+            async fn list_active_sessions(&self, user_id: &UserId, current_session_id: Option<&str>) -> Result<Vec<SessionSummary>, UserServiceError>;
+            /// Signs out one session by revoking its refresh tokens.
+            async fn revoke_session(&self, user_id: &UserId, session_id: &str) -> Result<(), UserServiceError>;
+            /// Whether the session an access token was issued to still
+            /// exists, i.e. was neither signed out nor revoked.
+            async fn is_session_active(&self, user_id: &UserId, session_id: &str) -> Result<bool, UserServiceError>;
         }
+        /// How the service emails users about their account.
+        #[derive(Clone)]
+        pub struct AccountMail {
+            pub mail_sender: Arc<dyn MailSender>,
+            /// Base URL that links in emails point at.
+            pub public_url: String,
+            /// Whether users must verify their address before signing in.
+            pub require_verification: bool,
+        }
+
         pub struct UserServiceImpl {
-            user_repository: Arc<CosmosDbUserRepository>,
+            user_repository: Arc<dyn UserRepository>,
+            refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+            session_repository: Arc<dyn SessionRepository>,
+            transaction_repository: Arc<dyn TransactionRepository>,
+            one_time_token_repository: Arc<dyn OneTimeTokenRepository>,
+            access_token_repository: Arc<dyn PersonalAccessTokenRepository>,
+            password_policy: PasswordPolicy,
+            password_hashing: PasswordHashingConfig,
+            account_mail: AccountMail,
+            login_throttle: LoginThrottle,
+            /// Told about revoked sessions so their open WebSocket
+            /// connections drop their tokens.
+            connection_manager: SharedConnectionManager,
+            /// Hash checked when a sign-in names an unknown email, computed
+            /// with `password_hashing` on first use.
+            dummy_password_hash: Arc<OnceLock<String>>,
         }
         impl UserServiceImpl {
+            /// Personal access tokens a user may hold at once.
+            pub const MAX_ACCESS_TOKENS_PER_USER: usize = 50;
+            /// `last_used_at` is only rewritten once it is this stale, so a
+            /// busy script does not cost a write per request.
+            const ACCESS_TOKEN_LAST_USED_RESOLUTION_SECONDS: i64 = 60;
+
+            #[allow(clippy::too_many_arguments)]
             #[must_use]
-            pub fn new(user_repository: Arc<CosmosDbUserRepository>) -> Self {
-                Self { user_repository }
+            pub fn new(
+                user_repository: Arc<dyn UserRepository>,
+                refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+                session_repository: Arc<dyn SessionRepository>,
+                transaction_repository: Arc<dyn TransactionRepository>,
+                one_time_token_repository: Arc<dyn OneTimeTokenRepository>,
+                access_token_repository: Arc<dyn PersonalAccessTokenRepository>,
+                password_policy: PasswordPolicy,
+                password_hashing: PasswordHashingConfig,
+                account_mail: AccountMail,
+                login_throttle: LoginThrottle,
+                connection_manager: SharedConnectionManager,
+            ) -> Self {
+                Self {
+                    user_repository,
+                    refresh_token_repository,
+                    session_repository,
+                    transaction_repository,
+                    one_time_token_repository,
+                    access_token_repository,
+                    password_policy,
+                    password_hashing,
+                    account_mail,
+                    login_throttle,
+                    connection_manager,
+                    dummy_password_hash: Arc::new(OnceLock::new()),
+                }
             }
-        }
 
-        #[async_trait]
-        impl UserService<'_> for UserServiceImpl {
-            async fn register_user(&self, user: CreateUserRequest, secret: &str) -> Result<Json<AuthResponse>, UserServiceError> {
+            fn repository_error(operation: &str, e: &color_eyre::Report) -> UserServiceError {
+                UserServiceError::RepositoryError {
+                    operation: operation.to_string(),
+                    source: Box::new(std::io::Error::other(e.to_string())),
+                }
+            }
 
-                logging::log!("Registering user: {}", user.email);
+            async fn load_user(&self, user_id: &UserId) -> Result<User, UserServiceError> {
+                self.get_user_by_id(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_id", &e))?
+                    .ok_or_else(|| UserServiceError::AccountNotFound {
+                        user_id: user_id.to_string(),
+                    })
+            }
 
-                // Check if user already exists
-                match self.get_user_by_email(&user.email).await {
-                    Ok(Some(_)) => {
-                        return Err(UserServiceError::UserAlreadyExists {
-                            email: user.email
-                        });
+            fn verify_current_password(user: &User, password: &str, field: &'static str) -> Result<(), UserServiceError> {
+                if AuthService::verify_password(password, user.password_hash().as_str()).unwrap_or(false) {
+                    Ok(())
+                } else {
+                    Err(UserServiceError::IncorrectPassword { field })
+                }
+            }
+
+            async fn save_user(&self, user: User) -> Result<(), UserServiceError> {
+                self.update_user(user).await.map_err(|e| match e.downcast_ref::<DuplicateEmail>() {
+                    Some(duplicate) => UserServiceError::UserAlreadyExists {
+                        email: duplicate.email.clone(),
                     },
-                    Ok(None) => {},
+                    None => Self::repository_error("update_user", &e),
+                })
+            }
+
+            /// Revokes every refresh token of the user. Open WebSocket
+            /// connections of the revoked sessions are told to sign out.
+            async fn sign_out_everywhere(&self, user_id: &UserId) -> Result<(), UserServiceError> {
+                let revoked = self.list_sessions(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("list_sessions", &e))?;
+                self.revoke_all_refresh_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("revoke_all_refresh_tokens", &e))?;
+                for session in &revoked {
+                    self.connection_manager.force_logout(user_id.as_str(), session.id()).await;
+                }
+                Ok(())
+            }
+
+            /// Signs out every session and issues a new pair, keeping only
+            /// the calling session signed in.
+            async fn restart_sessions(&self, user: User, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError> {
+                self.sign_out_everywhere(user.id()).await?;
+
+                let token_pair = self.issue_token_pair(&user, client, auth).await?;
+                Ok(Json(AuthResponse {
+                    access_token: token_pair.access_token,
+                    refresh_token: token_pair.refresh_token,
+                    token_type: "Bearer".to_string(),
+                    expires_in: token_pair.expires_in,
+                    user: UserProfile::from(user),
+                }))
+            }
+
+            /// Refuses the sign-in while the account or the client address is
+            /// locked out.
+            async fn ensure_sign_in_allowed(&self, email: &str, client_ip: Option<IpAddr>) -> Result<(), UserServiceError> {
+                let lockout = self.login_throttle
+                    .check(email, client_ip)
+                    .await
+                    .map_err(|e| Self::repository_error("get_login_attempts", &e))?;
+                match lockout {
+                    Some(remaining) => {
+                        logging::warn!("Sign-in refused during lockout: {email}");
+                        let milliseconds = u64::try_from(remaining.num_milliseconds()).unwrap_or(0);
+                        Err(UserServiceError::AccountLocked {
+                            retry_after_seconds: milliseconds.div_ceil(1000).max(1),
+                        })
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            /// Counts a wrong password or second-factor code against the
+            /// account and the client address, then passes `result` on.
+            async fn throttle_failure<T>(&self, email: &str, client_ip: Option<IpAddr>, result: Result<T, UserServiceError>) -> Result<T, UserServiceError> {
+                if matches!(result, Err(UserServiceError::InvalidCredentials | UserServiceError::InvalidMfaCode))
+                    && let Err(e) = self.login_throttle.record_failure(email, client_ip).await
+                {
+                    logging::warn!("Failed to record failed sign-in for {email}: {e:?}");
+                }
+                result
+            }
+
+            /// Looks up the account for `email` and checks its password. Every
+            /// mismatch, including an unknown email, is `InvalidCredentials`.
+            async fn verify_credentials(&self, email: &str, password: &str) -> Result<User, UserServiceError> {
+                // A malformed email cannot match any account
+                let Ok(email) = Email::new(email) else {
+                    return Err(UserServiceError::InvalidCredentials);
+                };
+
+                let user = match self.get_user_by_email(&email).await {
+                    Ok(Some(user)) => user,
+                    // Don't reveal whether the account exists, not even
+                    // through how long the answer takes
+                    Ok(None) => {
+                        let _ = AuthService::verify_password(password, self.dummy_password_hash());
+                        return Err(UserServiceError::InvalidCredentials);
+                    }
                     Err(e) => {
                         return Err(UserServiceError::RepositoryError {
-                            operation: "check_user_exists".to_string(),
+                            operation: "get_user_by_email".to_string(),
                             source: Box::new(std::io::Error::other(e.to_string())),
                         });
                     }
+                };
+
+                if AuthService::verify_password(password, user.password_hash().as_str()).unwrap_or(false) {
+                    Ok(user)
+                } else {
+                    Err(UserServiceError::InvalidCredentials)
+                }
+            }
+
+            /// A hash of a random password made with the configured algorithm
+            /// and parameters, so checking it costs as much as checking a
+            /// real account's.
+            fn dummy_password_hash(&self) -> &str {
+                self.dummy_password_hash.get_or_init(|| {
+                    let mut password = [0u8; 16];
+                    rand::rngs::OsRng.fill_bytes(&mut password);
+                    AuthService::hash_password(&hex::encode(password), &self.password_hashing)
+                        .unwrap_or_default()
+                })
+            }
+
+            /// Replaces the user's password hash if it was made with an
+            /// outdated algorithm or parameters. Only called right after the
+            /// password was verified; failures are logged and the old hash
+            /// stays usable.
+            async fn upgrade_password_hash(&self, user: User, password: &str) -> User {
+                if !AuthService::needs_rehash(user.password_hash().as_str(), &self.password_hashing) {
+                    return user;
+                }
+
+                let hash = match AuthService::hash_password(password, &self.password_hashing) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        logging::warn!("Failed to rehash password for user {}: {e:?}", user.id());
+                        return user;
+                    }
+                };
+                let mut upgraded = user.clone();
+                if let Err(e) = upgraded.change_password_hash(hash) {
+                    logging::warn!("Failed to rehash password for user {}: {e:?}", user.id());
+                    return user;
                 }
+                match self.save_user(upgraded.clone()).await {
+                    Ok(()) => {
+                        logging::log!("Upgraded password hash for user {}", user.id());
+                        upgraded
+                    }
+                    Err(e) => {
+                        logging::warn!("Failed to store rehashed password for user {}: {e:?}", user.id());
+                        user
+                    }
+                }
+            }
+
+            /// Checks a second-factor code for a user with two-factor
+            /// authentication enabled. Six digits are treated as an
+            /// authenticator code, anything else as a recovery code; either
+            /// kind is accepted only once.
+            async fn verify_second_factor(&self, user: &mut User, code: &str) -> Result<(), UserServiceError> {
+                let Some(totp) = user.totp().filter(|totp| totp.is_enabled()) else {
+                    return Err(UserServiceError::MfaNotEnrolled);
+                };
+
+                if MfaService::is_totp_code(code) {
+                    let step = MfaService::verify_code(totp.secret(), code, totp.last_used_step())
+                        .map_err(|e| UserServiceError::AuthServiceError {
+                            source: Box::new(e),
+                        })?
+                        .ok_or(UserServiceError::InvalidMfaCode)?;
+                    // A concurrent request with the same code may have
+                    // recorded this step since the user was loaded
+                    let recorded = self.record_totp_step(user, step)
+                        .await
+                        .map_err(|e| Self::repository_error("record_totp_step", &e))?;
+                    if !recorded {
+                        return Err(UserServiceError::InvalidMfaCode);
+                    }
+                    user.record_totp_step(step);
+                    return Ok(());
+                }
+
+                self.one_time_token_repository
+                    .consume_one_time_token(&MfaService::recovery_code_key(user.id(), code), TokenPurpose::MfaRecovery)
+                    .await
+                    .map_err(|e| Self::repository_error("consume_one_time_token", &e))?
+                    .ok_or(UserServiceError::InvalidMfaCode)?;
+                logging::log!("Recovery code used by user {}", user.id());
+                Ok(())
+            }
+
+            /// Invalidates the user's recovery codes and stores a new set.
+            async fn issue_recovery_codes(&self, user_id: &UserId) -> Result<RecoveryCodesResponse, UserServiceError> {
+                self.one_time_token_repository
+                    .revoke_one_time_tokens(user_id, TokenPurpose::MfaRecovery)
+                    .await
+                    .map_err(|e| Self::repository_error("revoke_one_time_tokens", &e))?;
+
+                let (recovery_codes, records) = MfaService::generate_recovery_codes(user_id)
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+                for record in records {
+                    self.one_time_token_repository
+                        .store_one_time_token(record)
+                        .await
+                        .map_err(|e| Self::repository_error("store_one_time_token", &e))?;
+                }
+
+                Ok(RecoveryCodesResponse { recovery_codes })
+            }
+
+            /// Issues a token pair for a user who has passed every sign-in step
+            /// and forgets the account's failed attempts.
+            async fn sign_in(&self, user: User, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError> {
+                if let Err(e) = self.login_throttle.record_success(user.email().as_str()).await {
+                    logging::warn!("Failed to clear failed sign-ins for user {}: {e:?}", user.id());
+                }
+
+                let token_pair = self.issue_token_pair(&user, client, auth).await?;
+                Ok(Json(AuthResponse {
+                    access_token: token_pair.access_token,
+                    refresh_token: token_pair.refresh_token,
+                    token_type: "Bearer".to_string(),
+                    expires_in: token_pair.expires_in,
+                    user: UserProfile::from(user),
+                }))
+            }
+
+            /// Issues a verification token for the user's current address and
+            /// emails a link to it.
+            async fn send_verification_email(&self, user: &User, auth: &AuthService) -> Result<(), UserServiceError> {
+                let (token, record) = auth.generate_email_verification_token(user)
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+                self.one_time_token_repository
+                    .store_one_time_token(record)
+                    .await
+                    .map_err(|e| Self::repository_error("store_one_time_token", &e))?;
+
+                let link = format!("{}/verify-email?token={token}", self.account_mail.public_url);
+                self.send_mail(
+                    user,
+                    "Verify your email address",
+                    format!(
+                        "Please confirm your email address for Finance Tracker by opening this link:\n\n\
+                         {link}\n\n\
+                         The link expires in {} hours. If you did not request this, you can ignore this email.\n",
+                        AuthService::EMAIL_VERIFICATION_TOKEN_DURATION_HOURS,
+                    ),
+                )
+                .await
+            }
+
+            /// Issues a reset token for the account registered to `email`, if
+            /// any, and emails a link to it.
+            async fn send_password_reset_email(&self, email: &str) -> Result<(), UserServiceError> {
+                let Ok(email) = Email::new(email) else {
+                    return Ok(());
+                };
+                let Some(user) = self.get_user_by_email(&email)
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_email", &e))?
+                else {
+                    return Ok(());
+                };
+
+                let (token, record) = AuthService::generate_password_reset_token(&user)
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+                self.one_time_token_repository
+                    .store_one_time_token(record)
+                    .await
+                    .map_err(|e| Self::repository_error("store_one_time_token", &e))?;
+
+                let link = format!("{}/reset-password?token={token}", self.account_mail.public_url);
+                self.send_mail(
+                    &user,
+                    "Reset your password",
+                    format!(
+                        "Someone asked to reset the password of your Finance Tracker account. \
+                         To choose a new password, open this link:\n\n\
+                         {link}\n\n\
+                         The link expires in {} minutes and works once. If you did not ask for this, \
+                         you can ignore this email; your password has not been changed.\n",
+                        AuthService::PASSWORD_RESET_TOKEN_DURATION_MINUTES,
+                    ),
+                )
+                .await
+            }
+
+            async fn send_mail(&self, user: &User, subject: &str, text: String) -> Result<(), UserServiceError> {
+                let message = MailMessage {
+                    to: user.email().to_string(),
+                    subject: subject.to_string(),
+                    body: format!("Hi {},\n\n{text}", user.first_name()),
+                };
+                self.account_mail
+                    .mail_sender
+                    .send(message)
+                    .await
+                    .map_err(|e| UserServiceError::MailDeliveryFailed {
+                        source: e.into(),
+                    })
+            }
+
+            /// Sends the verification email, logging rather than failing on
+            /// error: the account change has already been saved, and the user
+            /// can ask for another email.
+            async fn try_send_verification_email(&self, user: &User, auth: &AuthService) {
+                if let Err(e) = self.send_verification_email(user, auth).await {
+                    logging::warn!("Failed to send verification email to user {}: {e:?}", user.id());
+                }
+            }
+
+            /// Generates a token pair for the user and records its refresh
+            /// token so it can later be rotated or revoked, and the session
+            /// it starts for `client`.
+            async fn issue_token_pair(&self, user: &User, client: &ClientInfo, auth: &AuthService) -> Result<TokenPair, UserServiceError> {
+                let token_pair = auth.generate_token_pair(user)
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+
+                self.store_refresh_token(token_pair.refresh_token_record(user.id())).await
+                    .map_err(|e| UserServiceError::RepositoryError {
+                        operation: "store_refresh_token".to_string(),
+                        source: Box::new(std::io::Error::other(e.to_string())),
+                    })?;
+                self.create_session(token_pair.session(user.id(), client))
+                    .await
+                    .map_err(|e| Self::repository_error("create_session", &e))?;
+
+                Ok(token_pair)
+            }
+        }
+
+        #[async_trait]
+        impl UserService<'_> for UserServiceImpl {
+            async fn register_user(&self, user: CreateUserRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<RegisterResponse>, UserServiceError> {
+
+                logging::log!("Registering user: {}", user.email);
+
+                user.validate(&self.password_policy)?;
 
                 // Hash password
-                let hash = AuthService::hash_password(&user.password)
+                let hash = AuthService::hash_password(&user.password, &self.password_hashing)
                     .map_err(|e| UserServiceError::AuthServiceError {
                         source: Box::new(e),
                     })?;
@@ -69,82 +561,496 @@ cfg_if! {
                     &user.last_name,
                 )?;
 
-                // Create user
+                // The store enforces unique emails, so a concurrent registration
+                // for the same address fails here rather than creating a duplicate
                 self.create_user(user.clone()).await
-                    .map_err(|e| UserServiceError::RepositoryError {
-                        operation: "create_user".to_string(),
-                        source: Box::new(std::io::Error::other(e.to_string())),
+                    .map_err(|e| match e.downcast_ref::<DuplicateEmail>() {
+                        Some(duplicate) => UserServiceError::UserAlreadyExists {
+                            email: duplicate.email.clone(),
+                        },
+                        None => UserServiceError::RepositoryError {
+                            operation: "create_user".to_string(),
+                            source: Box::new(std::io::Error::other(e.to_string())),
+                        },
                     })?;
 
                 logging::log!("User registered successfully: {}", user.email().as_str());
 
-                        let token_pair = AuthService::generate_token_pair(&user, secret)
-                    .map_err(|e| UserServiceError::AuthServiceError {
-                        source: Box::new(e),
-                    })?;
+                self.try_send_verification_email(&user, auth).await;
 
-                let user_profile = UserProfile {
-                    id: user.id().to_string(),
-                    email: user.email().to_string(),
-                    first_name: user.first_name().to_string(),
-                    last_name: user.last_name().to_string(),
-                };
+                if self.account_mail.require_verification {
+                    return Ok(axum::Json(RegisterResponse::VerificationPending {
+                        message: "Check your inbox for a link to verify your email address".to_string(),
+                        user: UserProfile::from(user),
+                    }));
+                }
+
+                let token_pair = self.issue_token_pair(&user, client, auth).await?;
 
-                Ok(axum::Json(AuthResponse {
+                Ok(axum::Json(RegisterResponse::Authenticated(AuthResponse {
                     access_token: token_pair.access_token,
                     refresh_token: token_pair.refresh_token,
                     token_type: "Bearer".to_string(),
                     expires_in: token_pair.expires_in,
-                    user: user_profile,
-                }))
+                    user: UserProfile::from(user),
+                })))
             }
 
-            async fn authenticate_user(&self, email: &str, password: &str, secret: &str) -> Result<Json<AuthResponse>, UserServiceError> {
-                // Find user by email
-
+            async fn authenticate_user(&self, email: &str, password: &str, client: &ClientInfo, auth: &AuthService) -> Result<Json<LoginResponse>, UserServiceError> {
                 logging::log!("Authenticating user: {}", email);
 
-                let user = match self.get_user_by_email(email).await {
-                    Ok(Some(user)) => user,
-                    Ok(None) => {
-                        return Err(UserServiceError::UserNotFound {
-                            email: email.to_string(),
-                        });
-                    },
-                    Err(e) => {
-                        return Err(UserServiceError::RepositoryError {
-                            operation: "get_user_by_email".to_string(),
-                            source: Box::new(std::io::Error::other(e.to_string())),
-                        });
-                    }
-                };
+                self.ensure_sign_in_allowed(email, client.ip).await?;
+                let result = self.verify_credentials(email, password).await;
+                let user = self.throttle_failure(email, client.ip, result).await?;
+                let user = self.upgrade_password_hash(user, password).await;
 
-                // Verify password
-                if AuthService::verify_password(password, user.password_hash().as_str()).is_err() {
-                    return Err(UserServiceError::InvalidCredentials);
+                if self.account_mail.require_verification && !user.is_email_verified() {
+                    return Err(UserServiceError::EmailNotVerified);
+                }
+
+                if user.is_mfa_enabled() {
+                    let (mfa_token, record, expires_in) = auth.generate_mfa_challenge_token(&user)
+                        .map_err(|e| UserServiceError::AuthServiceError {
+                            source: Box::new(e),
+                        })?;
+                    self.one_time_token_repository
+                        .store_one_time_token(record)
+                        .await
+                        .map_err(|e| Self::repository_error("store_one_time_token", &e))?;
+                    logging::log!("Password accepted, awaiting second factor: {}", user.email().as_str());
+                    return Ok(axum::Json(LoginResponse::MfaRequired { mfa_token, expires_in }));
                 }
 
                 logging::log!("User authenticated successfully: {}", user.email().as_str());
 
-                let token_pair = AuthService::generate_token_pair(&user, secret)
+                let Json(response) = self.sign_in(user, client, auth).await?;
+                Ok(axum::Json(LoginResponse::Authenticated(response)))
+            }
+
+            async fn complete_mfa_login(&self, request: MfaLoginRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError> {
+                let claims = auth.verify_mfa_challenge_token(&request.mfa_token)
+                    .map_err(|_| UserServiceError::InvalidMfaChallenge)?;
+                let user_id = UserId::new(claims.sub).map_err(|_| UserServiceError::InvalidMfaChallenge)?;
+                let mut user = self.get_user_by_id(&user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_id", &e))?
+                    .ok_or(UserServiceError::InvalidMfaChallenge)?;
+
+                // The account changed since the password step
+                if user.email().as_str() != claims.email || !user.is_mfa_enabled() {
+                    return Err(UserServiceError::InvalidMfaChallenge);
+                }
+
+                let email = user.email().to_string();
+                self.ensure_sign_in_allowed(&email, client.ip).await?;
+                let result = self.verify_second_factor(&mut user, &request.code).await;
+                self.throttle_failure(&email, client.ip, result).await?;
+
+                // Consumed only after a correct code, so a mistyped one does
+                // not end the sign-in, but a challenge never yields two sessions
+                self.one_time_token_repository
+                    .consume_one_time_token(&claims.jti, TokenPurpose::MfaChallenge)
+                    .await
+                    .map_err(|e| Self::repository_error("consume_one_time_token", &e))?
+                    .filter(|record| record.user_id() == user.id())
+                    .ok_or(UserServiceError::InvalidMfaChallenge)?;
+
+                logging::log!("User authenticated successfully: {}", user.email().as_str());
+                self.sign_in(user, client, auth).await
+            }
+
+            async fn verify_email(&self, token: &str, auth: &AuthService) -> Result<UserProfile, UserServiceError> {
+                let claims = auth.verify_email_verification_token(token)
+                    .map_err(|_| UserServiceError::InvalidVerificationToken)?;
+                let record = self.one_time_token_repository
+                    .consume_one_time_token(&claims.jti, TokenPurpose::EmailVerification)
+                    .await
+                    .map_err(|e| Self::repository_error("consume_one_time_token", &e))?
+                    .ok_or(UserServiceError::InvalidVerificationToken)?;
+
+                let mut user = self.get_user_by_id(record.user_id())
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_id", &e))?
+                    .ok_or(UserServiceError::InvalidVerificationToken)?;
+
+                // A link sent to a previous address must not verify the new one
+                if user.id().as_str() != claims.sub || user.email().as_str() != claims.email {
+                    return Err(UserServiceError::InvalidVerificationToken);
+                }
+
+                if !user.is_email_verified() {
+                    user.mark_email_verified();
+                    self.save_user(user.clone()).await?;
+                    logging::log!("Email verified for user {}", user.id());
+                }
+
+                Ok(UserProfile::from(user))
+            }
+
+            async fn resend_verification_email(&self, email: &str, auth: &AuthService) -> Result<(), UserServiceError> {
+                let Ok(email) = Email::new(email) else {
+                    return Ok(());
+                };
+
+                let user = self.get_user_by_email(&email)
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_email", &e))?;
+                if let Some(user) = user.filter(|user| !user.is_email_verified()) {
+                    self.try_send_verification_email(&user, auth).await;
+                }
+
+                Ok(())
+            }
+
+            async fn request_password_reset(&self, email: &str) {
+                let service = self.clone();
+                let email = email.to_string();
+                tokio::spawn(async move {
+                    if let Err(e) = service.send_password_reset_email(&email).await {
+                        logging::warn!("Failed to send password reset email: {e:?}");
+                    }
+                });
+            }
+
+            async fn reset_password(&self, request: ResetPasswordRequest) -> Result<(), UserServiceError> {
+                let token_id = AuthService::hash_one_time_token(&request.token);
+                let record = self.one_time_token_repository
+                    .get_one_time_token(&token_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_one_time_token", &e))?
+                    .filter(|record| record.purpose() == TokenPurpose::PasswordReset && record.is_usable())
+                    .ok_or(UserServiceError::InvalidResetToken)?;
+
+                let mut user = self.get_user_by_id(record.user_id())
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_id", &e))?
+                    .ok_or(UserServiceError::InvalidResetToken)?;
+                request.validate(&self.password_policy, &user)?;
+
+                let hash = AuthService::hash_password(&request.new_password, &self.password_hashing)
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+
+                // Consumed only once the new password is accepted, so a
+                // rejected password does not use up the link
+                self.one_time_token_repository
+                    .consume_one_time_token(&token_id, TokenPurpose::PasswordReset)
+                    .await
+                    .map_err(|e| Self::repository_error("consume_one_time_token", &e))?
+                    .ok_or(UserServiceError::InvalidResetToken)?;
+
+                user.change_password_hash(hash).map_err(UserDomainError::from)?;
+                // Following the emailed link proves control of the address
+                if !user.is_email_verified() {
+                    user.mark_email_verified();
+                }
+                self.save_user(user.clone()).await?;
+                self.sign_out_everywhere(user.id()).await?;
+
+                logging::log!("Password reset for user {}", user.id());
+                Ok(())
+            }
+
+            async fn get_profile(&self, user_id: &UserId) -> Result<UserProfile, UserServiceError> {
+                Ok(UserProfile::from(self.load_user(user_id).await?))
+            }
+
+            async fn update_profile(&self, user_id: &UserId, request: UpdateProfileRequest) -> Result<UserProfile, UserServiceError> {
+                request.validate()?;
+
+                let mut user = self.load_user(user_id).await?;
+                user.update_name(&request.first_name, &request.last_name)
+                    .map_err(UserDomainError::from)?;
+                self.save_user(user.clone()).await?;
+
+                Ok(UserProfile::from(user))
+            }
+
+            async fn change_password(&self, user_id: &UserId, request: ChangePasswordRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError> {
+                let mut user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.current_password, "current_password")?;
+                request.validate(&self.password_policy, &user)?;
+
+                let hash = AuthService::hash_password(&request.new_password, &self.password_hashing)
                     .map_err(|e| UserServiceError::AuthServiceError {
                         source: Box::new(e),
                     })?;
+                user.change_password_hash(hash).map_err(UserDomainError::from)?;
+                self.save_user(user.clone()).await?;
+
+                logging::log!("Password changed for user {}", user.id());
+                self.restart_sessions(user, client, auth).await
+            }
+
+            async fn change_email(&self, user_id: &UserId, request: ChangeEmailRequest, client: &ClientInfo, auth: &AuthService) -> Result<Json<AuthResponse>, UserServiceError> {
+                let mut user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.current_password, "current_password")?;
 
-                let user_profile = UserProfile {
-                    id: user.id().to_string(),
-                    email: user.email().to_string(),
-                    first_name: user.first_name().to_string(),
-                    last_name: user.last_name().to_string(),
+                user.change_email(&request.new_email).map_err(|e| {
+                    let error = UserDomainError::from(e);
+                    UserDomainError::Validation {
+                        errors: vec![FieldError::new("new_email", error.code(), error.to_string())],
+                    }
+                })?;
+                self.save_user(user.clone()).await?;
+
+                logging::log!("Email changed for user {}", user.id());
+                self.try_send_verification_email(&user, auth).await;
+                self.restart_sessions(user, client, auth).await
+            }
+
+            async fn delete_account(&self, user_id: &UserId, request: DeleteAccountRequest) -> Result<(), UserServiceError> {
+                let user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.password, "password")?;
+
+                // Dependent data goes first so a failure part-way leaves an
+                // account the user can still sign in to and delete again.
+                self.transaction_repository
+                    .delete_transactions_for_owner(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_transactions_for_owner", &e))?;
+                self.access_token_repository
+                    .delete_all_access_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_all_access_tokens", &e))?;
+                self.one_time_token_repository
+                    .delete_all_one_time_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_all_one_time_tokens", &e))?;
+                self.login_throttle
+                    .forget_account(user.email().as_str())
+                    .await
+                    .map_err(|e| Self::repository_error("clear_login_attempts", &e))?;
+                // Sign-in state is deleted rather than revoked, since nothing
+                // should outlive the account, and open connections are closed.
+                let sessions = self.list_sessions(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("list_sessions", &e))?;
+                self.delete_all_refresh_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_all_refresh_tokens", &e))?;
+                for session in &sessions {
+                    self.connection_manager.force_logout(user_id.as_str(), session.id()).await;
+                }
+                self.delete_user(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_user", &e))?;
+
+                logging::log!("Deleted account {}", user_id);
+                Ok(())
+            }
+
+            async fn start_totp_enrollment(&self, user_id: &UserId, request: StartTotpEnrollmentRequest) -> Result<TotpEnrollmentResponse, UserServiceError> {
+                let mut user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.current_password, "current_password")?;
+                if user.is_mfa_enabled() {
+                    return Err(UserServiceError::MfaAlreadyEnabled);
+                }
+
+                let secret = MfaService::generate_secret()
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+                let otpauth_uri = MfaService::otpauth_uri(&secret, user.email().as_str())
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?;
+                user.begin_totp_enrollment(secret.clone());
+                self.save_user(user).await?;
+
+                Ok(TotpEnrollmentResponse { secret, otpauth_uri })
+            }
+
+            async fn confirm_totp_enrollment(&self, user_id: &UserId, request: ConfirmTotpRequest) -> Result<RecoveryCodesResponse, UserServiceError> {
+                let mut user = self.load_user(user_id).await?;
+                if user.is_mfa_enabled() {
+                    return Err(UserServiceError::MfaAlreadyEnabled);
+                }
+                let Some(totp) = user.totp() else {
+                    return Err(UserServiceError::MfaNotEnrolled);
                 };
 
-                Ok(axum::Json(AuthResponse {
-                    access_token: token_pair.access_token,
-                    refresh_token: token_pair.refresh_token,
-                    token_type: "Bearer".to_string(),
-                    expires_in: token_pair.expires_in,
-                    user: user_profile,
-                }))
+                let step = MfaService::verify_code(totp.secret(), &request.code, totp.last_used_step())
+                    .map_err(|e| UserServiceError::AuthServiceError {
+                        source: Box::new(e),
+                    })?
+                    .ok_or(UserServiceError::InvalidMfaCode)?;
+                user.confirm_totp(step);
+                self.save_user(user).await?;
+
+                logging::log!("Two-factor authentication enabled for user {}", user_id);
+                self.issue_recovery_codes(user_id).await
+            }
+
+            async fn regenerate_recovery_codes(&self, user_id: &UserId, request: RegenerateRecoveryCodesRequest) -> Result<RecoveryCodesResponse, UserServiceError> {
+                let user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.current_password, "current_password")?;
+                if !user.is_mfa_enabled() {
+                    return Err(UserServiceError::MfaNotEnrolled);
+                }
+
+                self.issue_recovery_codes(user_id).await
+            }
+
+            async fn disable_mfa(&self, user_id: &UserId, request: DisableMfaRequest) -> Result<(), UserServiceError> {
+                let mut user = self.load_user(user_id).await?;
+                Self::verify_current_password(&user, &request.current_password, "current_password")?;
+                if user.totp().is_none() {
+                    return Err(UserServiceError::MfaNotEnrolled);
+                }
+
+                // A stolen session and password alone must not be enough to
+                // strip the second factor
+                if user.is_mfa_enabled() {
+                    let email = user.email().to_string();
+                    self.ensure_sign_in_allowed(&email, None).await?;
+                    let result = self.verify_second_factor(&mut user, &request.code).await;
+                    self.throttle_failure(&email, None, result).await?;
+                }
+
+                user.remove_totp();
+                self.save_user(user).await?;
+                self.one_time_token_repository
+                    .revoke_one_time_tokens(user_id, TokenPurpose::MfaRecovery)
+                    .await
+                    .map_err(|e| Self::repository_error("revoke_one_time_tokens", &e))?;
+
+                logging::log!("Two-factor authentication disabled for user {}", user_id);
+                Ok(())
+            }
+
+            async fn create_access_token(&self, user_id: &UserId, request: CreateAccessTokenRequest) -> Result<CreatedAccessTokenResponse, UserServiceError> {
+                request.validate()?;
+                self.load_user(user_id).await?;
+
+                let existing = self.access_token_repository
+                    .list_access_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("list_access_tokens", &e))?;
+                if existing.len() >= Self::MAX_ACCESS_TOKENS_PER_USER {
+                    return Err(UserServiceError::AccessTokenLimitReached {
+                        limit: Self::MAX_ACCESS_TOKENS_PER_USER,
+                    });
+                }
+
+                let mut scopes = request.scopes;
+                scopes.sort_by_key(|scope| scope.as_str());
+                scopes.dedup();
+                let expires_at = request
+                    .expires_in_days
+                    .map(|days| Utc::now() + Duration::days(i64::from(days)));
+                let (token, record) = AuthService::generate_personal_access_token(
+                    user_id,
+                    request.name.trim().to_string(),
+                    scopes,
+                    expires_at,
+                )
+                .map_err(|e| UserServiceError::AuthServiceError {
+                    source: Box::new(e),
+                })?;
+                let summary = AccessTokenSummary::from(&record);
+                self.access_token_repository
+                    .create_access_token(record)
+                    .await
+                    .map_err(|e| Self::repository_error("create_access_token", &e))?;
+
+                logging::log!("Created personal access token {} for user {}", summary.id, user_id);
+                Ok(CreatedAccessTokenResponse { token, summary })
+            }
+
+            async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<AccessTokenSummary>, UserServiceError> {
+                let tokens = self.access_token_repository
+                    .list_access_tokens(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("list_access_tokens", &e))?;
+                Ok(tokens.iter().map(AccessTokenSummary::from).collect())
+            }
+
+            async fn revoke_access_token(&self, user_id: &UserId, token_id: &str) -> Result<(), UserServiceError> {
+                let deleted = self.access_token_repository
+                    .delete_access_token(user_id, token_id)
+                    .await
+                    .map_err(|e| Self::repository_error("delete_access_token", &e))?;
+                if !deleted {
+                    return Err(UserServiceError::AccessTokenNotFound {
+                        token_id: token_id.to_string(),
+                    });
+                }
+
+                logging::log!("Revoked personal access token {} of user {}", token_id, user_id);
+                Ok(())
+            }
+
+            async fn authenticate_access_token(&self, token: &str) -> Result<(User, PersonalAccessToken), UserServiceError> {
+                let token_id = AuthService::personal_access_token_id(token)
+                    .ok_or(UserServiceError::InvalidAccessToken)?;
+                let token_hash = AuthService::hash_one_time_token(token);
+                let record = self.access_token_repository
+                    .get_access_token(token_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_access_token", &e))?
+                    // The id is public, so the digest must not be compared in
+                    // a way that leaks how much of it matched
+                    .filter(|record| {
+                        bool::from(record.token_hash().as_bytes().ct_eq(token_hash.as_bytes()))
+                            && !record.is_expired()
+                    })
+                    .ok_or(UserServiceError::InvalidAccessToken)?;
+
+                let user = self.get_user_by_id(record.user_id())
+                    .await
+                    .map_err(|e| Self::repository_error("get_user_by_id", &e))?
+                    .ok_or(UserServiceError::InvalidAccessToken)?;
+
+                let now = Utc::now();
+                let stale = record.last_used_at().is_none_or(|last_used_at| {
+                    now - last_used_at
+                        >= Duration::seconds(Self::ACCESS_TOKEN_LAST_USED_RESOLUTION_SECONDS)
+                });
+                // Bookkeeping only; a failed write must not fail the request
+                if stale
+                    && let Err(e) = self.access_token_repository
+                        .record_access_token_use(record.id(), now)
+                        .await
+                {
+                    logging::warn!("Failed to record use of access token {}: {e:?}", record.id());
+                }
+
+                Ok((user, record))
+            }
+
+            async fn list_active_sessions(&self, user_id: &UserId, current_session_id: Option<&str>) -> Result<Vec<SessionSummary>, UserServiceError> {
+                let sessions = self.list_sessions(user_id)
+                    .await
+                    .map_err(|e| Self::repository_error("list_sessions", &e))?;
+                Ok(sessions
+                    .iter()
+                    .filter(|session| !session.is_expired())
+                    .map(|session| SessionSummary::new(session, current_session_id))
+                    .collect())
+            }
+
+            async fn revoke_session(&self, user_id: &UserId, session_id: &str) -> Result<(), UserServiceError> {
+                self.get_session(user_id, session_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_session", &e))?
+                    .ok_or_else(|| UserServiceError::SessionNotFound {
+                        session_id: session_id.to_string(),
+                    })?;
+                self.revoke_refresh_token_family(user_id, session_id)
+                    .await
+                    .map_err(|e| Self::repository_error("revoke_refresh_token_family", &e))?;
+
+                logging::log!("Revoked session {} of user {}", session_id, user_id);
+                Ok(())
+            }
+
+            async fn is_session_active(&self, user_id: &UserId, session_id: &str) -> Result<bool, UserServiceError> {
+                let session = self.get_session(user_id, session_id)
+                    .await
+                    .map_err(|e| Self::repository_error("get_session", &e))?;
+                Ok(session.is_some_and(|session| !session.is_expired()))
             }
         }
 
@@ -156,9 +1062,84 @@ cfg_if! {
                 self.user_repository.create_user(user).await
             }
 
-            async fn get_user_by_email(&self, email: &str) -> Result<Option<User>> {
+            async fn get_user_by_email(&self, email: &Email) -> Result<Option<User>> {
                 self.user_repository.get_user_by_email(email).await
-            }    // Additional methods can be implemented as needed
+            }
+
+            async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>> {
+                self.user_repository.get_user_by_id(id).await
+            }
+
+            async fn update_user(&self, user: User) -> Result<()> {
+                self.user_repository.update_user(user).await
+            }
+
+            async fn delete_user(&self, id: &UserId) -> Result<bool> {
+                self.user_repository.delete_user(id).await
+            }
+
+            async fn record_totp_step(&self, user: &User, step: i64) -> Result<bool> {
+                self.user_repository.record_totp_step(user, step).await
+            }
+        }
+
+        #[async_trait]
+        impl RefreshTokenRepository for UserServiceImpl {
+            async fn store_refresh_token(&self, record: RefreshTokenRecord) -> Result<()> {
+                self.refresh_token_repository.store_refresh_token(record).await
+            }
+
+            async fn get_refresh_token(&self, user_id: &UserId, token_id: &str) -> Result<Option<RefreshTokenRecord>> {
+                self.refresh_token_repository.get_refresh_token(user_id, token_id).await
+            }
+
+            async fn mark_refresh_token_used(&self, user_id: &UserId, token_id: &str, replaced_by: &str) -> Result<bool> {
+                self.refresh_token_repository.mark_refresh_token_used(user_id, token_id, replaced_by).await
+            }
+
+            // A session lives exactly as long as its refresh-token family, so
+            // every way of revoking a family also ends its session.
+            async fn revoke_refresh_token_family(&self, user_id: &UserId, family_id: &str) -> Result<()> {
+                self.refresh_token_repository.revoke_refresh_token_family(user_id, family_id).await?;
+                self.session_repository.delete_session(user_id, family_id).await
+            }
+
+            async fn revoke_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.refresh_token_repository.revoke_all_refresh_tokens(user_id).await?;
+                self.session_repository.delete_all_sessions(user_id).await
+            }
+
+            async fn delete_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.refresh_token_repository.delete_all_refresh_tokens(user_id).await?;
+                self.session_repository.delete_all_sessions(user_id).await
+            }
+        }
+
+        #[async_trait]
+        impl SessionRepository for UserServiceImpl {
+            async fn create_session(&self, session: Session) -> Result<()> {
+                self.session_repository.create_session(session).await
+            }
+
+            async fn get_session(&self, user_id: &UserId, session_id: &str) -> Result<Option<Session>> {
+                self.session_repository.get_session(user_id, session_id).await
+            }
+
+            async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>> {
+                self.session_repository.list_sessions(user_id).await
+            }
+
+            async fn record_session_activity(&self, user_id: &UserId, session_id: &str, seen_at: chrono::DateTime<Utc>, ip_address: Option<String>, expires_at: chrono::DateTime<Utc>) -> Result<bool> {
+                self.session_repository.record_session_activity(user_id, session_id, seen_at, ip_address, expires_at).await
+            }
+
+            async fn delete_session(&self, user_id: &UserId, session_id: &str) -> Result<()> {
+                self.session_repository.delete_session(user_id, session_id).await
+            }
+
+            async fn delete_all_sessions(&self, user_id: &UserId) -> Result<()> {
+                self.session_repository.delete_all_sessions(user_id).await
+            }
         }
 
         // CLone implementation for UserServiceImpl
@@ -166,7 +1147,507 @@ cfg_if! {
             fn clone(&self) -> Self {
                 Self {
                     user_repository: Arc::clone(&self.user_repository),
+                    refresh_token_repository: Arc::clone(&self.refresh_token_repository),
+                    session_repository: Arc::clone(&self.session_repository),
+                    transaction_repository: Arc::clone(&self.transaction_repository),
+                    one_time_token_repository: Arc::clone(&self.one_time_token_repository),
+                    access_token_repository: Arc::clone(&self.access_token_repository),
+                    password_policy: self.password_policy.clone(),
+                    password_hashing: self.password_hashing.clone(),
+                    account_mail: self.account_mail.clone(),
+                    login_throttle: self.login_throttle.clone(),
+                    connection_manager: Arc::clone(&self.connection_manager),
+                    dummy_password_hash: Arc::clone(&self.dummy_password_hash),
+                }
+            }
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        pub(crate) mod tests {
+            use super::*;
+            use crate::domain::models::login_throttle::LoginThrottlePolicy;
+            use crate::domain::models::personal_access_token::TokenScope;
+            use crate::infrastructure::auth::mfa_service::tests::code_at;
+            use crate::infrastructure::auth::signing_keys::JwtKeyRing;
+            use crate::infrastructure::config::app_config::TokenConfig;
+            use crate::infrastructure::mail::mail_sender_stdout::StdoutMailSender;
+            use crate::infrastructure::persistence::repositories::Repositories;
+            use crate::infrastructure::web::websocket::{ConnectionManager, WebSocketMessage};
+
+            pub(crate) const PASSWORD: &str = "Tangerine-Harbor-42";
+
+            /// A service over fresh in-memory repositories. Hashing uses the
+            /// cheapest Argon2id parameters so tests stay fast.
+            pub(crate) fn service() -> UserServiceImpl {
+                service_over(Repositories::in_memory())
+            }
+
+            /// Like [`service`], over the given repositories.
+            pub(crate) fn service_over(repositories: Repositories) -> UserServiceImpl {
+                UserServiceImpl::new(
+                    repositories.users,
+                    repositories.refresh_tokens,
+                    repositories.sessions,
+                    repositories.transactions,
+                    repositories.one_time_tokens,
+                    repositories.access_tokens,
+                    PasswordPolicy::default(),
+                    PasswordHashingConfig {
+                        argon2_memory_kib: 64,
+                        argon2_iterations: 1,
+                        ..PasswordHashingConfig::default()
+                    },
+                    AccountMail {
+                        mail_sender: Arc::new(StdoutMailSender::new("noreply@example.com".parse().unwrap())),
+                        public_url: "http://localhost:3000".to_string(),
+                        require_verification: false,
+                    },
+                    LoginThrottle::new(repositories.login_attempts, LoginThrottlePolicy::default()),
+                    Arc::new(ConnectionManager::new()),
+                )
+            }
+
+            pub(crate) fn auth() -> AuthService {
+                AuthService::new(
+                    JwtKeyRing::hmac("test-secret-that-is-long-enough-for-hs256"),
+                    TokenConfig::default(),
+                )
+            }
+
+            fn sign_up(email: &str) -> CreateUserRequest {
+                CreateUserRequest {
+                    email: email.to_string(),
+                    password: PASSWORD.to_string(),
+                    first_name: "Ada".to_string(),
+                    last_name: "Byron".to_string(),
+                }
+            }
+
+            /// Registers `email` with [`PASSWORD`] and returns the token pair
+            /// it was signed in with.
+            pub(crate) async fn register(service: &UserServiceImpl, auth: &AuthService, email: &str) -> AuthResponse {
+                match service.register_user(sign_up(email), &ClientInfo::default(), auth).await.unwrap().0 {
+                    RegisterResponse::Authenticated(response) => response,
+                    RegisterResponse::VerificationPending { .. } => panic!("verification is not required"),
+                }
+            }
+
+            /// Registers the same email from two tasks at once; the store's
+            /// unique email constraint must let exactly one of them through.
+            async fn registers_an_email_once_under_concurrent_sign_ups(service: UserServiceImpl) {
+                let service = Arc::new(service);
+                let auth = Arc::new(auth());
+                let attempts: Vec<_> = (0..2)
+                    .map(|_| {
+                        let (service, auth) = (Arc::clone(&service), Arc::clone(&auth));
+                        tokio::spawn(async move {
+                            service.register_user(sign_up("ada@example.com"), &ClientInfo::default(), &auth).await
+                        })
+                    })
+                    .collect();
+                let mut results = Vec::new();
+                for attempt in attempts {
+                    results.push(attempt.await.unwrap());
+                }
+
+                assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+                assert!(results.iter().any(|result| matches!(
+                    result,
+                    Err(UserServiceError::UserAlreadyExists { email }) if email == "ada@example.com"
+                )));
+            }
+
+            #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+            async fn concurrent_sign_ups_create_one_account() {
+                registers_an_email_once_under_concurrent_sign_ups(service()).await;
+            }
+
+            #[cfg(feature = "sqlite")]
+            #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+            async fn concurrent_sign_ups_create_one_sqlite_account() {
+                registers_an_email_once_under_concurrent_sign_ups(service_over(Repositories::sqlite_in_memory().await)).await;
+            }
+
+            #[tokio::test]
+            async fn unknown_email_is_checked_against_a_dummy_hash() {
+                let service = service();
+                let auth = auth();
+                register(&service, &auth, "ada@example.com").await;
+
+                let wrong_password = service.verify_credentials("ada@example.com", "Wrong-Password-1").await;
+                let unknown_email = service.verify_credentials("nobody@example.com", PASSWORD).await;
+
+                assert!(matches!(wrong_password, Err(UserServiceError::InvalidCredentials)));
+                assert!(matches!(unknown_email, Err(UserServiceError::InvalidCredentials)));
+                // The dummy hash costs as much to check as a real one.
+                let dummy = service.dummy_password_hash.get().unwrap();
+                assert!(!AuthService::needs_rehash(dummy, &service.password_hashing));
+            }
+
+            #[tokio::test]
+            async fn delete_account_removes_tokens_and_sign_in_failures() {
+                let mut service = service();
+                let connection_manager = Arc::new(ConnectionManager::new());
+                service.connection_manager = Arc::clone(&connection_manager);
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let user = service.load_user(&user_id).await.unwrap();
+                let refresh_token_id = auth.verify_token(&registered.refresh_token).unwrap().jti;
+                let session_id = auth.verify_access_token(&registered.access_token).unwrap().family_id.unwrap();
+                let connection_id = uuid::Uuid::new_v4();
+                let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+                connection_manager.add_connection(connection_id, sender, None).await;
+                connection_manager
+                    .authenticate_connection(&connection_id, user_id.to_string(), Some(session_id.clone()))
+                    .await;
+
+                let (_, reset_token) = AuthService::generate_password_reset_token(&user).unwrap();
+                let reset_token_id = reset_token.id().to_string();
+                service.one_time_token_repository.store_one_time_token(reset_token).await.unwrap();
+                for _ in 0..5 {
+                    let _ = service
+                        .authenticate_user("ada@example.com", "Wrong-Password-1", &ClientInfo::default(), &auth)
+                        .await;
+                }
+                assert!(service.login_throttle.check("ada@example.com", None).await.unwrap().is_some());
+
+                service
+                    .delete_account(&user_id, DeleteAccountRequest { password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+
+                assert!(service.get_user_by_id(&user_id).await.unwrap().is_none());
+                assert!(service.one_time_token_repository.get_one_time_token(&reset_token_id).await.unwrap().is_none());
+                assert!(service.login_throttle.check("ada@example.com", None).await.unwrap().is_none());
+                assert!(service.list_sessions(&user_id).await.unwrap().is_empty());
+                // Deleted rather than merely revoked.
+                assert!(service.get_refresh_token(&user_id, &refresh_token_id).await.unwrap().is_none());
+                let mut forced = Vec::new();
+                while let Ok(message) = receiver.try_recv() {
+                    if let WebSocketMessage::ForceLogout { session_id } = message {
+                        forced.push(session_id);
+                    }
+                }
+                assert_eq!(forced, vec![session_id]);
+            }
+
+            #[tokio::test]
+            async fn verification_token_can_be_redeemed_once() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user = service.load_user(&UserId::new(registered.user.id).unwrap()).await.unwrap();
+                let (token, record) = auth.generate_email_verification_token(&user).unwrap();
+                service.one_time_token_repository.store_one_time_token(record).await.unwrap();
+
+                let (first, second) = tokio::join!(
+                    service.verify_email(&token, &auth),
+                    service.verify_email(&token, &auth),
+                );
+
+                assert!(first.is_ok() != second.is_ok());
+                let replayed = service.verify_email(&token, &auth).await;
+                assert!(matches!(replayed, Err(UserServiceError::InvalidVerificationToken)));
+            }
+
+            #[tokio::test]
+            async fn disabling_mfa_requires_a_second_factor() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let enrollment = service
+                    .start_totp_enrollment(&user_id, StartTotpEnrollmentRequest { current_password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+                let code = code_at(&enrollment.secret, 0);
+                let recovery = service
+                    .confirm_totp_enrollment(&user_id, ConfirmTotpRequest { code: code.clone() })
+                    .await
+                    .unwrap();
+                let disable = |code: &str| DisableMfaRequest {
+                    current_password: PASSWORD.to_string(),
+                    code: code.to_string(),
+                };
+
+                let without_code = service.disable_mfa(&user_id, disable("")).await;
+                // The code that confirmed enrollment cannot be replayed.
+                let replayed = service.disable_mfa(&user_id, disable(&code)).await;
+                assert!(matches!(without_code, Err(UserServiceError::InvalidMfaCode)));
+                assert!(matches!(replayed, Err(UserServiceError::InvalidMfaCode)));
+                assert!(service.load_user(&user_id).await.unwrap().is_mfa_enabled());
+
+                service.disable_mfa(&user_id, disable(&recovery.recovery_codes[0])).await.unwrap();
+
+                assert!(service.load_user(&user_id).await.unwrap().totp().is_none());
+            }
+
+            #[tokio::test]
+            async fn recovery_codes_work_once_and_are_replaced_on_regeneration() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let enrollment = service
+                    .start_totp_enrollment(&user_id, StartTotpEnrollmentRequest { current_password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+                let first = service
+                    .confirm_totp_enrollment(&user_id, ConfirmTotpRequest { code: code_at(&enrollment.secret, 0) })
+                    .await
+                    .unwrap();
+                let mut user = service.load_user(&user_id).await.unwrap();
+
+                service.verify_second_factor(&mut user, &first.recovery_codes[0]).await.unwrap();
+                let reused = service.verify_second_factor(&mut user, &first.recovery_codes[0]).await;
+                assert!(matches!(reused, Err(UserServiceError::InvalidMfaCode)));
+
+                let second = service
+                    .regenerate_recovery_codes(&user_id, RegenerateRecoveryCodesRequest { current_password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+                let old = service.verify_second_factor(&mut user, &first.recovery_codes[1]).await;
+                assert!(matches!(old, Err(UserServiceError::InvalidMfaCode)));
+                service.verify_second_factor(&mut user, &second.recovery_codes[1]).await.unwrap();
+            }
+
+            #[tokio::test]
+            async fn an_authenticator_code_is_accepted_once_across_requests() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let enrollment = service
+                    .start_totp_enrollment(&user_id, StartTotpEnrollmentRequest { current_password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+                let _ = service
+                    .confirm_totp_enrollment(&user_id, ConfirmTotpRequest { code: code_at(&enrollment.secret, 0) })
+                    .await
+                    .unwrap();
+                // Two requests that loaded the user before either saved it.
+                let mut first = service.load_user(&user_id).await.unwrap();
+                let mut second = first.clone();
+                let code = code_at(&enrollment.secret, 1);
+
+                service.verify_second_factor(&mut first, &code).await.unwrap();
+                let replayed = service.verify_second_factor(&mut second, &code).await;
+
+                assert!(matches!(replayed, Err(UserServiceError::InvalidMfaCode)));
+            }
+
+            #[tokio::test]
+            async fn an_mfa_challenge_can_be_completed_once() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let enrollment = service
+                    .start_totp_enrollment(&user_id, StartTotpEnrollmentRequest { current_password: PASSWORD.to_string() })
+                    .await
+                    .unwrap();
+                let recovery = service
+                    .confirm_totp_enrollment(&user_id, ConfirmTotpRequest { code: code_at(&enrollment.secret, 0) })
+                    .await
+                    .unwrap();
+                let login = service
+                    .authenticate_user("ada@example.com", PASSWORD, &ClientInfo::default(), &auth)
+                    .await
+                    .unwrap();
+                let LoginResponse::MfaRequired { mfa_token, .. } = login.0 else {
+                    panic!("MFA is enabled");
+                };
+                let complete = |code: &str| MfaLoginRequest {
+                    mfa_token: mfa_token.clone(),
+                    code: code.to_string(),
+                };
+
+                // A wrong code leaves the challenge open.
+                let wrong = service.complete_mfa_login(complete("000000"), &ClientInfo::default(), &auth).await;
+                assert!(matches!(wrong, Err(UserServiceError::InvalidMfaCode)));
+                let _ = service
+                    .complete_mfa_login(complete(&recovery.recovery_codes[0]), &ClientInfo::default(), &auth)
+                    .await
+                    .unwrap();
+
+                let replayed = service
+                    .complete_mfa_login(complete(&recovery.recovery_codes[1]), &ClientInfo::default(), &auth)
+                    .await;
+                assert!(matches!(replayed, Err(UserServiceError::InvalidMfaChallenge)));
+            }
+
+            #[tokio::test]
+            async fn sign_in_upgrades_an_outdated_hash() {
+                let mut service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let old_hash = service.load_user(&user_id).await.unwrap().password_hash().as_str().to_string();
+
+                service.password_hashing.argon2_iterations = 2;
+                let _ = service
+                    .authenticate_user("ada@example.com", PASSWORD, &ClientInfo::default(), &auth)
+                    .await
+                    .unwrap();
+
+                let new_hash = service.load_user(&user_id).await.unwrap().password_hash().as_str().to_string();
+                assert_ne!(new_hash, old_hash);
+                assert!(!AuthService::needs_rehash(&new_hash, &service.password_hashing));
+                assert!(AuthService::verify_password(PASSWORD, &new_hash).unwrap());
+            }
+
+            #[tokio::test]
+            async fn access_tokens_carry_only_their_scopes() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let created = service
+                    .create_access_token(&user_id, CreateAccessTokenRequest {
+                        name: "Budget export".to_string(),
+                        scopes: vec![TokenScope::TransactionsRead, TokenScope::TransactionsRead],
+                        expires_in_days: Some(30),
+                    })
+                    .await
+                    .unwrap();
+
+                let (user, record) = service.authenticate_access_token(&created.token).await.unwrap();
+
+                assert_eq!(user.id(), &user_id);
+                assert_eq!(record.scopes(), [TokenScope::TransactionsRead]);
+            }
+
+            #[tokio::test]
+            async fn access_tokens_with_a_wrong_secret_are_rejected() {
+                let service = service();
+                let auth = auth();
+                let registered = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(registered.user.id).unwrap();
+                let created = service
+                    .create_access_token(&user_id, CreateAccessTokenRequest {
+                        name: "Budget export".to_string(),
+                        scopes: TokenScope::ALL.to_vec(),
+                        expires_in_days: None,
+                    })
+                    .await
+                    .unwrap();
+                let (prefix, secret) = created.token.rsplit_once('_').unwrap();
+                let forged = format!("{prefix}_{}", "0".repeat(secret.len()));
+
+                let result = service.authenticate_access_token(&forged).await;
+                assert!(matches!(result, Err(UserServiceError::InvalidAccessToken)));
+
+                service.revoke_access_token(&user_id, &created.summary.id).await.unwrap();
+                let result = service.authenticate_access_token(&created.token).await;
+                assert!(matches!(result, Err(UserServiceError::InvalidAccessToken)));
+            }
+
+            #[tokio::test]
+            async fn revoked_sessions_are_no_longer_active() {
+                let service = service();
+                let auth = auth();
+                let first = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(first.user.id).unwrap();
+                let first_session = auth.verify_access_token(&first.access_token).unwrap().family_id.unwrap();
+                assert!(service.is_session_active(&user_id, &first_session).await.unwrap());
+
+                service.revoke_session(&user_id, &first_session).await.unwrap();
+
+                assert!(!service.is_session_active(&user_id, &first_session).await.unwrap());
+                assert!(!service.is_session_active(&user_id, "unknown").await.unwrap());
+            }
+
+            #[tokio::test]
+            async fn changing_the_password_signs_out_other_sessions() {
+                let mut service = service();
+                let connection_manager = Arc::new(ConnectionManager::new());
+                service.connection_manager = Arc::clone(&connection_manager);
+                let auth = auth();
+                let first = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(first.user.id).unwrap();
+                let old_session = auth.verify_access_token(&first.access_token).unwrap().family_id.unwrap();
+                let connection_id = uuid::Uuid::new_v4();
+                let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+                connection_manager.add_connection(connection_id, sender, None).await;
+                connection_manager
+                    .authenticate_connection(&connection_id, user_id.to_string(), Some(old_session.clone()))
+                    .await;
+
+                let changed = service
+                    .change_password(
+                        &user_id,
+                        ChangePasswordRequest {
+                            current_password: PASSWORD.to_string(),
+                            new_password: "Marigold-Lantern-77".to_string(),
+                        },
+                        &ClientInfo::default(),
+                        &auth,
+                    )
+                    .await
+                    .unwrap();
+                let new_session = auth.verify_access_token(&changed.access_token).unwrap().family_id.unwrap();
+
+                assert!(!service.is_session_active(&user_id, &old_session).await.unwrap());
+                assert!(service.is_session_active(&user_id, &new_session).await.unwrap());
+                let mut forced = Vec::new();
+                while let Ok(message) = receiver.try_recv() {
+                    if let WebSocketMessage::ForceLogout { session_id } = message {
+                        forced.push(session_id);
+                    }
+                }
+                assert_eq!(forced, vec![old_session]);
+            }
+
+            #[tokio::test]
+            async fn resetting_the_password_signs_out_every_session() {
+                let mut service = service();
+                let connection_manager = Arc::new(ConnectionManager::new());
+                service.connection_manager = Arc::clone(&connection_manager);
+                let auth = auth();
+                let first = register(&service, &auth, "ada@example.com").await;
+                let user_id = UserId::new(first.user.id).unwrap();
+                let second = service
+                    .authenticate_user("ada@example.com", PASSWORD, &ClientInfo::default(), &auth)
+                    .await
+                    .unwrap();
+                let LoginResponse::Authenticated(second) = second.0 else {
+                    panic!("MFA is not enabled");
+                };
+                let sessions: Vec<String> = [&first.access_token, &second.access_token]
+                    .into_iter()
+                    .map(|token| auth.verify_access_token(token).unwrap().family_id.unwrap())
+                    .collect();
+                let connection_id = uuid::Uuid::new_v4();
+                let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+                connection_manager.add_connection(connection_id, sender, None).await;
+                connection_manager
+                    .authenticate_connection(&connection_id, user_id.to_string(), Some(sessions[0].clone()))
+                    .await;
+
+                let user = service.load_user(&user_id).await.unwrap();
+                let (reset_token, record) = AuthService::generate_password_reset_token(&user).unwrap();
+                service.one_time_token_repository.store_one_time_token(record).await.unwrap();
+                service
+                    .reset_password(ResetPasswordRequest {
+                        token: reset_token,
+                        new_password: "Marigold-Lantern-77".to_string(),
+                    })
+                    .await
+                    .unwrap();
+
+                for session in &sessions {
+                    assert!(!service.is_session_active(&user_id, session).await.unwrap());
+                }
+                assert!(service.list_sessions(&user_id).await.unwrap().is_empty());
+                let mut forced = Vec::new();
+                while let Ok(message) = receiver.try_recv() {
+                    if let WebSocketMessage::ForceLogout { session_id } = message {
+                        forced.push(session_id);
+                    }
                 }
+                assert_eq!(forced, vec![sessions[0].clone()]);
             }
         }
     }
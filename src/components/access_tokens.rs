@@ -0,0 +1,315 @@
+use crate::domain::errors::common::FieldError;
+use crate::domain::models::personal_access_token::{
+    AccessTokenSummary, CreateAccessTokenRequest, CreatedAccessTokenResponse, TokenScope,
+};
+use crate::infrastructure::auth::auth_context::AuthState;
+use chrono::{DateTime, Utc};
+use gloo_net::http::{Request, Response};
+use leptos::{prelude::*, task::spawn_local};
+
+const TOKENS_URL: &str = "/api/users/me/tokens";
+const CARD_CLASS: &str = "bg-white rounded-xl shadow-sm border border-gray-200 p-6 space-y-4";
+const INPUT_CLASS: &str = "block w-full px-4 py-3 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 text-gray-900";
+const BUTTON_CLASS: &str = "py-3 px-6 rounded-2xl text-white font-semibold bg-gradient-to-r from-violet-600 to-blue-600 hover:from-violet-700 hover:to-blue-700 disabled:opacity-50";
+
+/// A failed call to the account API, as shown to the user.
+pub(crate) struct ApiError {
+    pub(crate) message: String,
+    field_errors: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub(crate) fn new(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            field_errors: Vec::new(),
+        }
+    }
+}
+
+/// The `Authorization` header for the signed-in session, refreshing the
+/// access token if needed.
+pub(crate) async fn bearer(auth: &AuthState) -> Result<String, ApiError> {
+    auth.get_valid_access_token()
+        .await
+        .map(|token| format!("Bearer {token}"))
+        .ok_or_else(|| ApiError::new("Your session has expired. Please sign in again."))
+}
+
+/// Passes successful responses through and turns everything else into the
+/// message and field errors the server returned.
+pub(crate) async fn check(
+    response: Result<Response, gloo_net::Error>,
+) -> Result<Response, ApiError> {
+    let response =
+        response.map_err(|_| ApiError::new("Network error occurred. Please try again."))?;
+    if response.ok() {
+        return Ok(response);
+    }
+
+    let body = response
+        .json::<serde_json::Value>()
+        .await
+        .unwrap_or_default();
+    Err(ApiError {
+        message: body["error"]
+            .as_str()
+            .unwrap_or("Something went wrong. Please try again.")
+            .to_string(),
+        field_errors: serde_json::from_value(body["errors"].clone()).unwrap_or_default(),
+    })
+}
+
+fn format_date(at: Option<DateTime<Utc>>, otherwise: &str) -> String {
+    at.map_or_else(
+        || otherwise.to_string(),
+        |at| at.format("%Y-%m-%d").to_string(),
+    )
+}
+
+/// Lists the user's personal access tokens, creates new ones and revokes
+/// them. A new token is shown once, right after it is created.
+#[component]
+#[allow(clippy::must_use_candidate)]
+#[allow(clippy::too_many_lines)]
+pub fn AccessTokensPage() -> impl IntoView {
+    let auth = StoredValue::new(AuthState::new());
+    let (tokens, set_tokens) = signal(Vec::<AccessTokenSummary>::new());
+    let (name, set_name) = signal(String::new());
+    let (read, set_read) = signal(true);
+    let (write, set_write) = signal(false);
+    let (expiry, set_expiry) = signal("90".to_string());
+    let (created, set_created) = signal(None::<String>);
+    let (saving, set_saving) = signal(false);
+    let (error, set_error) = signal(None::<String>);
+    let (field_errors, set_field_errors) = signal(Vec::<FieldError>::new());
+
+    let show_error = move |e: ApiError| {
+        if e.field_errors.is_empty() {
+            set_error.set(Some(e.message));
+        } else {
+            set_field_errors.set(e.field_errors);
+        }
+    };
+
+    // Effects only run in the browser, where the refresh token is stored
+    Effect::new(move |_| {
+        spawn_local(async move {
+            let result = async {
+                let authorization = bearer(&auth.get_value()).await?;
+                let response = check(
+                    Request::get(TOKENS_URL)
+                        .header("Authorization", &authorization)
+                        .send()
+                        .await,
+                )
+                .await?;
+                response
+                    .json::<Vec<AccessTokenSummary>>()
+                    .await
+                    .map_err(|_| ApiError::new("Unexpected response from the server"))
+            }
+            .await;
+            match result {
+                Ok(list) => set_tokens.set(list),
+                Err(e) => show_error(e),
+            }
+        });
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error.set(None);
+        set_field_errors.set(Vec::new());
+        set_created.set(None);
+
+        let scopes = [
+            (read.get(), TokenScope::TransactionsRead),
+            (write.get(), TokenScope::TransactionsWrite),
+        ]
+        .into_iter()
+        .filter_map(|(selected, scope)| selected.then_some(scope))
+        .collect();
+        let request = CreateAccessTokenRequest {
+            name: name.get(),
+            scopes,
+            // "never" does not parse and leaves the token without expiry
+            expires_in_days: expiry.get().parse().ok(),
+        };
+
+        set_saving.set(true);
+        spawn_local(async move {
+            let result = async {
+                let authorization = bearer(&auth.get_value()).await?;
+                let request = Request::post(TOKENS_URL)
+                    .header("Authorization", &authorization)
+                    .json(&request)
+                    .map_err(|_| ApiError::new("Failed to serialize request data"))?;
+                let response = check(request.send().await).await?;
+                response
+                    .json::<CreatedAccessTokenResponse>()
+                    .await
+                    .map_err(|_| ApiError::new("Unexpected response from the server"))
+            }
+            .await;
+            match result {
+                Ok(response) => {
+                    set_created.set(Some(response.token));
+                    set_name.set(String::new());
+                    set_tokens.update(|tokens| tokens.insert(0, response.summary));
+                }
+                Err(e) => show_error(e),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    let revoke = move |id: String| {
+        set_error.set(None);
+        spawn_local(async move {
+            let result = async {
+                let authorization = bearer(&auth.get_value()).await?;
+                check(
+                    Request::delete(&format!("{TOKENS_URL}/{id}"))
+                        .header("Authorization", &authorization)
+                        .send()
+                        .await,
+                )
+                .await
+            }
+            .await;
+            match result {
+                Ok(_) => set_tokens.update(|tokens| tokens.retain(|token| token.id != id)),
+                Err(e) => show_error(e),
+            }
+        });
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-50">
+            <div class="max-w-3xl mx-auto py-8 px-4 space-y-6">
+                <div class="flex items-center justify-between">
+                    <div>
+                        <h1 class="text-2xl font-bold text-gray-900">"Personal access tokens"</h1>
+                        <p class="text-sm text-gray-600">
+                            "Tokens let scripts and integrations use the API on your behalf."
+                        </p>
+                    </div>
+                    <a href="/dashboard" class="text-sm font-semibold text-violet-600 hover:text-violet-700 hover:underline">
+                        "Back to dashboard"
+                    </a>
+                </div>
+
+                <Show when=move || error.get().is_some()>
+                    <p class="rounded-2xl bg-red-50 border border-red-200/50 p-4 text-sm font-medium text-red-800">
+                        {move || error.get().unwrap_or_default()}
+                    </p>
+                </Show>
+                <Show when=move || created.get().is_some()>
+                    <div class="rounded-2xl bg-emerald-50 border border-emerald-200/50 p-4 space-y-2">
+                        <p class="text-sm font-medium text-emerald-800">
+                            "Copy your new token now. You will not be able to see it again."
+                        </p>
+                        <code class="block break-all rounded-lg bg-white p-3 text-sm text-gray-900">
+                            {move || created.get().unwrap_or_default()}
+                        </code>
+                    </div>
+                </Show>
+
+                <form class=CARD_CLASS on:submit=on_submit>
+                    <h2 class="text-lg font-semibold text-gray-900">"New token"</h2>
+                    <input
+                        type="text"
+                        required
+                        class=INPUT_CLASS
+                        placeholder="What is this token for?"
+                        prop:value=name
+                        on:input=move |ev| set_name.set(event_target_value(&ev))
+                    />
+                    <fieldset class="space-y-2">
+                        <legend class="text-sm font-medium text-gray-700">"Scopes"</legend>
+                        <label class="flex items-center space-x-2 text-sm text-gray-700">
+                            <input
+                                type="checkbox"
+                                prop:checked=read
+                                on:change=move |ev| set_read.set(event_target_checked(&ev))
+                            />
+                            <span>{TokenScope::TransactionsRead.as_str()}" — view transactions"</span>
+                        </label>
+                        <label class="flex items-center space-x-2 text-sm text-gray-700">
+                            <input
+                                type="checkbox"
+                                prop:checked=write
+                                on:change=move |ev| set_write.set(event_target_checked(&ev))
+                            />
+                            <span>{TokenScope::TransactionsWrite.as_str()}" — create, edit and delete transactions"</span>
+                        </label>
+                    </fieldset>
+                    <label class="block text-sm font-medium text-gray-700">
+                        "Expires"
+                        <select
+                            class=INPUT_CLASS
+                            prop:value=expiry
+                            on:change=move |ev| set_expiry.set(event_target_value(&ev))
+                        >
+                            <option value="7">"In 7 days"</option>
+                            <option value="30">"In 30 days"</option>
+                            <option value="90">"In 90 days"</option>
+                            <option value="365">"In a year"</option>
+                            <option value="never">"Never"</option>
+                        </select>
+                    </label>
+                    <For
+                        each=move || field_errors.get()
+                        key=|error| error.code.clone()
+                        children=|error| view! { <p class="text-sm text-red-600">{error.message}</p> }
+                    />
+                    <button type="submit" disabled=saving class=BUTTON_CLASS>
+                        {move || if saving.get() { "Creating..." } else { "Create token" }}
+                    </button>
+                </form>
+
+                <div class=CARD_CLASS>
+                    <h2 class="text-lg font-semibold text-gray-900">"Your tokens"</h2>
+                    <Show
+                        when=move || !tokens.get().is_empty()
+                        fallback=|| view! { <p class="text-sm text-gray-500">"You have no access tokens."</p> }
+                    >
+                        <For
+                            each=move || tokens.get()
+                            key=|token| token.id.clone()
+                            children=move |token| {
+                                let id = token.id.clone();
+                                let scopes = token
+                                    .scopes
+                                    .iter()
+                                    .map(|scope| scope.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+                                view! {
+                                    <div class="flex items-center justify-between py-3 border-b border-gray-100 last:border-b-0">
+                                        <div>
+                                            <p class="font-medium text-gray-900">{token.name}</p>
+                                            <p class="text-sm text-gray-500">{scopes}</p>
+                                            <p class="text-xs text-gray-500">
+                                                "Created " {token.created_at.format("%Y-%m-%d").to_string()}
+                                                " · Expires " {format_date(token.expires_at, "never")}
+                                                " · Last used " {format_date(token.last_used_at, "never")}
+                                            </p>
+                                        </div>
+                                        <button
+                                            class="text-sm font-semibold text-red-600 hover:text-red-700"
+                                            on:click=move |_| revoke(id.clone())
+                                        >
+                                            "Revoke"
+                                        </button>
+                                    </div>
+                                }
+                            }
+                        />
+                    </Show>
+                </div>
+            </div>
+        </div>
+    }
+}
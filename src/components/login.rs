@@ -1,5 +1,9 @@
-use crate::domain::models::user::{AuthResponse, CreateUserRequest, LoginRequest, UserProfile};
-use gloo_net::http::Request;
+use crate::domain::errors::common::{FieldError, ValidationErrorResponse};
+use crate::domain::models::mfa::MfaLoginRequest;
+use crate::domain::models::user::{
+    AuthResponse, CreateUserRequest, LoginRequest, LoginResponse, RegisterResponse, UserProfile,
+};
+use gloo_net::http::{Request, Response};
 use leptos::web_sys;
 use leptos::{prelude::*, task::spawn_local};
 use leptos_router::NavigateOptions;
@@ -238,6 +242,32 @@ pub fn AuthPage() -> impl IntoView {
     }
 }
 
+/// Keeps the refresh token so the session survives a page reload.
+fn store_refresh_token(auth_response: &AuthResponse) {
+    if let Some(window) = web_sys::window()
+        && let Ok(Some(storage)) = window.local_storage()
+    {
+        let _ = storage.set_item("refresh_token", &auth_response.refresh_token);
+    }
+}
+
+/// Message for a sign-in refused after too many failed attempts, using
+/// the server's `Retry-After` hint when present.
+fn locked_out_message(response: &Response) -> String {
+    let minutes = response
+        .headers()
+        .get("retry-after")
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(|seconds| seconds.div_ceil(60).max(1));
+    match minutes {
+        Some(1) => "Too many failed sign-in attempts. Please try again in a minute.".to_string(),
+        Some(minutes) => format!(
+            "Too many failed sign-in attempts. Please try again in {minutes} minutes."
+        ),
+        None => "Too many failed sign-in attempts. Please try again later.".to_string(),
+    }
+}
+
 #[component]
 fn LoginForm(
     loading: ReadSignal<bool>,
@@ -249,6 +279,10 @@ fn LoginForm(
     let (password, set_password) = signal(String::new());
     let (show_password, set_show_password) = signal(false);
     let (remember_me, set_remember_me) = signal(false);
+    // Set once the password is accepted for an account with two-factor
+    // authentication; the form then asks for a code instead.
+    let (mfa_token, set_mfa_token) = signal(None::<String>);
+    let (code, set_code) = signal(String::new());
     let navigate = use_navigate();
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
@@ -258,46 +292,90 @@ fn LoginForm(
 
         let email_val = email.get();
         let password_val = password.get();
+        let pending_mfa_token = mfa_token.get();
+        let code_val = code.get();
         let navigate = navigate.clone();
 
         spawn_local(async move {
-            let result = async {
-                let request = LoginRequest {
-                    email: email_val,
-                    password: password_val,
-                };
-                if let Ok(response) = Request::post("/api/auth/login")
-                    .json(&request)
-                    .map_err(|_| "Failed to serialize login data".to_string())?
-                    .send()
-                    .await
-                {
+            let result = if let Some(mfa_token) = pending_mfa_token {
+                async {
+                    let request = MfaLoginRequest {
+                        mfa_token,
+                        code: code_val,
+                    };
+                    let Ok(response) = Request::post("/api/auth/login/mfa")
+                        .json(&request)
+                        .map_err(|_| "Failed to serialize login data".to_string())?
+                        .send()
+                        .await
+                    else {
+                        return Err("Network error occurred. Please try again.".to_string());
+                    };
+
                     if response.ok() {
-                        if let Ok(auth_response) = response.json::<AuthResponse>().await {
-                            if let Some(window) = web_sys::window() {
-                                if let Ok(Some(storage)) = window.local_storage() {
-                                    let _ = storage
-                                        .set_item("refresh_token", &auth_response.refresh_token);
+                        let auth_response = response
+                            .json::<AuthResponse>()
+                            .await
+                            .map_err(|_| "Failed to parse response".to_string())?;
+                        store_refresh_token(&auth_response);
+                        navigate("/dashboard", NavigateOptions::default());
+                        Ok(())
+                    } else if response.status() == 429 {
+                        Err(locked_out_message(&response))
+                    } else if response.status() == 401 {
+                        set_mfa_token.set(None);
+                        Err("Your sign-in attempt has expired. Please sign in again.".to_string())
+                    } else {
+                        Err("Invalid authentication code".to_string())
+                    }
+                }
+                .await
+            } else {
+                async {
+                    let request = LoginRequest {
+                        email: email_val,
+                        password: password_val,
+                    };
+                    if let Ok(response) = Request::post("/api/auth/login")
+                        .json(&request)
+                        .map_err(|_| "Failed to serialize login data".to_string())?
+                        .send()
+                        .await
+                    {
+                        if response.ok() {
+                            match response.json::<LoginResponse>().await {
+                                Ok(LoginResponse::Authenticated(auth_response)) => {
+                                    store_refresh_token(&auth_response);
+                                    navigate("/dashboard", NavigateOptions::default());
+                                    Ok(())
+                                }
+                                Ok(LoginResponse::MfaRequired { mfa_token, .. }) => {
+                                    set_code.set(String::new());
+                                    set_mfa_token.set(Some(mfa_token));
+                                    Ok(())
+                                }
+                                Err(_) => {
+                                    set_error.set(Some("Failed to parse response".to_string()));
+                                    Err("Failed to parse response".to_string())
                                 }
                             }
-                            navigate("/dashboard", NavigateOptions::default());
-                            Ok(())
+                        } else if response.status() == 429 {
+                            Err(locked_out_message(&response))
+                        } else if response.status() == 403 {
+                            Err("Please verify your email address before signing in. Check your inbox for the link.".to_string())
                         } else {
-                            set_error.set(Some("Failed to parse response".to_string()));
-                            Err("Failed to parse response".to_string())
+                            set_error.set(Some("Invalid email or password".to_string()));
+                            Err("Invalid email or password".to_string())
                         }
                     } else {
-                        set_error.set(Some("Invalid email or password".to_string()));
-                        Err("Invalid email or password".to_string())
+                        set_error.set(Some(
+                            "Network error occurred. Please try again.".to_string(),
+                        ));
+                        Err("Network error occurred. Please try again.".to_string())
                     }
-                } else {
-                    set_error.set(Some(
-                        "Network error occurred. Please try again.".to_string(),
-                    ));
-                    Err("Network error occurred. Please try again.".to_string())
                 }
-            }
-            .await;
+                .await
+            };
 
             if let Err(err_msg) = result {
                 set_error.set(Some(err_msg));
@@ -339,147 +417,184 @@ fn LoginForm(
                 </div>
             </Show>
 
-            // Enhanced email field
-            <div class="space-y-2">
-                <label for="email" class="block text-sm font-semibold text-gray-800">
-                    "Email Address"
-                </label>
-                <div class="relative group">
-                    <div class="absolute inset-y-0 left-0 pl-4 flex items-center pointer-events-none group-focus-within:text-violet-600 transition-colors duration-200">
-                        <svg
-                            class="h-5 w-5 text-gray-400 group-focus-within:text-violet-500"
-                            fill="none"
-                            stroke="currentColor"
-                            viewBox="0 0 24 24"
-                        >
-                            <path
-                                stroke-linecap="round"
-                                stroke-linejoin="round"
-                                stroke-width="2"
-                                d="M16 12a4 4 0 10-8 0 4 4 0 008 0zm0 0v1.5a2.5 2.5 0 005 0V12a9 9 0 10-9 9m4.5-1.206a8.959 8.959 0 01-4.5 1.207"
-                            />
-                        </svg>
+            <Show when=move || mfa_token.get().is_none()>
+                // Enhanced email field
+                <div class="space-y-2">
+                    <label for="email" class="block text-sm font-semibold text-gray-800">
+                        "Email Address"
+                    </label>
+                    <div class="relative group">
+                        <div class="absolute inset-y-0 left-0 pl-4 flex items-center pointer-events-none group-focus-within:text-violet-600 transition-colors duration-200">
+                            <svg
+                                class="h-5 w-5 text-gray-400 group-focus-within:text-violet-500"
+                                fill="none"
+                                stroke="currentColor"
+                                viewBox="0 0 24 24"
+                            >
+                                <path
+                                    stroke-linecap="round"
+                                    stroke-linejoin="round"
+                                    stroke-width="2"
+                                    d="M16 12a4 4 0 10-8 0 4 4 0 008 0zm0 0v1.5a2.5 2.5 0 005 0V12a9 9 0 10-9 9m4.5-1.206a8.959 8.959 0 01-4.5 1.207"
+                                />
+                            </svg>
+                        </div>
+                        <input
+                            id="email"
+                            name="email"
+                            type="email"
+                            required
+                            class="block w-full pl-12 pr-4 py-4 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 focus:bg-white transition-all duration-300 text-gray-900"
+                            placeholder="Enter your email address"
+                            prop:value=email
+                            on:input=move |ev| set_email.set(event_target_value(&ev))
+                        />
                     </div>
-                    <input
-                        id="email"
-                        name="email"
-                        type="email"
-                        required
-                        class="block w-full pl-12 pr-4 py-4 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 focus:bg-white transition-all duration-300 text-gray-900"
-                        placeholder="Enter your email address"
-                        prop:value=email
-                        on:input=move |ev| set_email.set(event_target_value(&ev))
-                    />
                 </div>
-            </div>
 
-            // Enhanced password field
-            <div class="space-y-2">
-                <label for="password" class="block text-sm font-semibold text-gray-800">
-                    "Password"
-                </label>
-                <div class="relative group">
-                    <div class="absolute inset-y-0 left-0 pl-4 flex items-center pointer-events-none group-focus-within:text-violet-600 transition-colors duration-200">
-                        <svg
-                            class="h-5 w-5 text-gray-400 group-focus-within:text-violet-500"
-                            fill="none"
-                            stroke="currentColor"
-                            viewBox="0 0 24 24"
-                        >
-                            <path
-                                stroke-linecap="round"
-                                stroke-linejoin="round"
-                                stroke-width="2"
-                                d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z"
-                            />
-                        </svg>
+                // Enhanced password field
+                <div class="space-y-2">
+                    <label for="password" class="block text-sm font-semibold text-gray-800">
+                        "Password"
+                    </label>
+                    <div class="relative group">
+                        <div class="absolute inset-y-0 left-0 pl-4 flex items-center pointer-events-none group-focus-within:text-violet-600 transition-colors duration-200">
+                            <svg
+                                class="h-5 w-5 text-gray-400 group-focus-within:text-violet-500"
+                                fill="none"
+                                stroke="currentColor"
+                                viewBox="0 0 24 24"
+                            >
+                                <path
+                                    stroke-linecap="round"
+                                    stroke-linejoin="round"
+                                    stroke-width="2"
+                                    d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z"
+                                />
+                            </svg>
+                        </div>
+                        <input
+                            id="password"
+                            name="password"
+                            type=move || if show_password.get() { "text" } else { "password" }
+                            required
+                            class="block w-full pl-12 pr-14 py-4 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 focus:bg-white transition-all duration-300 text-gray-900"
+                            placeholder="Enter your password"
+                            prop:value=password
+                            on:input=move |ev| set_password.set(event_target_value(&ev))
+                        />
+                        <div class="absolute inset-y-0 right-0 pr-4 flex items-center">
+                            <button
+                                type="button"
+                                class="text-gray-400 hover:text-violet-600 transition-colors duration-200 p-1 rounded-lg hover:bg-violet-50"
+                                on:click=move |_| set_show_password.update(|show| *show = !*show)
+                            >
+                                {move || {
+                                    if show_password.get() {
+                                        view! {
+                                            <svg
+                                                class="h-5 w-5"
+                                                fill="none"
+                                                stroke="currentColor"
+                                                viewBox="0 0 24 24"
+                                            >
+                                                <path
+                                                    stroke-linecap="round"
+                                                    stroke-linejoin="round"
+                                                    stroke-width="2"
+                                                    d="M13.875 18.825A10.05 10.05 0 0112 19c-4.478 0-8.268-2.943-9.543-7a9.97 9.97 0 011.563-3.029m5.858.908a3 3 0 114.243 4.243M9.878 9.878l4.242 4.242M9.878 9.878L3 3m6.878 6.878L21 21"
+                                                />
+                                            </svg>
+                                        }
+                                            .into_any()
+                                    } else {
+                                        view! {
+                                            <svg
+                                                class="h-5 w-5"
+                                                fill="none"
+                                                stroke="currentColor"
+                                                viewBox="0 0 24 24"
+                                            >
+                                                <path
+                                                    stroke-linecap="round"
+                                                    stroke-linejoin="round"
+                                                    stroke-width="2"
+                                                    d="M15 12a3 3 0 11-6 0 3 3 0 016 0z"
+                                                />
+                                                <path
+                                                    stroke-linecap="round"
+                                                    stroke-linejoin="round"
+                                                    stroke-width="2"
+                                                    d="M2.458 12C3.732 7.943 7.523 5 12 5c4.478 0 8.268 2.943 9.542 7-1.274 4.057-5.064 7-9.542 7-4.477 0-8.268-2.943-9.542-7z"
+                                                />
+                                            </svg>
+                                        }
+                                            .into_any()
+                                    }
+                                }}
+                            </button>
+                        </div>
                     </div>
-                    <input
-                        id="password"
-                        name="password"
-                        type=move || if show_password.get() { "text" } else { "password" }
-                        required
-                        class="block w-full pl-12 pr-14 py-4 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 focus:bg-white transition-all duration-300 text-gray-900"
-                        placeholder="Enter your password"
-                        prop:value=password
-                        on:input=move |ev| set_password.set(event_target_value(&ev))
-                    />
-                    <div class="absolute inset-y-0 right-0 pr-4 flex items-center">
-                        <button
-                            type="button"
-                            class="text-gray-400 hover:text-violet-600 transition-colors duration-200 p-1 rounded-lg hover:bg-violet-50"
-                            on:click=move |_| set_show_password.update(|show| *show = !*show)
+                </div>
+
+                // Enhanced options row
+                <div class="flex items-center justify-between">
+                    <div class="flex items-center">
+                        <input
+                            id="remember-me"
+                            name="remember-me"
+                            type="checkbox"
+                            class="h-4 w-4 text-violet-600 focus:ring-violet-500 border-gray-300 rounded transition-colors duration-200"
+                            prop:checked=remember_me
+                            on:change=move |ev| set_remember_me.set(event_target_checked(&ev))
+                        />
+                        <label for="remember-me" class="ml-3 block text-sm text-gray-700 font-medium">
+                            "Remember me for 30 days"
+                        </label>
+                    </div>
+                    <div class="text-sm">
+                        <a
+                            href="/forgot-password"
+                            class="font-semibold text-violet-600 hover:text-violet-700 transition-colors duration-200 hover:underline"
                         >
-                            {move || {
-                                if show_password.get() {
-                                    view! {
-                                        <svg
-                                            class="h-5 w-5"
-                                            fill="none"
-                                            stroke="currentColor"
-                                            viewBox="0 0 24 24"
-                                        >
-                                            <path
-                                                stroke-linecap="round"
-                                                stroke-linejoin="round"
-                                                stroke-width="2"
-                                                d="M13.875 18.825A10.05 10.05 0 0112 19c-4.478 0-8.268-2.943-9.543-7a9.97 9.97 0 011.563-3.029m5.858.908a3 3 0 114.243 4.243M9.878 9.878l4.242 4.242M9.878 9.878L3 3m6.878 6.878L21 21"
-                                            />
-                                        </svg>
-                                    }
-                                } else {
-                                    view! {
-                                        <svg
-                                            class="h-5 w-5"
-                                            fill="none"
-                                            stroke="currentColor"
-                                            viewBox="0 0 24 24"
-                                        >
-                                            <path
-                                                stroke-linecap="round"
-                                                stroke-linejoin="round"
-                                                stroke-width="2"
-                                                d="M15 12a3 3 0 11-6 0 3 3 0 016 0z"
-                                            />
-                                            <path
-                                                stroke-linecap="round"
-                                                stroke-linejoin="round"
-                                                stroke-width="2"
-                                                d="M2.458 12C3.732 7.943 7.523 5 12 5c4.478 0 8.268 2.943 9.542 7-1.274 4.057-5.064 7-9.542 7-4.477 0-8.268-2.943-9.542-7z"
-                                            />
-                                        </svg>
-                                    }
-                                }
-                            }}
-                        </button>
+                            "Forgot password?"
+                        </a>
                     </div>
                 </div>
-            </div>
+            </Show>
 
-            // Enhanced options row
-            <div class="flex items-center justify-between">
-                <div class="flex items-center">
+            // Second step for accounts with two-factor authentication
+            <Show when=move || mfa_token.get().is_some()>
+                <div class="space-y-2">
+                    <label for="mfa-code" class="block text-sm font-semibold text-gray-800">
+                        "Authentication Code"
+                    </label>
+                    <p class="text-sm text-gray-600">
+                        "Enter the 6-digit code from your authenticator app, or one of your recovery codes."
+                    </p>
                     <input
-                        id="remember-me"
-                        name="remember-me"
-                        type="checkbox"
-                        class="h-4 w-4 text-violet-600 focus:ring-violet-500 border-gray-300 rounded transition-colors duration-200"
-                        prop:checked=remember_me
-                        on:change=move |ev| set_remember_me.set(event_target_checked(&ev))
+                        id="mfa-code"
+                        name="mfa-code"
+                        type="text"
+                        autocomplete="one-time-code"
+                        required
+                        class="block w-full px-4 py-4 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 focus:bg-white transition-all duration-300 text-gray-900 tracking-widest"
+                        placeholder="123456"
+                        prop:value=code
+                        on:input=move |ev| set_code.set(event_target_value(&ev))
                     />
-                    <label for="remember-me" class="ml-3 block text-sm text-gray-700 font-medium">
-                        "Remember me for 30 days"
-                    </label>
-                </div>
-                <div class="text-sm">
-                    <a
-                        href="#"
-                        class="font-semibold text-violet-600 hover:text-violet-700 transition-colors duration-200 hover:underline"
+                    <button
+                        type="button"
+                        class="text-sm font-semibold text-violet-600 hover:text-violet-700 transition-colors duration-200 hover:underline"
+                        on:click=move |_| {
+                            set_mfa_token.set(None);
+                            set_error.set(None);
+                        }
                     >
-                        "Forgot password?"
-                    </a>
+                        "Back to sign in"
+                    </button>
                 </div>
-            </div>
+            </Show>
 
             // Enhanced submit button
             <div class="pt-2">
@@ -512,7 +627,13 @@ fn LoginForm(
                                             ></path>
                                         </svg>
                                     </div>
-                                    "Signing you in..."
+                                    {move || {
+                                        if mfa_token.get().is_some() {
+                                            "Verifying..."
+                                        } else {
+                                            "Signing you in..."
+                                        }
+                                    }}
                                 </div>
                             }
                                 .into_any()
@@ -532,7 +653,15 @@ fn LoginForm(
                                             d="M11 16l-4-4m0 0l4-4m-4 4h14m-5 4v1a3 3 0 01-3 3H6a3 3 0 01-3-3V7a3 3 0 013-3h7a3 3 0 013 3v1"
                                         />
                                     </svg>
-                                    <span>"Sign in to your account"</span>
+                                    <span>
+                                        {move || {
+                                            if mfa_token.get().is_some() {
+                                                "Verify and sign in"
+                                            } else {
+                                                "Sign in to your account"
+                                            }
+                                        }}
+                                    </span>
                                 </div>
                             }
                                 .into_any()
@@ -604,6 +733,8 @@ fn RegisterForm(
     let (last_name, set_last_name) = signal(String::new());
     let (show_password, set_show_password) = signal(false);
     let (accept_terms, set_accept_terms) = signal(false);
+    let (field_errors, set_field_errors) = signal(Vec::<FieldError>::new());
+    let (notice, set_notice) = signal(None::<String>);
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
@@ -615,6 +746,8 @@ fn RegisterForm(
 
         set_loading.set(true);
         set_error.set(None);
+        set_notice.set(None);
+        set_field_errors.set(Vec::new());
 
         let email_val = email.get();
         let password_val = password.get();
@@ -635,8 +768,11 @@ fn RegisterForm(
                     match request_builder.send().await {
                         Ok(response) => {
                             if response.ok() {
-                                match response.json::<AuthResponse>().await {
-                                    Ok(auth_response) => {
+                                match response.json::<RegisterResponse>().await {
+                                    Ok(RegisterResponse::VerificationPending { message, .. }) => {
+                                        set_notice.set(Some(message));
+                                    }
+                                    Ok(RegisterResponse::Authenticated(auth_response)) => {
                                         if let Some(window) = web_sys::window() {
                                             if let Ok(Some(storage)) = window.local_storage() {
                                                 let _ = storage.set_item(
@@ -652,6 +788,22 @@ fn RegisterForm(
                                         set_error.set(Some("Failed to parse response".to_string()));
                                     }
                                 }
+                            } else if response.status() == 400 {
+                                let errors = response
+                                    .json::<ValidationErrorResponse>()
+                                    .await
+                                    .map(|body| body.errors)
+                                    .unwrap_or_default();
+                                if errors.is_empty() {
+                                    set_error.set(Some(
+                                        "Registration failed. Please check your details.".to_string(),
+                                    ));
+                                } else {
+                                    set_error.set(Some(
+                                        "Please correct the highlighted fields".to_string(),
+                                    ));
+                                    set_field_errors.set(errors);
+                                }
                             } else if response.status() == 409 {
                                 set_error.set(Some(
                                     "An account with this email already exists".to_string(),
@@ -675,6 +827,14 @@ fn RegisterForm(
 
     view! {
         <form class="space-y-6" on:submit=on_submit>
+            <Show when=move || notice.get().is_some()>
+                <div class="rounded-2xl bg-gradient-to-r from-emerald-50 to-teal-50 border border-emerald-200/50 p-4 animate-in slide-in-from-top duration-300">
+                    <p class="text-sm font-medium text-emerald-800">
+                        {move || notice.get().unwrap_or_default()}
+                    </p>
+                </div>
+            </Show>
+
             // Enhanced error message (same as login)
             <Show when=move || error.get().is_some()>
                 <div class="rounded-2xl bg-gradient-to-r from-red-50 to-pink-50 border border-red-200/50 p-4 animate-in slide-in-from-top duration-300">
@@ -738,6 +898,7 @@ fn RegisterForm(
                             on:input=move |ev| set_first_name.set(event_target_value(&ev))
                         />
                     </div>
+                    <FieldErrorText field="first_name" errors=field_errors />
                 </div>
                 <div class="space-y-2">
                     <label for="last_name" class="block text-sm font-semibold text-gray-800">
@@ -770,6 +931,7 @@ fn RegisterForm(
                             on:input=move |ev| set_last_name.set(event_target_value(&ev))
                         />
                     </div>
+                    <FieldErrorText field="last_name" errors=field_errors />
                 </div>
             </div>
 
@@ -805,6 +967,7 @@ fn RegisterForm(
                         on:input=move |ev| set_email.set(event_target_value(&ev))
                     />
                 </div>
+                <FieldErrorText field="email" errors=field_errors />
             </div>
 
             // Enhanced password field (similar to login)
@@ -861,6 +1024,7 @@ fn RegisterForm(
                                             />
                                         </svg>
                                     }
+                                        .into_any()
                                 } else {
                                     view! {
                                         <svg
@@ -883,6 +1047,7 @@ fn RegisterForm(
                                             />
                                         </svg>
                                     }
+                                        .into_any()
                                 }
                             }}
                         </button>
@@ -908,6 +1073,7 @@ fn RegisterForm(
                     </div>
                     <span class="ml-3 text-xs text-gray-500">"Strong password"</span>
                 </div>
+                <FieldErrorText field="password" errors=field_errors />
             </div>
 
             // Enhanced terms checkbox
@@ -1003,3 +1169,18 @@ fn RegisterForm(
         </form>
     }
 }
+
+/// Shows the server-side validation messages reported for `field`.
+#[component]
+fn FieldErrorText(field: &'static str, errors: ReadSignal<Vec<FieldError>>) -> impl IntoView {
+    let messages = move || {
+        errors
+            .get()
+            .into_iter()
+            .filter(|error| error.field == field)
+            .map(|error| view! { <p class="text-sm text-red-600">{error.message}</p> })
+            .collect_view()
+    };
+
+    view! { <div class="space-y-1">{messages}</div> }
+}
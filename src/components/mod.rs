@@ -1,2 +1,6 @@
+pub mod access_tokens;
 pub mod login;
+pub mod password_reset;
+pub mod sessions;
+pub mod verify_email;
 pub mod ws_connection_status;
@@ -0,0 +1,214 @@
+use crate::domain::errors::common::FieldError;
+use crate::domain::models::user::{ForgotPasswordRequest, ResetPasswordRequest};
+use gloo_net::http::Request;
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::hooks::use_query_map;
+
+const CARD_CLASS: &str = "w-full max-w-md backdrop-blur-xl bg-white/90 border border-white/20 shadow-2xl rounded-3xl p-8 space-y-6";
+const INPUT_CLASS: &str = "block w-full px-4 py-3 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 text-gray-900";
+const BUTTON_CLASS: &str = "w-full py-3 px-4 rounded-2xl text-white font-semibold bg-gradient-to-r from-violet-600 to-blue-600 hover:from-violet-700 hover:to-blue-700 disabled:opacity-50";
+
+/// Asks for an email address and requests a reset link for it. The server
+/// answers the same way whether or not the address has an account.
+#[component]
+#[allow(clippy::must_use_candidate)]
+pub fn ForgotPasswordPage() -> impl IntoView {
+    let (email, set_email) = signal(String::new());
+    let (sending, set_sending) = signal(false);
+    let (message, set_message) = signal(None::<String>);
+    let (error, set_error) = signal(None::<String>);
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_sending.set(true);
+        set_message.set(None);
+        set_error.set(None);
+
+        let request = ForgotPasswordRequest { email: email.get() };
+        spawn_local(async move {
+            match Request::post("/api/auth/forgot-password").json(&request) {
+                Ok(request) => match request.send().await {
+                    Ok(response) if response.ok() => set_message.set(Some(
+                        "If an account exists for this address, we have sent a link to reset your password."
+                            .to_string(),
+                    )),
+                    Ok(_) => set_error.set(Some(
+                        "Could not send a reset link. Please try again.".to_string(),
+                    )),
+                    Err(_) => set_error.set(Some(
+                        "Network error occurred. Please try again.".to_string(),
+                    )),
+                },
+                Err(_) => set_error.set(Some("Failed to serialize request data".to_string())),
+            }
+            set_sending.set(false);
+        });
+    };
+
+    view! {
+        <div class="min-h-screen bg-gradient-to-br from-indigo-100 via-white to-cyan-100 flex items-center justify-center p-4">
+            <div class=CARD_CLASS>
+                <div class="text-center space-y-2">
+                    <h1 class="text-2xl font-bold text-gray-900">"Forgot your password?"</h1>
+                    <p class="text-sm text-gray-600">
+                        "Enter your email and we will send you a link to choose a new one."
+                    </p>
+                </div>
+                <Show when=move || message.get().is_some()>
+                    <p class="rounded-2xl bg-emerald-50 border border-emerald-200/50 p-4 text-sm font-medium text-emerald-800">
+                        {move || message.get().unwrap_or_default()}
+                    </p>
+                </Show>
+                <Show when=move || error.get().is_some()>
+                    <p class="rounded-2xl bg-red-50 border border-red-200/50 p-4 text-sm font-medium text-red-800">
+                        {move || error.get().unwrap_or_default()}
+                    </p>
+                </Show>
+                <form class="space-y-4" on:submit=on_submit>
+                    <input
+                        type="email"
+                        required
+                        class=INPUT_CLASS
+                        placeholder="you@example.com"
+                        prop:value=email
+                        on:input=move |ev| set_email.set(event_target_value(&ev))
+                    />
+                    <button type="submit" disabled=sending class=BUTTON_CLASS>
+                        {move || if sending.get() { "Sending..." } else { "Send reset link" }}
+                    </button>
+                </form>
+                <p class="text-center text-sm">
+                    <a href="/login" class="font-semibold text-violet-600 hover:text-violet-700 hover:underline">
+                        "Back to sign in"
+                    </a>
+                </p>
+            </div>
+        </div>
+    }
+}
+
+/// Landing page for the link in the reset email. Sets a new password using
+/// the `token` query parameter.
+#[component]
+#[allow(clippy::must_use_candidate)]
+#[allow(clippy::too_many_lines)]
+pub fn ResetPasswordPage() -> impl IntoView {
+    let query = use_query_map();
+    let (password, set_password) = signal(String::new());
+    let (confirm, set_confirm) = signal(String::new());
+    let (sending, set_sending) = signal(false);
+    let (done, set_done) = signal(false);
+    let (error, set_error) = signal(None::<String>);
+    let (field_errors, set_field_errors) = signal(Vec::<FieldError>::new());
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_error.set(None);
+        set_field_errors.set(Vec::new());
+
+        let Some(token) = query.read_untracked().get("token") else {
+            set_error.set(Some("This password reset link is incomplete".to_string()));
+            return;
+        };
+        if password.get() != confirm.get() {
+            set_error.set(Some("Passwords do not match".to_string()));
+            return;
+        }
+
+        set_sending.set(true);
+        let request = ResetPasswordRequest {
+            token,
+            new_password: password.get(),
+        };
+        spawn_local(async move {
+            match Request::post("/api/auth/reset-password").json(&request) {
+                Ok(request) => match request.send().await {
+                    Ok(response) if response.ok() => set_done.set(true),
+                    Ok(response) => {
+                        let body = response
+                            .json::<serde_json::Value>()
+                            .await
+                            .unwrap_or_default();
+                        let errors: Vec<FieldError> =
+                            serde_json::from_value(body["errors"].clone()).unwrap_or_default();
+                        if errors.is_empty() {
+                            let message = body["error"]
+                                .as_str()
+                                .unwrap_or("Could not reset your password. Please try again.");
+                            set_error.set(Some(message.to_string()));
+                        } else {
+                            set_field_errors.set(errors);
+                        }
+                    }
+                    Err(_) => set_error.set(Some(
+                        "Network error occurred. Please try again.".to_string(),
+                    )),
+                },
+                Err(_) => set_error.set(Some("Failed to serialize request data".to_string())),
+            }
+            set_sending.set(false);
+        });
+    };
+
+    view! {
+        <div class="min-h-screen bg-gradient-to-br from-indigo-100 via-white to-cyan-100 flex items-center justify-center p-4">
+            <div class=CARD_CLASS>
+                <h1 class="text-2xl font-bold text-gray-900 text-center">"Choose a new password"</h1>
+                <Show
+                    when=move || !done.get()
+                    fallback=|| {
+                        view! {
+                            <div class="space-y-4 text-center">
+                                <p class="text-emerald-700 font-medium">
+                                    "Your password has been reset. All other sessions have been signed out."
+                                </p>
+                                <a href="/login" class="inline-block py-3 px-6 rounded-2xl text-white font-semibold bg-gradient-to-r from-violet-600 to-blue-600 hover:from-violet-700 hover:to-blue-700">
+                                    "Sign in"
+                                </a>
+                            </div>
+                        }
+                    }
+                >
+                    <Show when=move || error.get().is_some()>
+                        <p class="rounded-2xl bg-red-50 border border-red-200/50 p-4 text-sm font-medium text-red-800">
+                            {move || error.get().unwrap_or_default()}
+                        </p>
+                    </Show>
+                    <form class="space-y-4" on:submit=on_submit>
+                        <input
+                            type="password"
+                            required
+                            autocomplete="new-password"
+                            class=INPUT_CLASS
+                            placeholder="New password"
+                            prop:value=password
+                            on:input=move |ev| set_password.set(event_target_value(&ev))
+                        />
+                        <For
+                            each=move || field_errors.get()
+                            key=|error| error.code.clone()
+                            children=|error| view! { <p class="text-sm text-red-600">{error.message}</p> }
+                        />
+                        <input
+                            type="password"
+                            required
+                            autocomplete="new-password"
+                            class=INPUT_CLASS
+                            placeholder="Confirm new password"
+                            prop:value=confirm
+                            on:input=move |ev| set_confirm.set(event_target_value(&ev))
+                        />
+                        <button type="submit" disabled=sending class=BUTTON_CLASS>
+                            {move || if sending.get() { "Saving..." } else { "Reset password" }}
+                        </button>
+                    </form>
+                    <p class="text-center text-sm">
+                        <a href="/forgot-password" class="font-semibold text-violet-600 hover:text-violet-700 hover:underline">
+                            "Request a new link"
+                        </a>
+                    </p>
+                </Show>
+            </div>
+        </div>
+    }
+}
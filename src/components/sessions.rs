@@ -0,0 +1,136 @@
+use crate::components::access_tokens::{ApiError, bearer, check};
+use crate::domain::models::session::SessionSummary;
+use crate::infrastructure::auth::auth_context::AuthState;
+use gloo_net::http::Request;
+use leptos::{prelude::*, task::spawn_local};
+
+const SESSIONS_URL: &str = "/api/users/me/sessions";
+const CARD_CLASS: &str = "bg-white rounded-xl shadow-sm border border-gray-200 p-6 space-y-4";
+
+/// Lists the browsers and devices signed in to the account and signs them
+/// out. Signing out the current session returns to the login page.
+#[component]
+#[allow(clippy::must_use_candidate)]
+#[allow(clippy::too_many_lines)]
+pub fn SessionsPage() -> impl IntoView {
+    let auth = StoredValue::new(AuthState::new());
+    let (sessions, set_sessions) = signal(Vec::<SessionSummary>::new());
+    let (error, set_error) = signal(None::<String>);
+
+    // Effects only run in the browser, where the refresh token is stored
+    Effect::new(move |_| {
+        spawn_local(async move {
+            let result = async {
+                let authorization = bearer(&auth.get_value()).await?;
+                let response = check(
+                    Request::get(SESSIONS_URL)
+                        .header("Authorization", &authorization)
+                        .send()
+                        .await,
+                )
+                .await?;
+                response
+                    .json::<Vec<SessionSummary>>()
+                    .await
+                    .map_err(|_| ApiError::new("Unexpected response from the server"))
+            }
+            .await;
+            match result {
+                Ok(list) => set_sessions.set(list),
+                Err(e) => set_error.set(Some(e.message)),
+            }
+        });
+    });
+
+    let revoke = move |id: String, current: bool| {
+        set_error.set(None);
+        spawn_local(async move {
+            let result = async {
+                let authorization = bearer(&auth.get_value()).await?;
+                check(
+                    Request::delete(&format!("{SESSIONS_URL}/{id}"))
+                        .header("Authorization", &authorization)
+                        .send()
+                        .await,
+                )
+                .await
+            }
+            .await;
+            match result {
+                Ok(_) if current => {
+                    auth.get_value().clear();
+                    if let Some(window) = leptos::web_sys::window() {
+                        let _ = window.location().set_href("/login");
+                    }
+                }
+                Ok(_) => {
+                    set_sessions.update(|sessions| sessions.retain(|session| session.id != id));
+                }
+                Err(e) => set_error.set(Some(e.message)),
+            }
+        });
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-50">
+            <div class="max-w-3xl mx-auto py-8 px-4 space-y-6">
+                <div class="flex items-center justify-between">
+                    <div>
+                        <h1 class="text-2xl font-bold text-gray-900">"Active sessions"</h1>
+                        <p class="text-sm text-gray-600">
+                            "Browsers and devices that are signed in to your account."
+                        </p>
+                    </div>
+                    <a href="/dashboard" class="text-sm font-semibold text-violet-600 hover:text-violet-700 hover:underline">
+                        "Back to dashboard"
+                    </a>
+                </div>
+
+                <Show when=move || error.get().is_some()>
+                    <p class="rounded-2xl bg-red-50 border border-red-200/50 p-4 text-sm font-medium text-red-800">
+                        {move || error.get().unwrap_or_default()}
+                    </p>
+                </Show>
+
+                <div class=CARD_CLASS>
+                    <Show
+                        when=move || !sessions.get().is_empty()
+                        fallback=|| view! { <p class="text-sm text-gray-500">"You have no active sessions."</p> }
+                    >
+                        <For
+                            each=move || sessions.get()
+                            key=|session| session.id.clone()
+                            children=move |session| {
+                                let id = session.id.clone();
+                                let current = session.current;
+                                view! {
+                                    <div class="flex items-center justify-between py-3 border-b border-gray-100 last:border-b-0">
+                                        <div>
+                                            <p class="font-medium text-gray-900">
+                                                {session.user_agent.unwrap_or_else(|| "Unknown browser".to_string())}
+                                                <Show when=move || current>
+                                                    <span class="ml-2 text-xs font-semibold text-emerald-600">"This browser"</span>
+                                                </Show>
+                                            </p>
+                                            <p class="text-xs text-gray-500">
+                                                {session.ip_address.unwrap_or_else(|| "Unknown address".to_string())}
+                                                " · Signed in " {session.created_at.format("%Y-%m-%d").to_string()}
+                                                " · Last active " {session.last_seen_at.format("%Y-%m-%d %H:%M").to_string()}
+                                            </p>
+                                        </div>
+                                        <button
+                                            class="text-sm font-semibold text-red-600 hover:text-red-700"
+                                            on:click=move |_| revoke(id.clone(), current)
+                                        >
+                                            "Sign out"
+                                        </button>
+                                    </div>
+                                }
+                            }
+                        />
+                    </Show>
+                </div>
+            </div>
+        </div>
+    }
+}
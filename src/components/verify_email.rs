@@ -0,0 +1,141 @@
+use crate::domain::models::user::{ResendVerificationRequest, VerifyEmailRequest};
+use gloo_net::http::Request;
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::hooks::use_query_map;
+
+#[derive(Clone, PartialEq, Eq)]
+enum VerificationStatus {
+    Verifying,
+    Verified,
+    Failed(String),
+}
+
+/// Landing page for the link in the verification email. Redeems the
+/// `token` query parameter and, if that fails, lets the user request a new
+/// email.
+#[component]
+#[allow(clippy::must_use_candidate)]
+pub fn VerifyEmailPage() -> impl IntoView {
+    let query = use_query_map();
+    let (status, set_status) = signal(VerificationStatus::Verifying);
+
+    // Effects only run in the browser, so the token is redeemed exactly once
+    Effect::new(move |_| {
+        let Some(token) = query.read_untracked().get("token") else {
+            set_status.set(VerificationStatus::Failed(
+                "This verification link is incomplete".to_string(),
+            ));
+            return;
+        };
+
+        spawn_local(async move {
+            let Ok(request) =
+                Request::post("/api/auth/verify-email").json(&VerifyEmailRequest { token })
+            else {
+                set_status.set(VerificationStatus::Failed(
+                    "Failed to serialize request data".to_string(),
+                ));
+                return;
+            };
+            match request.send().await {
+                Ok(response) if response.ok() => set_status.set(VerificationStatus::Verified),
+                Ok(_) => set_status.set(VerificationStatus::Failed(
+                    "This verification link is invalid or has expired".to_string(),
+                )),
+                Err(_) => set_status.set(VerificationStatus::Failed(
+                    "Network error occurred. Please try again.".to_string(),
+                )),
+            }
+        });
+    });
+
+    view! {
+        <div class="min-h-screen bg-gradient-to-br from-indigo-100 via-white to-cyan-100 flex items-center justify-center p-4">
+            <div class="w-full max-w-md backdrop-blur-xl bg-white/90 border border-white/20 shadow-2xl rounded-3xl p-8 text-center space-y-6">
+                <h1 class="text-2xl font-bold text-gray-900">"Verify your email"</h1>
+                {move || match status.get() {
+                    VerificationStatus::Verifying => {
+                        view! { <p class="text-gray-600">"Verifying your email address..."</p> }
+                            .into_any()
+                    }
+                    VerificationStatus::Verified => {
+                        view! {
+                            <div class="space-y-4">
+                                <p class="text-emerald-700 font-medium">
+                                    "Your email address has been verified."
+                                </p>
+                                <a
+                                    href="/login"
+                                    class="inline-block py-3 px-6 rounded-2xl text-white font-semibold bg-gradient-to-r from-violet-600 to-blue-600 hover:from-violet-700 hover:to-blue-700"
+                                >
+                                    "Sign in"
+                                </a>
+                            </div>
+                        }
+                            .into_any()
+                    }
+                    VerificationStatus::Failed(message) => {
+                        view! {
+                            <div class="space-y-4">
+                                <p class="text-red-700 font-medium">{message}</p>
+                                <ResendVerificationForm />
+                            </div>
+                        }
+                            .into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ResendVerificationForm() -> impl IntoView {
+    let (email, set_email) = signal(String::new());
+    let (sending, set_sending) = signal(false);
+    let (message, set_message) = signal(None::<String>);
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_sending.set(true);
+        set_message.set(None);
+
+        let request = ResendVerificationRequest { email: email.get() };
+        spawn_local(async move {
+            let sent = match Request::post("/api/auth/resend-verification").json(&request) {
+                Ok(request) => request.send().await.is_ok_and(|response| response.ok()),
+                Err(_) => false,
+            };
+            set_message.set(Some(if sent {
+                "If your account still needs verifying, a new link is on its way.".to_string()
+            } else {
+                "Could not send a new link. Please try again.".to_string()
+            }));
+            set_sending.set(false);
+        });
+    };
+
+    view! {
+        <form class="space-y-3" on:submit=on_submit>
+            <p class="text-sm text-gray-600">"Enter your email to get a new verification link."</p>
+            <input
+                type="email"
+                required
+                class="block w-full px-4 py-3 bg-gray-50/50 border border-gray-200 rounded-2xl placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-violet-500/50 focus:border-violet-500 text-gray-900"
+                placeholder="you@example.com"
+                prop:value=email
+                on:input=move |ev| set_email.set(event_target_value(&ev))
+            />
+            <button
+                type="submit"
+                disabled=sending
+                class="w-full py-3 px-4 rounded-2xl text-white font-semibold bg-gradient-to-r from-violet-600 to-blue-600 hover:from-violet-700 hover:to-blue-700 disabled:opacity-50"
+            >
+                {move || if sending.get() { "Sending..." } else { "Send new link" }}
+            </button>
+            <Show when=move || message.get().is_some()>
+                <p class="text-sm text-gray-700">{move || message.get().unwrap_or_default()}</p>
+            </Show>
+        </form>
+    }
+}
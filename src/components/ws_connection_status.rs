@@ -6,6 +6,7 @@ cfg_if! {
     if #[cfg(feature = "hydrate")] {
         use leptos_use::{UseWebSocketReturn, core::ConnectionReadyState, use_websocket};
         use crate::components::ws_connection_status::codee::string::FromToStringCodec;
+        use crate::infrastructure::auth::auth_context::AuthState;
     }
 }
 
@@ -43,6 +44,12 @@ fn ClientSideConnectionStatus() -> impl IntoView {
             message: String,
         },
         Pong,
+        Authenticate {
+            access_token: String,
+        },
+        ForceLogout {
+            session_id: String,
+        },
     }
 
     // Safe WebSocket URL setup
@@ -128,6 +135,15 @@ fn ClientSideConnectionStatus() -> impl IntoView {
                         WebSocketMessage::Pong => {
                             leptos::logging::log!("Received pong from server");
                         }
+                        WebSocketMessage::ForceLogout { session_id } => {
+                            // This browser's session was revoked elsewhere
+                            leptos::logging::log!("Session {} was signed out", session_id);
+                            AuthState::new().clear();
+                            if let Some(window) = web_sys::window() {
+                                let _ = window.location().set_href("/login");
+                            }
+                        }
+                        WebSocketMessage::Authenticate { .. } => {}
                     }
                 }
                 Err(e) => {
@@ -139,22 +155,38 @@ fn ClientSideConnectionStatus() -> impl IntoView {
     });
 
     // Track connection state changes
-    Effect::new(move |_| {
-        let state = ready_state.get();
-        match state {
-            ConnectionReadyState::Open => {
-                connection_established_at.set(Some(chrono::Utc::now()));
-                leptos::logging::log!("WebSocket connection established");
-            }
-            ConnectionReadyState::Closed => {
-                connection_established_at.set(None);
-                leptos::logging::log!("WebSocket connection closed");
-            }
-            ConnectionReadyState::Connecting => {
-                leptos::logging::log!("WebSocket connecting...");
-            }
-            ConnectionReadyState::Closing => {
-                leptos::logging::log!("WebSocket closing...");
+    Effect::new({
+        let send = send.clone();
+        move |_| {
+            let state = ready_state.get();
+            match state {
+                ConnectionReadyState::Open => {
+                    connection_established_at.set(Some(chrono::Utc::now()));
+                    leptos::logging::log!("WebSocket connection established");
+
+                    // Tie the connection to the signed-in session so the server
+                    // can sign it out when the session is revoked
+                    let send = send.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        if let Some(access_token) = AuthState::new().get_valid_access_token().await
+                        {
+                            let authenticate = WebSocketMessage::Authenticate { access_token };
+                            if let Ok(json) = serde_json::to_string(&authenticate) {
+                                send(&json);
+                            }
+                        }
+                    });
+                }
+                ConnectionReadyState::Closed => {
+                    connection_established_at.set(None);
+                    leptos::logging::log!("WebSocket connection closed");
+                }
+                ConnectionReadyState::Connecting => {
+                    leptos::logging::log!("WebSocket connecting...");
+                }
+                ConnectionReadyState::Closing => {
+                    leptos::logging::log!("WebSocket closing...");
+                }
             }
         }
     });
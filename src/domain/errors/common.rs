@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// A validation failure tied to a single request field, as returned to API
+/// clients so forms can show the message next to the offending input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    #[must_use]
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Body of a `400 Bad Request` response carrying field-level errors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationErrorResponse {
+    #[serde(default)]
+    pub errors: Vec<FieldError>,
+}
@@ -1 +1,3 @@
+pub mod common;
+pub mod transaction_errors;
 pub mod user_errors;
@@ -0,0 +1,110 @@
+use thiserror::Error;
+
+use crate::domain::value_objects::{
+    account_name::AccountNameError, category::CategoryError, currency::CurrencyError,
+    memo::MemoError, payee::PayeeError, tag::TagError, transaction_id::TransactionIdError,
+    user_id::UserIdError,
+};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TransactionDomainError {
+    #[error("Transaction ID is invalid")]
+    InvalidTransactionId,
+
+    #[error("Owner ID is invalid")]
+    InvalidOwnerId,
+
+    #[error("Invalid account: {message}")]
+    InvalidAccount { message: String },
+
+    #[error("Transaction amount cannot be zero")]
+    ZeroAmount,
+
+    #[error("Invalid currency: {message}")]
+    InvalidCurrency { message: String },
+
+    #[error("Invalid payee: {message}")]
+    InvalidPayee { message: String },
+
+    #[error("Invalid memo: {message}")]
+    InvalidMemo { message: String },
+
+    #[error("Invalid category: {message}")]
+    InvalidCategory { message: String },
+
+    #[error("Invalid tag: {message}")]
+    InvalidTag { message: String },
+
+    #[error("A transaction can have at most {max} tags")]
+    TooManyTags { max: usize },
+}
+
+impl From<TransactionIdError> for TransactionDomainError {
+    fn from(_err: TransactionIdError) -> Self {
+        TransactionDomainError::InvalidTransactionId
+    }
+}
+
+impl From<UserIdError> for TransactionDomainError {
+    fn from(_err: UserIdError) -> Self {
+        TransactionDomainError::InvalidOwnerId
+    }
+}
+
+impl From<AccountNameError> for TransactionDomainError {
+    fn from(err: AccountNameError) -> Self {
+        TransactionDomainError::InvalidAccount {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<CurrencyError> for TransactionDomainError {
+    fn from(err: CurrencyError) -> Self {
+        TransactionDomainError::InvalidCurrency {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<PayeeError> for TransactionDomainError {
+    fn from(err: PayeeError) -> Self {
+        TransactionDomainError::InvalidPayee {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<MemoError> for TransactionDomainError {
+    fn from(err: MemoError) -> Self {
+        TransactionDomainError::InvalidMemo {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<CategoryError> for TransactionDomainError {
+    fn from(err: CategoryError) -> Self {
+        TransactionDomainError::InvalidCategory {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<TagError> for TransactionDomainError {
+    fn from(err: TagError) -> Self {
+        TransactionDomainError::InvalidTag {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl TransactionDomainError {
+    #[must_use]
+    pub fn is_validation_error(&self) -> bool {
+        !matches!(
+            self,
+            TransactionDomainError::InvalidTransactionId | TransactionDomainError::InvalidOwnerId
+        )
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::domain::errors::common::FieldError;
+use crate::domain::models::user::UserUpdateError;
 use crate::domain::value_objects::{
     email::EmailError, password_hash::PasswordHashError, user_id::UserIdError,
     user_name::UserNameError,
@@ -25,8 +27,14 @@ pub enum UserDomainError {
     #[error("Email address is required")]
     EmailRequired,
 
-    #[error("Name contains invalid characters")]
-    InvalidNameFormat,
+    #[error("Invalid first name: {message}")]
+    InvalidFirstName { message: String },
+
+    #[error("Invalid last name: {message}")]
+    InvalidLastName { message: String },
+
+    #[error("One or more fields are invalid")]
+    Validation { errors: Vec<FieldError> },
 }
 
 impl From<EmailError> for UserDomainError {
@@ -43,6 +51,17 @@ impl From<EmailError> for UserDomainError {
     }
 }
 
+impl From<UserUpdateError> for UserDomainError {
+    fn from(error: UserUpdateError) -> Self {
+        match error {
+            UserUpdateError::InvalidEmail(e) => e.into(),
+            UserUpdateError::InvalidPasswordHash(e) => e.into(),
+            UserUpdateError::InvalidFirstName(e) => UserDomainError::first_name(&e),
+            UserUpdateError::InvalidLastName(e) => UserDomainError::last_name(&e),
+        }
+    }
+}
+
 impl From<PasswordHashError> for UserDomainError {
     fn from(err: PasswordHashError) -> Self {
         UserDomainError::WeakPassword {
@@ -57,19 +76,29 @@ impl From<UserIdError> for UserDomainError {
     }
 }
 
-impl From<UserNameError> for UserDomainError {
-    fn from(err: UserNameError) -> Self {
+impl UserDomainError {
+    /// Maps a name error for the first-name field.
+    #[must_use]
+    pub fn first_name(err: &UserNameError) -> Self {
         match err {
             UserNameError::Empty => UserDomainError::EmptyFirstName,
-            UserNameError::TooShort | UserNameError::TooLong | UserNameError::ContainsNumbers => {
-                UserDomainError::InvalidNameFormat
-            }
-            UserNameError::InvalidCharacters => UserDomainError::InvalidNameFormat,
+            other => UserDomainError::InvalidFirstName {
+                message: other.to_string(),
+            },
+        }
+    }
+
+    /// Maps a name error for the last-name field.
+    #[must_use]
+    pub fn last_name(err: &UserNameError) -> Self {
+        match err {
+            UserNameError::Empty => UserDomainError::EmptyLastName,
+            other => UserDomainError::InvalidLastName {
+                message: other.to_string(),
+            },
         }
     }
-}
 
-impl UserDomainError {
     #[must_use]
     pub fn is_validation_error(&self) -> bool {
         matches!(
@@ -79,7 +108,55 @@ impl UserDomainError {
                 | UserDomainError::EmptyFirstName
                 | UserDomainError::EmptyLastName
                 | UserDomainError::EmailRequired
-                | UserDomainError::InvalidNameFormat
+                | UserDomainError::InvalidFirstName { .. }
+                | UserDomainError::InvalidLastName { .. }
+                | UserDomainError::Validation { .. }
         )
     }
+
+    /// Name of the request field the error relates to, if it can be
+    /// attributed to a single one.
+    #[must_use]
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            UserDomainError::InvalidEmail { .. } | UserDomainError::EmailRequired => Some("email"),
+            UserDomainError::WeakPassword { .. } => Some("password"),
+            UserDomainError::EmptyFirstName | UserDomainError::InvalidFirstName { .. } => {
+                Some("first_name")
+            }
+            UserDomainError::EmptyLastName | UserDomainError::InvalidLastName { .. } => {
+                Some("last_name")
+            }
+            UserDomainError::InvalidUserId | UserDomainError::Validation { .. } => None,
+        }
+    }
+
+    /// Stable machine-readable error code for API clients.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            UserDomainError::InvalidEmail { .. } => "invalid_email",
+            UserDomainError::WeakPassword { .. } => "weak_password",
+            UserDomainError::EmptyFirstName => "first_name_required",
+            UserDomainError::EmptyLastName => "last_name_required",
+            UserDomainError::InvalidUserId => "invalid_user_id",
+            UserDomainError::EmailRequired => "email_required",
+            UserDomainError::InvalidFirstName { .. } => "invalid_first_name",
+            UserDomainError::InvalidLastName { .. } => "invalid_last_name",
+            UserDomainError::Validation { .. } => "validation_failed",
+        }
+    }
+
+    /// Flattens the error into per-field errors for API responses. Errors
+    /// that cannot be attributed to a field yield an empty list.
+    #[must_use]
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            UserDomainError::Validation { errors } => errors.clone(),
+            other => other
+                .field()
+                .map(|field| vec![FieldError::new(field, other.code(), other.to_string())])
+                .unwrap_or_default(),
+        }
+    }
 }
@@ -2,19 +2,34 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
+        use crate::application::transaction_service::TransactionServiceImpl;
         use crate::application::user_service::UserService;
+        use crate::infrastructure::auth::jwt_service::AuthService;
         use crate::infrastructure::config::app_config::AppConfig;
+        use crate::infrastructure::web::websocket::SharedConnectionManager;
 
         pub struct AppState<T: for<'a> UserService<'a> + Send + Sync + 'static> {
             user_service: T,
+            transaction_service: TransactionServiceImpl,
+            auth_service: AuthService,
             app_config: AppConfig,
+            connection_manager: SharedConnectionManager,
         }
 
         impl<T: for<'a> UserService<'a> + Send + Sync + 'static> AppState<T> {
-            pub fn new(user_service: T, app_config: AppConfig) -> Self {
+            pub fn new(
+                user_service: T,
+                transaction_service: TransactionServiceImpl,
+                auth_service: AuthService,
+                app_config: AppConfig,
+                connection_manager: SharedConnectionManager,
+            ) -> Self {
                 Self {
                     user_service,
+                    transaction_service,
+                    auth_service,
                     app_config,
+                    connection_manager,
                 }
             }
 
@@ -22,15 +37,30 @@ cfg_if! {
                 &self.user_service
             }
 
+            pub fn transaction_service(&self) -> &TransactionServiceImpl {
+                &self.transaction_service
+            }
+
+            pub fn auth_service(&self) -> &AuthService {
+                &self.auth_service
+            }
+
             pub fn app_config(&self) -> &AppConfig {
                 &self.app_config
             }
+
+            pub fn connection_manager(&self) -> &SharedConnectionManager {
+                &self.connection_manager
+            }
         }
         impl<T: for<'a> UserService<'a> + Clone + Send + Sync> Clone for AppState<T> {
             fn clone(&self) -> Self {
                 Self {
                     user_service: self.user_service.clone(),
+                    transaction_service: self.transaction_service.clone(),
+                    auth_service: self.auth_service.clone(),
                     app_config: self.app_config.clone(),
+                    connection_manager: self.connection_manager.clone(),
                 }
             }
         }
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Limits on failed sign-in attempts.
+///
+/// Failures are counted per account and per client IP. Once a counter
+/// reaches its limit, every further failure locks that key for
+/// `base_lockout_seconds`, doubling each time up to `max_lockout_seconds`.
+/// A counter starts over after `failure_window_seconds` without failures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoginThrottlePolicy {
+    pub max_failures_per_account: u32,
+    /// Higher than the account limit, as many users can share an address.
+    pub max_failures_per_ip: u32,
+    pub base_lockout_seconds: u64,
+    pub max_lockout_seconds: u64,
+    pub failure_window_seconds: u64,
+}
+
+impl Default for LoginThrottlePolicy {
+    fn default() -> Self {
+        Self {
+            max_failures_per_account: 5,
+            max_failures_per_ip: 20,
+            base_lockout_seconds: 30,
+            max_lockout_seconds: 15 * 60,
+            failure_window_seconds: 60 * 60,
+        }
+    }
+}
+
+impl LoginThrottlePolicy {
+    /// How long to lock a key after its `failures`-th failure, given the
+    /// key's `limit`. `None` while the key is still under its limit.
+    #[must_use]
+    pub fn lockout_after(&self, failures: u32, limit: u32) -> Option<Duration> {
+        let excess = failures.checked_sub(limit)?;
+        let seconds = self
+            .base_lockout_seconds
+            .saturating_mul(2u64.saturating_pow(excess))
+            .min(self.max_lockout_seconds);
+        Some(Duration::seconds(
+            i64::try_from(seconds).unwrap_or(i64::MAX),
+        ))
+    }
+
+    /// Failures before this instant no longer count.
+    #[must_use]
+    pub fn window_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::seconds(i64::try_from(self.failure_window_seconds).unwrap_or(i64::MAX))
+    }
+}
+
+/// Failed sign-in attempts recorded for one throttling key, such as an
+/// account or a client IP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginAttempts {
+    /// The throttling key, e.g. `account:<hash>` or `ip:<hash>`.
+    id: String,
+    failed_count: u32,
+    last_failed_at: DateTime<Utc>,
+    #[serde(default)]
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginAttempts {
+    /// Rebuilds a record loaded from storage.
+    #[must_use]
+    pub fn restore(
+        key: String,
+        failed_count: u32,
+        last_failed_at: DateTime<Utc>,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: key,
+            failed_count,
+            last_failed_at,
+            locked_until,
+        }
+    }
+
+    /// Records a failure at `now`, starting the count over if the previous
+    /// failure happened before `window_start`.
+    pub fn record_failure(&mut self, now: DateTime<Utc>, window_start: DateTime<Utc>) {
+        self.failed_count = if self.last_failed_at < window_start {
+            1
+        } else {
+            self.failed_count.saturating_add(1)
+        };
+        self.last_failed_at = now;
+    }
+
+    /// Starts the record for a key's first failure.
+    #[must_use]
+    pub fn first_failure(key: String, now: DateTime<Utc>) -> Self {
+        Self::restore(key, 1, now, None)
+    }
+
+    pub fn lock_until(&mut self, until: DateTime<Utc>) {
+        self.locked_until = Some(until);
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn failed_count(&self) -> u32 {
+        self.failed_count
+    }
+
+    #[must_use]
+    pub fn last_failed_at(&self) -> DateTime<Utc> {
+        self.last_failed_at
+    }
+
+    #[must_use]
+    pub fn locked_until(&self) -> Option<DateTime<Utc>> {
+        self.locked_until
+    }
+
+    /// Time left on the lock at `now`, if the key is locked.
+    #[must_use]
+    pub fn remaining_lockout(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.locked_until
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LoginThrottlePolicy {
+        LoginThrottlePolicy {
+            base_lockout_seconds: 30,
+            max_lockout_seconds: 300,
+            ..LoginThrottlePolicy::default()
+        }
+    }
+
+    #[test]
+    fn does_not_lock_below_the_limit() {
+        assert_eq!(policy().lockout_after(4, 5), None);
+    }
+
+    #[test]
+    fn doubles_the_lockout_for_each_failure_past_the_limit() {
+        let policy = policy();
+
+        assert_eq!(policy.lockout_after(5, 5), Some(Duration::seconds(30)));
+        assert_eq!(policy.lockout_after(6, 5), Some(Duration::seconds(60)));
+        assert_eq!(policy.lockout_after(7, 5), Some(Duration::seconds(120)));
+        assert_eq!(policy.lockout_after(8, 5), Some(Duration::seconds(240)));
+    }
+
+    #[test]
+    fn caps_the_lockout_without_overflowing() {
+        let policy = policy();
+
+        assert_eq!(policy.lockout_after(9, 5), Some(Duration::seconds(300)));
+        assert_eq!(
+            policy.lockout_after(u32::MAX, 5),
+            Some(Duration::seconds(300))
+        );
+    }
+
+    #[test]
+    fn starts_counting_over_after_a_quiet_window() {
+        let policy = policy();
+        let start = Utc::now();
+        let mut attempts = LoginAttempts::first_failure("account:test".to_string(), start);
+
+        let soon = start + Duration::minutes(1);
+        attempts.record_failure(soon, policy.window_start(soon));
+        assert_eq!(attempts.failed_count(), 2);
+
+        let later =
+            soon + Duration::seconds(i64::try_from(policy.failure_window_seconds).unwrap() + 1);
+        attempts.record_failure(later, policy.window_start(later));
+        assert_eq!(attempts.failed_count(), 1);
+    }
+
+    #[test]
+    fn reports_time_left_only_while_locked() {
+        let now = Utc::now();
+        let mut attempts = LoginAttempts::first_failure("ip:test".to_string(), now);
+        attempts.lock_until(now + Duration::seconds(30));
+
+        assert_eq!(attempts.remaining_lockout(now), Some(Duration::seconds(30)));
+        assert_eq!(
+            attempts.remaining_lockout(now + Duration::seconds(30)),
+            None
+        );
+    }
+}
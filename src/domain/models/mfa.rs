@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+/// A TOTP authenticator (RFC 6238) registered to a user.
+///
+/// The credential is stored as soon as enrollment starts, but it only
+/// guards sign-in once the user has confirmed it with a valid code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpCredential {
+    /// Base32-encoded shared secret.
+    secret: String,
+    enabled: bool,
+    /// Time step of the last accepted code. Codes from this step or earlier
+    /// are rejected, so each code can be used only once.
+    #[serde(default)]
+    last_used_step: Option<i64>,
+}
+
+impl TotpCredential {
+    /// Starts an unconfirmed credential for `secret`.
+    #[must_use]
+    pub fn new(secret: String) -> Self {
+        Self {
+            secret,
+            enabled: false,
+            last_used_step: None,
+        }
+    }
+
+    /// Rebuilds a credential loaded from storage.
+    #[must_use]
+    pub fn restore(secret: String, enabled: bool, last_used_step: Option<i64>) -> Self {
+        Self {
+            secret,
+            enabled,
+            last_used_step,
+        }
+    }
+
+    #[must_use]
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    /// Whether enrollment has been confirmed, so sign-in requires a code.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[must_use]
+    pub fn last_used_step(&self) -> Option<i64> {
+        self.last_used_step
+    }
+
+    /// Confirms enrollment with the code accepted at `step`.
+    pub fn enable(&mut self, step: i64) {
+        self.enabled = true;
+        self.record_used_step(step);
+    }
+
+    /// Whether a code from `step` may still be accepted.
+    #[must_use]
+    pub fn is_unused_step(&self, step: i64) -> bool {
+        self.last_used_step.is_none_or(|last| step > last)
+    }
+
+    /// Records that a code was accepted at `step`.
+    pub fn record_used_step(&mut self, step: i64) {
+        self.last_used_step = Some(self.last_used_step.map_or(step, |last| last.max(step)));
+    }
+}
+
+/// Body of `POST /api/users/me/mfa/totp`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartTotpEnrollmentRequest {
+    pub current_password: String,
+}
+
+/// Returned when TOTP enrollment starts: the secret to enter into an
+/// authenticator app, and the same secret as an `otpauth://` URI for a QR
+/// code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpEnrollmentResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// Body of `POST /api/users/me/mfa/totp/confirm`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmTotpRequest {
+    pub code: String,
+}
+
+/// Body of `POST /api/users/me/mfa/recovery-codes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegenerateRecoveryCodesRequest {
+    pub current_password: String,
+}
+
+/// Freshly generated recovery codes. They are shown once; only their
+/// digests are stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Body of `DELETE /api/users/me/mfa`. Once two-factor authentication is
+/// enabled, `code` must be an authenticator code or an unused recovery code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisableMfaRequest {
+    pub current_password: String,
+    #[serde(default)]
+    pub code: String,
+}
+
+/// Body of `POST /api/auth/login/mfa`: the challenge token from the password
+/// step and either an authenticator code or a recovery code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaLoginRequest {
+    pub mfa_token: String,
+    pub code: String,
+}
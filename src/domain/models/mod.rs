@@ -1,2 +1,10 @@
 pub mod app_state;
+pub mod login_throttle;
+pub mod mfa;
+pub mod one_time_token;
+pub mod password_policy;
+pub mod personal_access_token;
+pub mod refresh_token;
+pub mod session;
+pub mod transaction;
 pub mod user;
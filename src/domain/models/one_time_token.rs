@@ -0,0 +1,152 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a one-time token may be used for. A token issued for one purpose is
+/// never accepted for another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPurpose {
+    EmailVerification,
+    PasswordReset,
+    /// A two-factor recovery code, usable instead of an authenticator code.
+    MfaRecovery,
+    /// The challenge between the password and second-factor sign-in steps.
+    MfaChallenge,
+}
+
+impl TokenPurpose {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "email_verification",
+            TokenPurpose::PasswordReset => "password_reset",
+            TokenPurpose::MfaRecovery => "mfa_recovery",
+            TokenPurpose::MfaChallenge => "mfa_challenge",
+        }
+    }
+}
+
+impl fmt::Display for TokenPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TokenPurpose {
+    type Err = color_eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "email_verification" => Ok(TokenPurpose::EmailVerification),
+            "password_reset" => Ok(TokenPurpose::PasswordReset),
+            "mfa_recovery" => Ok(TokenPurpose::MfaRecovery),
+            "mfa_challenge" => Ok(TokenPurpose::MfaChallenge),
+            other => Err(color_eyre::eyre::eyre!("Unknown token purpose '{other}'")),
+        }
+    }
+}
+
+/// Server-side record of a single-use token sent to a user out of band,
+/// e.g. in an email link.
+///
+/// The record is keyed by an identifier the token carries, never by the
+/// token itself, so a leaked store cannot be replayed. Consuming a token
+/// records `used_at`; after that, or once it expires, it is rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OneTimeToken {
+    id: String,
+    user_id: UserId,
+    purpose: TokenPurpose,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    #[serde(default)]
+    used_at: Option<DateTime<Utc>>,
+}
+
+impl OneTimeToken {
+    #[must_use]
+    pub fn new(
+        id: String,
+        user_id: UserId,
+        purpose: TokenPurpose,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            purpose,
+            created_at: Utc::now(),
+            expires_at,
+            used_at: None,
+        }
+    }
+
+    /// Rebuilds a token loaded from storage.
+    #[must_use]
+    pub fn restore(
+        id: String,
+        user_id: UserId,
+        purpose: TokenPurpose,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        used_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            purpose,
+            created_at,
+            expires_at,
+            used_at,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    #[must_use]
+    pub fn purpose(&self) -> TokenPurpose {
+        self.purpose
+    }
+
+    #[must_use]
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    #[must_use]
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    #[must_use]
+    pub fn used_at(&self) -> Option<DateTime<Utc>> {
+        self.used_at
+    }
+
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// A token can be consumed only while it is unused and not expired.
+    #[must_use]
+    pub fn is_usable(&self) -> bool {
+        self.used_at.is_none() && !self.is_expired()
+    }
+
+    pub fn mark_used(&mut self) {
+        if self.used_at.is_none() {
+            self.used_at = Some(Utc::now());
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::domain::errors::user_errors::UserDomainError;
+use serde::{Deserialize, Serialize};
+
+/// bcrypt silently ignores everything past the 72nd byte of its input.
+pub const BCRYPT_MAX_PASSWORD_BYTES: usize = 72;
+
+/// Shortest name or email fragment that is checked for inside a password;
+/// shorter fragments match too much by accident.
+const MIN_PERSONAL_FRAGMENT_LEN: usize = 3;
+
+/// Common passwords rejected outright, one per line, lowercase.
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+/// Rules a plaintext password must satisfy before it is hashed.
+// Each flag is an independent, individually configurable rule.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters.
+    pub min_length: usize,
+    /// Maximum length in bytes, capped at [`BCRYPT_MAX_PASSWORD_BYTES`].
+    pub max_bytes: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Reject passwords found in the bundled common-password list.
+    pub reject_common: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_bytes: BCRYPT_MAX_PASSWORD_BYTES,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: false,
+            reject_common: true,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Checks `password` against the policy. `email` and `names` are the
+    /// account's personal details, which the password must not contain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::WeakPassword` if the password:
+    /// - Is shorter than `min_length` characters
+    /// - Is longer than `max_bytes` bytes
+    /// - Lacks a required character class
+    /// - Appears in the common-password list
+    /// - Contains the email's local part or one of the names
+    pub fn validate(
+        &self,
+        password: &str,
+        email: &str,
+        names: &[&str],
+    ) -> Result<(), UserDomainError> {
+        let weak = |message: String| Err(UserDomainError::WeakPassword { message });
+
+        if password.chars().count() < self.min_length {
+            return weak(format!(
+                "Password must be at least {} characters long",
+                self.min_length
+            ));
+        }
+
+        let max_bytes = self.max_bytes.min(BCRYPT_MAX_PASSWORD_BYTES);
+        if password.len() > max_bytes {
+            return weak(format!("Password must be at most {max_bytes} bytes long"));
+        }
+
+        let mut missing = Vec::new();
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            missing.push("a lowercase letter");
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            missing.push("an uppercase letter");
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            missing.push("a digit");
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            missing.push("a symbol");
+        }
+        if !missing.is_empty() {
+            return weak(format!("Password must contain {}", missing.join(", ")));
+        }
+
+        let lowered = password.to_lowercase();
+
+        if self.reject_common && Self::is_common(&lowered) {
+            return weak("Password is too common".to_string());
+        }
+
+        let local_part = email.split('@').next().unwrap_or_default();
+        let contains_personal = std::iter::once(local_part)
+            .chain(names.iter().copied())
+            .map(|fragment| fragment.trim().to_lowercase())
+            .filter(|fragment| fragment.chars().count() >= MIN_PERSONAL_FRAGMENT_LEN)
+            .any(|fragment| lowered.contains(&fragment));
+        if contains_personal {
+            return weak("Password must not contain your email or name".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn is_common(lowered: &str) -> bool {
+        COMMON_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .any(|common| common == lowered)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<(), UserDomainError>) -> String {
+        match result {
+            Err(UserDomainError::WeakPassword { message }) => message,
+            other => panic!("expected a weak password, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_a_password_meeting_every_rule() {
+        let policy = PasswordPolicy::default();
+
+        assert!(
+            policy
+                .validate("Tangerine-Harbor-42", "ada@example.com", &["Ada", "Byron"])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn enforces_length_in_characters_and_bytes() {
+        let policy = PasswordPolicy::default();
+
+        assert!(message(policy.validate("Ab1", "ada@example.com", &[])).contains("at least 8"));
+        // Eight characters, but more than eight bytes, is long enough.
+        assert!(policy.validate("Ünïcødé1", "ada@example.com", &[]).is_ok());
+
+        let too_long = format!("Aa1{}", "x".repeat(BCRYPT_MAX_PASSWORD_BYTES));
+        assert!(message(policy.validate(&too_long, "ada@example.com", &[])).contains("at most 72"));
+
+        // A configured maximum above what bcrypt reads is capped.
+        let generous = PasswordPolicy {
+            max_bytes: 1024,
+            ..PasswordPolicy::default()
+        };
+        assert!(
+            generous
+                .validate(&too_long, "ada@example.com", &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn lists_every_missing_character_class() {
+        let policy = PasswordPolicy {
+            require_symbol: true,
+            ..PasswordPolicy::default()
+        };
+
+        assert_eq!(
+            message(policy.validate("abcdefghij", "ada@example.com", &[])),
+            "Password must contain an uppercase letter, a digit, a symbol"
+        );
+        assert!(
+            policy
+                .validate("abcdefghiJ1!", "ada@example.com", &[])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_common_passwords_regardless_of_case() {
+        let policy = PasswordPolicy::default();
+
+        assert_eq!(
+            message(policy.validate("Passw0rd", "ada@example.com", &[])),
+            "Password is too common"
+        );
+
+        let permissive = PasswordPolicy {
+            reject_common: false,
+            ..PasswordPolicy::default()
+        };
+        assert!(
+            permissive
+                .validate("Passw0rd", "ada@example.com", &[])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_personal_details_but_ignores_short_fragments() {
+        let policy = PasswordPolicy::default();
+
+        assert!(
+            policy
+                .validate("Lovelace2024x", "lovelace@example.com", &[])
+                .is_err()
+        );
+        assert!(
+            policy
+                .validate("Xbyron2024x", "ada@example.com", &["Ada", " Byron "])
+                .is_err()
+        );
+        // Two-letter fragments are too short to count as a match.
+        assert!(
+            policy
+                .validate("Always2024x", "al@example.com", &["Al"])
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::domain::errors::common::FieldError;
+use crate::domain::errors::user_errors::UserDomainError;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a personal access token may do. Scopes only narrow what the
+/// owner's session could do; account management always needs a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenScope {
+    #[serde(rename = "transactions:read")]
+    TransactionsRead,
+    #[serde(rename = "transactions:write")]
+    TransactionsWrite,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 2] = [TokenScope::TransactionsRead, TokenScope::TransactionsWrite];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            TokenScope::TransactionsRead => "transactions:read",
+            TokenScope::TransactionsWrite => "transactions:write",
+        }
+    }
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TokenScope {
+    type Err = color_eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "transactions:read" => Ok(TokenScope::TransactionsRead),
+            "transactions:write" => Ok(TokenScope::TransactionsWrite),
+            other => Err(color_eyre::eyre::eyre!("Unknown token scope '{other}'")),
+        }
+    }
+}
+
+/// A long-lived token a user creates for scripts and integrations.
+///
+/// Only the SHA-256 digest of the token is stored; the token itself is shown
+/// once, when it is created. It carries its record's `id`, so a presented
+/// token is found with a point read and then compared by digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalAccessToken {
+    id: String,
+    user_id: UserId,
+    name: String,
+    token_hash: String,
+    scopes: Vec<TokenScope>,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    #[must_use]
+    pub fn new(
+        id: String,
+        user_id: UserId,
+        name: String,
+        token_hash: String,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            name,
+            token_hash,
+            scopes,
+            created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
+        }
+    }
+
+    /// Rebuilds a token loaded from storage.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn restore(
+        id: String,
+        user_id: UserId,
+        name: String,
+        token_hash: String,
+        scopes: Vec<TokenScope>,
+        created_at: DateTime<Utc>,
+        expires_at: Option<DateTime<Utc>>,
+        last_used_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            name,
+            token_hash,
+            scopes,
+            created_at,
+            expires_at,
+            last_used_at,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    #[must_use]
+    pub fn scopes(&self) -> &[TokenScope] {
+        &self.scopes
+    }
+
+    #[must_use]
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    #[must_use]
+    pub fn last_used_at(&self) -> Option<DateTime<Utc>> {
+        self.last_used_at
+    }
+
+    /// Tokens without an expiry stay valid until revoked.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    #[must_use]
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn record_use(&mut self, used_at: DateTime<Utc>) {
+        self.last_used_at = Some(used_at);
+    }
+
+    /// Scopes as stored in SQL: space-separated, as in an OAuth `scope`.
+    #[must_use]
+    pub fn scopes_to_string(scopes: &[TokenScope]) -> String {
+        scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Parses scopes stored by [`Self::scopes_to_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first unknown scope.
+    pub fn parse_scopes(value: &str) -> color_eyre::Result<Vec<TokenScope>> {
+        value.split_whitespace().map(str::parse).collect()
+    }
+}
+
+/// Body of `POST /api/users/me/tokens`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAccessTokenRequest {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    /// Omit for a token that never expires.
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+impl CreateAccessTokenRequest {
+    pub const MAX_NAME_LENGTH: usize = 100;
+    pub const MAX_EXPIRES_IN_DAYS: u32 = 365;
+
+    /// Validates every field, collecting all failures.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::Validation` listing each invalid field.
+    pub fn validate(&self) -> Result<(), UserDomainError> {
+        let mut errors: Vec<FieldError> = Vec::new();
+
+        let name = self.name.trim();
+        if name.is_empty() {
+            errors.push(FieldError::new("name", "name_required", "Name is required"));
+        } else if name.chars().count() > Self::MAX_NAME_LENGTH {
+            errors.push(FieldError::new(
+                "name",
+                "name_too_long",
+                format!("Name must be at most {} characters", Self::MAX_NAME_LENGTH),
+            ));
+        }
+        if self.scopes.is_empty() {
+            errors.push(FieldError::new(
+                "scopes",
+                "scopes_required",
+                "Select at least one scope",
+            ));
+        }
+        if let Some(days) = self.expires_in_days
+            && !(1..=Self::MAX_EXPIRES_IN_DAYS).contains(&days)
+        {
+            errors.push(FieldError::new(
+                "expires_in_days",
+                "invalid_expiry",
+                format!(
+                    "Expiry must be between 1 and {} days",
+                    Self::MAX_EXPIRES_IN_DAYS
+                ),
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(UserDomainError::Validation { errors })
+        }
+    }
+}
+
+/// A personal access token as listed to its owner, without the secret.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessTokenSummary {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<&PersonalAccessToken> for AccessTokenSummary {
+    fn from(token: &PersonalAccessToken) -> Self {
+        Self {
+            id: token.id().to_string(),
+            name: token.name().to_string(),
+            scopes: token.scopes().to_vec(),
+            created_at: token.created_at(),
+            expires_at: token.expires_at(),
+            last_used_at: token.last_used_at(),
+        }
+    }
+}
+
+/// Returned once when a token is created; the token cannot be shown again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedAccessTokenResponse {
+    pub token: String,
+    #[serde(flatten)]
+    pub summary: AccessTokenSummary,
+}
@@ -0,0 +1,147 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Server-side record of an issued refresh token, keyed by the token's `jti`.
+///
+/// Every token minted from the same login shares a `family_id`, so a whole
+/// login session can be revoked at once. Tokens are single-use: exchanging
+/// one records `used_at` and the id of the token that replaced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenRecord {
+    id: String,
+    family_id: String,
+    user_id: UserId,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    revoked: bool,
+    revoked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    used_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    replaced_by: Option<String>,
+}
+
+impl RefreshTokenRecord {
+    #[must_use]
+    pub fn new(
+        token_id: String,
+        family_id: String,
+        user_id: UserId,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: token_id,
+            family_id,
+            user_id,
+            issued_at: Utc::now(),
+            expires_at,
+            revoked: false,
+            revoked_at: None,
+            used_at: None,
+            replaced_by: None,
+        }
+    }
+
+    /// Rebuilds a record loaded from storage.
+    #[must_use]
+    pub fn restore(
+        token_id: String,
+        family_id: String,
+        user_id: UserId,
+        issued_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        revoked_at: Option<DateTime<Utc>>,
+        used_at: Option<DateTime<Utc>>,
+        replaced_by: Option<String>,
+    ) -> Self {
+        Self {
+            id: token_id,
+            family_id,
+            user_id,
+            issued_at,
+            expires_at,
+            revoked: revoked_at.is_some(),
+            revoked_at,
+            used_at,
+            replaced_by,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn family_id(&self) -> &str {
+        &self.family_id
+    }
+
+    #[must_use]
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    #[must_use]
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+
+    #[must_use]
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    #[must_use]
+    pub fn revoked_at(&self) -> Option<DateTime<Utc>> {
+        self.revoked_at
+    }
+
+    #[must_use]
+    pub fn used_at(&self) -> Option<DateTime<Utc>> {
+        self.used_at
+    }
+
+    #[must_use]
+    pub fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    #[must_use]
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+
+    #[must_use]
+    pub fn replaced_by(&self) -> Option<&str> {
+        self.replaced_by.as_deref()
+    }
+
+    /// A token can be exchanged only while it is unused, not revoked and not
+    /// expired.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !self.is_used() && !self.is_revoked() && !self.is_expired()
+    }
+
+    /// Marks the token as exchanged for `replacement_id`.
+    pub fn mark_used(&mut self, replacement_id: &str) {
+        if self.used_at.is_none() {
+            self.used_at = Some(Utc::now());
+            self.replaced_by = Some(replacement_id.to_string());
+        }
+    }
+
+    pub fn revoke(&mut self) {
+        if !self.revoked {
+            self.revoked = true;
+            self.revoked_at = Some(Utc::now());
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A signed-in device or browser.
+///
+/// A session is one refresh-token family: its `id` is the `family_id` every
+/// token minted from the sign-in shares, and access tokens carry it too.
+/// Revoking the family ends the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    id: String,
+    user_id: UserId,
+    #[serde(default)]
+    user_agent: Option<String>,
+    #[serde(default)]
+    ip_address: Option<String>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    /// When the newest refresh token of the family expires.
+    expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// Longer `User-Agent` headers are cut to this many characters.
+    pub const MAX_USER_AGENT_LENGTH: usize = 512;
+
+    #[must_use]
+    pub fn new(
+        id: String,
+        user_id: UserId,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            user_id,
+            user_agent: user_agent.map(|user_agent| {
+                user_agent
+                    .chars()
+                    .take(Self::MAX_USER_AGENT_LENGTH)
+                    .collect()
+            }),
+            ip_address,
+            created_at: now,
+            last_seen_at: now,
+            expires_at,
+        }
+    }
+
+    /// Rebuilds a session loaded from storage.
+    #[must_use]
+    pub fn restore(
+        id: String,
+        user_id: UserId,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        created_at: DateTime<Utc>,
+        last_seen_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            user_agent,
+            ip_address,
+            created_at,
+            last_seen_at,
+            expires_at,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    #[must_use]
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
+    #[must_use]
+    pub fn ip_address(&self) -> Option<&str> {
+        self.ip_address.as_deref()
+    }
+
+    #[must_use]
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    #[must_use]
+    pub fn last_seen_at(&self) -> DateTime<Utc> {
+        self.last_seen_at
+    }
+
+    #[must_use]
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// A session whose refresh tokens have all expired cannot be resumed.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// Records a token refresh. The address is only replaced when known.
+    pub fn record_activity(
+        &mut self,
+        seen_at: DateTime<Utc>,
+        ip_address: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) {
+        self.last_seen_at = seen_at;
+        self.expires_at = expires_at;
+        if ip_address.is_some() {
+            self.ip_address = ip_address;
+        }
+    }
+}
+
+/// A session as listed to its owner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Whether this is the session the listing request was made from.
+    pub current: bool,
+}
+
+impl SessionSummary {
+    #[must_use]
+    pub fn new(session: &Session, current_session_id: Option<&str>) -> Self {
+        Self {
+            id: session.id().to_string(),
+            user_agent: session.user_agent().map(str::to_string),
+            ip_address: session.ip_address().map(str::to_string),
+            created_at: session.created_at(),
+            last_seen_at: session.last_seen_at(),
+            current: current_session_id == Some(session.id()),
+        }
+    }
+}
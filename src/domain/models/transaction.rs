@@ -0,0 +1,530 @@
+use crate::domain::{
+    errors::transaction_errors::TransactionDomainError,
+    value_objects::{AccountName, Category, Memo, Money, Payee, Tag, TransactionId, UserId},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    id: TransactionId,
+    owner_id: UserId,
+    account: AccountName,
+    amount: Money,
+    booked_on: NaiveDate,
+    value_on: NaiveDate,
+    payee: Payee,
+    memo: Option<Memo>,
+    category: Category,
+    tags: Vec<Tag>,
+    #[serde(with = "sortable_timestamp")]
+    created_at: DateTime<Utc>,
+    #[serde(with = "sortable_timestamp")]
+    updated_at: DateTime<Utc>,
+}
+
+impl Transaction {
+    pub const MAX_TAGS: usize = 10;
+
+    /// Create a new transaction with validation.
+    ///
+    /// `amount` is signed: negative for money leaving the account. When
+    /// `value_on` is `None` the value date defaults to the booking date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionDomainError` if any of the provided parameters are invalid:
+    /// - `ZeroAmount` if the amount is zero
+    /// - `InvalidAccount`, `InvalidPayee`, `InvalidMemo`, `InvalidCategory`
+    ///   or `InvalidTag` if the matching value fails validation
+    /// - `TooManyTags` if more than `MAX_TAGS` distinct tags are given
+    pub fn new(
+        owner_id: UserId,
+        account: &str,
+        amount: Money,
+        booked_on: NaiveDate,
+        value_on: Option<NaiveDate>,
+        payee: &str,
+        memo: Option<&str>,
+        category: &str,
+        tags: &[String],
+    ) -> Result<Self, TransactionDomainError> {
+        if amount.is_zero() {
+            return Err(TransactionDomainError::ZeroAmount);
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: TransactionId::generate(),
+            owner_id,
+            account: AccountName::new(account)?,
+            amount,
+            booked_on,
+            value_on: value_on.unwrap_or(booked_on),
+            payee: Payee::new(payee)?,
+            memo: Self::parse_memo(memo)?,
+            category: Category::new(category)?,
+            tags: Self::parse_tags(tags)?,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Rebuilds a transaction loaded from storage, re-validating every field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionDomainError` under the same conditions as
+    /// [`Transaction::new`], or if `id` or `owner_id` is invalid.
+    pub fn restore(
+        id: &str,
+        owner_id: &str,
+        account: &str,
+        amount: Money,
+        booked_on: NaiveDate,
+        value_on: NaiveDate,
+        payee: &str,
+        memo: Option<&str>,
+        category: &str,
+        tags: &[String],
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<Self, TransactionDomainError> {
+        let transaction = Self::new(
+            UserId::new(owner_id.to_string())?,
+            account,
+            amount,
+            booked_on,
+            Some(value_on),
+            payee,
+            memo,
+            category,
+            tags,
+        )?;
+
+        Ok(Self {
+            id: TransactionId::new(id.to_string())?,
+            created_at,
+            updated_at,
+            ..transaction
+        })
+    }
+
+    fn parse_memo(memo: Option<&str>) -> Result<Option<Memo>, TransactionDomainError> {
+        match memo.map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(memo) => Ok(Some(Memo::new(memo)?)),
+        }
+    }
+
+    fn parse_tags(tags: &[String]) -> Result<Vec<Tag>, TransactionDomainError> {
+        let mut parsed: Vec<Tag> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = Tag::new(tag)?;
+            if !parsed.contains(&tag) {
+                parsed.push(tag);
+            }
+        }
+
+        if parsed.len() > Self::MAX_TAGS {
+            return Err(TransactionDomainError::TooManyTags {
+                max: Self::MAX_TAGS,
+            });
+        }
+
+        Ok(parsed)
+    }
+
+    /// Replaces every editable field, validating exactly as [`Transaction::new`]
+    /// does. The id, owner and creation time are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionDomainError` under the same conditions as [`Transaction::new`].
+    pub fn update(
+        &mut self,
+        account: &str,
+        amount: Money,
+        booked_on: NaiveDate,
+        value_on: Option<NaiveDate>,
+        payee: &str,
+        memo: Option<&str>,
+        category: &str,
+        tags: &[String],
+    ) -> Result<(), TransactionDomainError> {
+        let updated = Self::new(
+            self.owner_id.clone(),
+            account,
+            amount,
+            booked_on,
+            value_on,
+            payee,
+            memo,
+            category,
+            tags,
+        )?;
+
+        *self = Self {
+            id: self.id.clone(),
+            created_at: self.created_at,
+            ..updated
+        };
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &TransactionId {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn owner_id(&self) -> &UserId {
+        &self.owner_id
+    }
+
+    #[must_use]
+    pub fn account(&self) -> &AccountName {
+        &self.account
+    }
+
+    #[must_use]
+    pub fn amount(&self) -> Money {
+        self.amount
+    }
+
+    #[must_use]
+    pub fn booked_on(&self) -> NaiveDate {
+        self.booked_on
+    }
+
+    #[must_use]
+    pub fn value_on(&self) -> NaiveDate {
+        self.value_on
+    }
+
+    #[must_use]
+    pub fn payee(&self) -> &Payee {
+        &self.payee
+    }
+
+    #[must_use]
+    pub fn memo(&self) -> Option<&Memo> {
+        self.memo.as_ref()
+    }
+
+    #[must_use]
+    pub fn category(&self) -> &Category {
+        &self.category
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    #[must_use]
+    pub fn is_expense(&self) -> bool {
+        self.amount.is_negative()
+    }
+
+    #[must_use]
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    #[must_use]
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+/// Body of `POST /api/transactions` and `PUT /api/transactions/{id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTransactionRequest {
+    pub account: String,
+    /// Signed amount in the currency's minor units, e.g. `-8950` for -89.50 USD.
+    pub amount_minor: i64,
+    pub currency: String,
+    pub booked_on: NaiveDate,
+    #[serde(default)]
+    pub value_on: Option<NaiveDate>,
+    pub payee: String,
+    #[serde(default)]
+    pub memo: Option<String>,
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionResponse {
+    pub id: String,
+    pub account: String,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub booked_on: NaiveDate,
+    pub value_on: NaiveDate,
+    pub payee: String,
+    pub memo: Option<String>,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Transaction> for TransactionResponse {
+    fn from(transaction: Transaction) -> Self {
+        Self {
+            id: transaction.id.to_string(),
+            account: transaction.account.to_string(),
+            amount_minor: transaction.amount.amount_minor(),
+            currency: transaction.amount.currency().to_string(),
+            booked_on: transaction.booked_on,
+            value_on: transaction.value_on,
+            payee: transaction.payee.to_string(),
+            memo: transaction.memo.map(|memo| memo.to_string()),
+            category: transaction.category.to_string(),
+            tags: transaction.tags.iter().map(ToString::to_string).collect(),
+            created_at: transaction.created_at,
+            updated_at: transaction.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionListResponse {
+    pub items: Vec<TransactionResponse>,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page.
+    pub continuation: Option<String>,
+}
+
+impl fmt::Display for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transaction {{ id: {}, owner_id: {}, account: {}, amount: {}, booked_on: {}, payee: {}, category: {} }}",
+            self.id,
+            self.owner_id,
+            self.account,
+            self.amount,
+            self.booked_on,
+            self.payee,
+            self.category
+        )
+    }
+}
+
+/// Stored timestamps always carry nine fraction digits. Cosmos DB orders and
+/// compares them as strings, and with chrono's default format a whole second
+/// (`…:00Z`) would sort after fractions of that same second (`…:00.5Z`).
+///
+/// Documents written before this format keep sorting slightly off by
+/// `created_at` until they are rewritten.
+pub mod sortable_timestamp {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[must_use]
+    pub fn format(timestamp: &DateTime<Utc>) -> String {
+        timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true)
+    }
+
+    /// # Errors
+    ///
+    /// Returns the serializer's error.
+    pub fn serialize<S: Serializer>(
+        timestamp: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format(timestamp))
+    }
+
+    /// Accepts any RFC 3339 timestamp, including ones written before the
+    /// fixed-width format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error for anything else.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        DateTime::<Utc>::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::Currency;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    fn create(
+        amount_minor: i64,
+        memo: Option<&str>,
+        tags: &[&str],
+    ) -> Result<Transaction, TransactionDomainError> {
+        let tags: Vec<String> = tags.iter().map(ToString::to_string).collect();
+        Transaction::new(
+            UserId::generate(),
+            "Checking",
+            Money::from_minor(amount_minor, Currency::USD),
+            date(1),
+            None,
+            "Grocery Store",
+            memo,
+            "Food",
+            &tags,
+        )
+    }
+
+    #[test]
+    fn new_defaults_value_date_and_normalizes_fields() {
+        let transaction = create(-8950, Some("  "), &["Weekly", "weekly", "food"]).unwrap();
+
+        assert_eq!(transaction.value_on(), date(1));
+        assert!(transaction.memo().is_none());
+        assert_eq!(transaction.tags().len(), 2);
+        assert!(transaction.is_expense());
+        assert_eq!(transaction.created_at(), transaction.updated_at());
+    }
+
+    #[test]
+    fn new_rejects_invalid_fields() {
+        assert_eq!(
+            create(0, None, &[]).unwrap_err(),
+            TransactionDomainError::ZeroAmount
+        );
+        assert!(matches!(
+            create(100, None, &["not a tag"]),
+            Err(TransactionDomainError::InvalidTag { .. })
+        ));
+
+        let payee = "x".repeat(101);
+        let result = Transaction::new(
+            UserId::generate(),
+            "Checking",
+            Money::from_minor(100, Currency::USD),
+            date(1),
+            None,
+            &payee,
+            None,
+            "Food",
+            &[],
+        );
+        assert!(matches!(
+            result,
+            Err(TransactionDomainError::InvalidPayee { .. })
+        ));
+    }
+
+    #[test]
+    fn new_limits_distinct_tags() {
+        let tags: Vec<String> = (0..=Transaction::MAX_TAGS)
+            .map(|i| format!("tag{i}"))
+            .collect();
+        let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+
+        assert_eq!(
+            create(100, None, &tags).unwrap_err(),
+            TransactionDomainError::TooManyTags {
+                max: Transaction::MAX_TAGS
+            }
+        );
+        assert!(create(100, None, &tags[..Transaction::MAX_TAGS]).is_ok());
+    }
+
+    #[test]
+    fn update_keeps_identity_and_revalidates() {
+        let mut transaction = create(-500, None, &[]).unwrap();
+        let id = transaction.id().clone();
+        let created_at = transaction.created_at();
+
+        transaction
+            .update(
+                "Savings",
+                Money::from_minor(1200, Currency::USD),
+                date(2),
+                Some(date(3)),
+                "Employer",
+                Some("Bonus"),
+                "Income",
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(transaction.id(), &id);
+        assert_eq!(transaction.created_at(), created_at);
+        assert_eq!(transaction.value_on(), date(3));
+        assert!(!transaction.is_expense());
+
+        let result = transaction.update(
+            "Savings",
+            Money::zero(Currency::USD),
+            date(2),
+            None,
+            "Employer",
+            None,
+            "Income",
+            &[],
+        );
+        assert_eq!(result.unwrap_err(), TransactionDomainError::ZeroAmount);
+        assert_eq!(transaction.amount().amount_minor(), 1200);
+    }
+
+    #[test]
+    fn restore_validates_stored_ids() {
+        let result = Transaction::restore(
+            "",
+            "owner",
+            "Checking",
+            Money::from_minor(100, Currency::USD),
+            date(1),
+            date(1),
+            "Payee",
+            None,
+            "Food",
+            &[],
+            Utc::now(),
+            Utc::now(),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionDomainError::InvalidTransactionId
+        );
+    }
+
+    #[test]
+    fn timestamps_sort_as_strings() {
+        let whole = "2024-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let fraction = "2024-03-01T12:00:00.5Z".parse::<DateTime<Utc>>().unwrap();
+        let next = "2024-03-01T12:00:01Z".parse::<DateTime<Utc>>().unwrap();
+
+        let formatted: Vec<String> = [whole, fraction, next]
+            .iter()
+            .map(sortable_timestamp::format)
+            .collect();
+
+        let mut sorted = formatted.clone();
+        sorted.sort();
+        assert_eq!(sorted, formatted);
+        assert_eq!(formatted[0], "2024-03-01T12:00:00.000000000Z");
+    }
+
+    #[test]
+    fn stored_timestamps_round_trip() {
+        let transaction = create(-1250, None, &[]).unwrap();
+
+        let json = serde_json::to_value(&transaction).unwrap();
+        let restored: Transaction = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(json["created_at"].as_str().unwrap().len(), 30);
+        assert_eq!(restored.created_at(), transaction.created_at());
+        assert_eq!(restored.updated_at(), transaction.updated_at());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::domain::{
-    errors::user_errors::UserDomainError,
+    errors::{common::FieldError, user_errors::UserDomainError},
+    models::{mfa::TotpCredential, password_policy::PasswordPolicy},
     value_objects::{Email, PasswordHash, UserId, UserName},
 };
 use chrono::{DateTime, Utc};
@@ -13,10 +14,21 @@ pub struct User {
     password_hash: PasswordHash,
     first_name: UserName,
     last_name: UserName,
+    /// Documents stored before verification existed lack this field; those
+    /// accounts are treated as verified so they can still sign in.
+    #[serde(default = "verified_by_default")]
+    email_verified: bool,
+    /// Authenticator used as a second sign-in factor, if one is enrolled.
+    #[serde(default)]
+    totp: Option<TotpCredential>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
 
+const fn verified_by_default() -> bool {
+    true
+}
+
 impl User {
     /// Create a new user with validation
     ///
@@ -39,13 +51,45 @@ impl User {
             id: UserId::generate(),
             email: Email::new(email)?,
             password_hash: PasswordHash::new(password_hash)?,
-            first_name: UserName::new(first_name)?,
-            last_name: UserName::new(last_name)?,
+            first_name: UserName::new(first_name).map_err(|e| UserDomainError::first_name(&e))?,
+            last_name: UserName::new(last_name).map_err(|e| UserDomainError::last_name(&e))?,
+            email_verified: false,
+            totp: None,
             created_at: now,
             updated_at: now,
         })
     }
 
+    /// Rebuilds a user loaded from storage, re-validating every field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError` under the same conditions as [`User::new`],
+    /// or `InvalidUserId` if `id` is invalid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn restore(
+        id: &str,
+        email: &str,
+        password_hash: String,
+        first_name: &str,
+        last_name: &str,
+        email_verified: bool,
+        totp: Option<TotpCredential>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<Self, UserDomainError> {
+        let user = Self::new(email, password_hash, first_name, last_name)?;
+
+        Ok(Self {
+            id: UserId::new(id.to_string())?,
+            email_verified,
+            totp,
+            created_at,
+            updated_at,
+            ..user
+        })
+    }
+
     #[must_use]
     pub fn email(&self) -> &Email {
         &self.email
@@ -68,6 +112,116 @@ impl User {
     pub fn password_hash(&self) -> &PasswordHash {
         &self.password_hash
     }
+
+    /// Whether the user has proven they control their email address.
+    #[must_use]
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified
+    }
+
+    #[must_use]
+    pub fn totp(&self) -> Option<&TotpCredential> {
+        self.totp.as_ref()
+    }
+
+    /// Whether sign-in requires a second factor.
+    #[must_use]
+    pub fn is_mfa_enabled(&self) -> bool {
+        self.totp.as_ref().is_some_and(TotpCredential::is_enabled)
+    }
+
+    #[must_use]
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    #[must_use]
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Replaces the user's first and last name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidFirstName` or `InvalidLastName` if a name is invalid;
+    /// the user is left unchanged.
+    pub fn update_name(
+        &mut self,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<(), UserUpdateError> {
+        let first_name = UserName::new(first_name)?;
+        let last_name = UserName::new(last_name).map_err(UserUpdateError::InvalidLastName)?;
+
+        self.first_name = first_name;
+        self.last_name = last_name;
+        self.touch();
+        Ok(())
+    }
+
+    /// Replaces the user's email address. The new address starts out
+    /// unverified.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidEmail` if the address is invalid.
+    pub fn change_email(&mut self, email: &str) -> Result<(), UserUpdateError> {
+        self.email = Email::new(email)?;
+        self.email_verified = false;
+        self.touch();
+        Ok(())
+    }
+
+    /// Replaces the stored password hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPasswordHash` if the hash is invalid.
+    pub fn change_password_hash(&mut self, password_hash: String) -> Result<(), UserUpdateError> {
+        self.password_hash = PasswordHash::new(password_hash)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Records that the user has confirmed their current email address.
+    pub fn mark_email_verified(&mut self) {
+        self.email_verified = true;
+        self.touch();
+    }
+
+    /// Stores a new, unconfirmed authenticator secret, replacing any
+    /// enrollment that was started but never confirmed.
+    pub fn begin_totp_enrollment(&mut self, secret: String) {
+        self.totp = Some(TotpCredential::new(secret));
+        self.touch();
+    }
+
+    /// Confirms the pending authenticator with the code accepted at `step`.
+    pub fn confirm_totp(&mut self, step: i64) {
+        if let Some(totp) = &mut self.totp {
+            totp.enable(step);
+            self.touch();
+        }
+    }
+
+    /// Records that an authenticator code was accepted at `step`.
+    pub fn record_totp_step(&mut self, step: i64) {
+        if let Some(totp) = &mut self.totp {
+            totp.record_used_step(step);
+            self.touch();
+        }
+    }
+
+    /// Removes the authenticator, so sign-in needs only the password again.
+    pub fn remove_totp(&mut self) {
+        self.totp = None;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +232,160 @@ pub struct CreateUserRequest {
     pub last_name: String,
 }
 
+impl CreateUserRequest {
+    /// Validates every field of the request, collecting all failures rather
+    /// than stopping at the first one. The password is checked against
+    /// `password_policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::Validation` listing each invalid field.
+    pub fn validate(&self, password_policy: &PasswordPolicy) -> Result<(), UserDomainError> {
+        let mut errors: Vec<FieldError> = Vec::new();
+
+        if let Err(e) = Email::new(&self.email) {
+            errors.extend(UserDomainError::from(e).field_errors());
+        }
+        if self.password.is_empty() {
+            errors.push(FieldError::new(
+                "password",
+                "password_required",
+                "Password is required",
+            ));
+        } else if let Err(e) = password_policy.validate(
+            &self.password,
+            &self.email,
+            &[&self.first_name, &self.last_name],
+        ) {
+            errors.extend(e.field_errors());
+        }
+        if let Err(e) = UserName::new(&self.first_name) {
+            errors.extend(UserDomainError::first_name(&e).field_errors());
+        }
+        if let Err(e) = UserName::new(&self.last_name) {
+            errors.extend(UserDomainError::last_name(&e).field_errors());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(UserDomainError::Validation { errors })
+        }
+    }
+}
+
+/// Body of `PATCH /api/users/me`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+impl UpdateProfileRequest {
+    /// Validates both names, collecting every failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::Validation` listing each invalid field.
+    pub fn validate(&self) -> Result<(), UserDomainError> {
+        let mut errors: Vec<FieldError> = Vec::new();
+
+        if let Err(e) = UserName::new(&self.first_name) {
+            errors.extend(UserDomainError::first_name(&e).field_errors());
+        }
+        if let Err(e) = UserName::new(&self.last_name) {
+            errors.extend(UserDomainError::last_name(&e).field_errors());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(UserDomainError::Validation { errors })
+        }
+    }
+}
+
+/// Body of `PUT /api/users/me/password`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+impl ChangePasswordRequest {
+    /// Checks the new password against `password_policy` for `user`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::Validation` with a `new_password` field error.
+    pub fn validate(
+        &self,
+        password_policy: &PasswordPolicy,
+        user: &User,
+    ) -> Result<(), UserDomainError> {
+        validate_new_password(password_policy, &self.new_password, user)
+    }
+}
+
+/// Checks a replacement password for `user`, reporting failures against the
+/// `new_password` field.
+fn validate_new_password(
+    password_policy: &PasswordPolicy,
+    new_password: &str,
+    user: &User,
+) -> Result<(), UserDomainError> {
+    password_policy
+        .validate(
+            new_password,
+            user.email().as_str(),
+            &[user.first_name().as_str(), user.last_name().as_str()],
+        )
+        .map_err(|e| UserDomainError::Validation {
+            errors: vec![FieldError::new("new_password", e.code(), e.to_string())],
+        })
+}
+
+/// Body of `POST /api/auth/forgot-password`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+/// Body of `POST /api/auth/reset-password`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+impl ResetPasswordRequest {
+    /// Checks the new password against `password_policy` for `user`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserDomainError::Validation` with a `new_password` field error.
+    pub fn validate(
+        &self,
+        password_policy: &PasswordPolicy,
+        user: &User,
+    ) -> Result<(), UserDomainError> {
+        validate_new_password(password_policy, &self.new_password, user)
+    }
+}
+
+/// Body of `PUT /api/users/me/email`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEmailRequest {
+    pub new_email: String,
+    pub current_password: String,
+}
+
+/// Body of `DELETE /api/users/me`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAccountRequest {
+    pub password: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
@@ -93,11 +401,53 @@ pub struct AuthResponse {
     pub user: UserProfile,
 }
 
+/// Body returned by `POST /api/auth/login`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Authenticated(AuthResponse),
+    /// The password was accepted but the account has two-factor
+    /// authentication. `mfa_token` must be sent to `POST /api/auth/login/mfa`
+    /// together with a code within `expires_in` seconds; it completes one
+    /// sign-in at most.
+    MfaRequired {
+        mfa_token: String,
+        expires_in: u64,
+    },
+}
+
+/// Body returned by `POST /api/auth/register`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RegisterResponse {
+    /// Email verification is disabled, so the user is signed in right away.
+    Authenticated(AuthResponse),
+    /// A verification email was sent; the user can sign in once they follow it.
+    VerificationPending { message: String, user: UserProfile },
+}
+
+/// Body of `POST /api/auth/verify-email`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+/// Body of `POST /api/auth/resend-verification`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResendVerificationRequest {
+    pub email: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RefreshTokenResponse {
     pub access_token: String,
@@ -112,6 +462,10 @@ pub struct UserProfile {
     pub email: String,
     pub first_name: String,
     pub last_name: String,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub mfa_enabled: bool,
 }
 
 impl From<User> for UserProfile {
@@ -121,6 +475,8 @@ impl From<User> for UserProfile {
             email: user.email.to_string(),
             first_name: user.first_name.to_string(),
             last_name: user.last_name.to_string(),
+            email_verified: user.email_verified,
+            mfa_enabled: user.is_mfa_enabled(),
         }
     }
 }
@@ -0,0 +1,23 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::login_throttle::LoginAttempts;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    async fn get_login_attempts(&self, key: &str) -> Result<Option<LoginAttempts>>;
+    /// Counts a failed attempt for `key` at `now` and returns the updated
+    /// record. The count starts over if the previous failure happened before
+    /// `window_start`.
+    async fn record_failed_login(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> Result<LoginAttempts>;
+    async fn lock_login(&self, key: &str, until: DateTime<Utc>) -> Result<()>;
+    /// Forgets every failure recorded for `key`.
+    async fn clear_login_attempts(&self, key: &str) -> Result<()>;
+}
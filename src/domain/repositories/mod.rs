@@ -1 +1,7 @@
+pub mod login_attempt_repository;
+pub mod one_time_token_repository;
+pub mod personal_access_token_repository;
+pub mod refresh_token_repository;
+pub mod session_repository;
+pub mod transaction_repository;
 pub mod user_repository;
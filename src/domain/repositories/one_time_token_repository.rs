@@ -0,0 +1,24 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::one_time_token::{OneTimeToken, TokenPurpose};
+use crate::domain::value_objects::UserId;
+use async_trait::async_trait;
+use color_eyre::Result;
+
+#[async_trait]
+pub trait OneTimeTokenRepository: Send + Sync {
+    async fn store_one_time_token(&self, token: OneTimeToken) -> Result<()>;
+    async fn get_one_time_token(&self, token_id: &str) -> Result<Option<OneTimeToken>>;
+    /// Marks the token as used and returns it, provided it exists for
+    /// `purpose`, is unused and has not expired. Returns `None` otherwise, so
+    /// of several concurrent calls for the same token at most one succeeds.
+    async fn consume_one_time_token(
+        &self,
+        token_id: &str,
+        purpose: TokenPurpose,
+    ) -> Result<Option<OneTimeToken>>;
+    /// Marks every unused token the user holds for `purpose` as used.
+    async fn revoke_one_time_tokens(&self, user_id: &UserId, purpose: TokenPurpose) -> Result<()>;
+    /// Deletes every token the user holds, used or not, for any purpose.
+    async fn delete_all_one_time_tokens(&self, user_id: &UserId) -> Result<()>;
+}
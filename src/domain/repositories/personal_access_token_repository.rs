@@ -0,0 +1,19 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::personal_access_token::PersonalAccessToken;
+use crate::domain::value_objects::UserId;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+
+#[async_trait]
+pub trait PersonalAccessTokenRepository: Send + Sync {
+    async fn create_access_token(&self, token: PersonalAccessToken) -> Result<()>;
+    async fn get_access_token(&self, token_id: &str) -> Result<Option<PersonalAccessToken>>;
+    /// The user's tokens, newest first.
+    async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<PersonalAccessToken>>;
+    /// Deletes the token if it belongs to the user. Returns whether it did.
+    async fn delete_access_token(&self, user_id: &UserId, token_id: &str) -> Result<bool>;
+    async fn delete_all_access_tokens(&self, user_id: &UserId) -> Result<()>;
+    async fn record_access_token_use(&self, token_id: &str, used_at: DateTime<Utc>) -> Result<()>;
+}
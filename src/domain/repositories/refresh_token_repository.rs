@@ -0,0 +1,30 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::refresh_token::RefreshTokenRecord;
+use crate::domain::value_objects::UserId;
+use async_trait::async_trait;
+use color_eyre::Result;
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn store_refresh_token(&self, record: RefreshTokenRecord) -> Result<()>;
+    async fn get_refresh_token(
+        &self,
+        user_id: &UserId,
+        token_id: &str,
+    ) -> Result<Option<RefreshTokenRecord>>;
+    /// Marks the token as exchanged for `replaced_by`. Returns `false` when
+    /// the token was already used or does not exist.
+    async fn mark_refresh_token_used(
+        &self,
+        user_id: &UserId,
+        token_id: &str,
+        replaced_by: &str,
+    ) -> Result<bool>;
+    async fn revoke_refresh_token_family(&self, user_id: &UserId, family_id: &str) -> Result<()>;
+    /// Revokes every refresh token of the user, signing out all sessions.
+    async fn revoke_all_refresh_tokens(&self, user_id: &UserId) -> Result<()>;
+    /// Removes every refresh token record of the user, e.g. when the
+    /// account itself is deleted.
+    async fn delete_all_refresh_tokens(&self, user_id: &UserId) -> Result<()>;
+}
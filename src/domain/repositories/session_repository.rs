@@ -0,0 +1,27 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::session::Session;
+use crate::domain::value_objects::UserId;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create_session(&self, session: Session) -> Result<()>;
+    async fn get_session(&self, user_id: &UserId, session_id: &str) -> Result<Option<Session>>;
+    /// The user's sessions, most recently seen first.
+    async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>>;
+    /// Records a token refresh on the session. Returns `false` when the
+    /// session does not exist.
+    async fn record_session_activity(
+        &self,
+        user_id: &UserId,
+        session_id: &str,
+        seen_at: DateTime<Utc>,
+        ip_address: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool>;
+    async fn delete_session(&self, user_id: &UserId, session_id: &str) -> Result<()>;
+    async fn delete_all_sessions(&self, user_id: &UserId) -> Result<()>;
+}
@@ -0,0 +1,448 @@
+// Repository traits (interfaces)
+
+use crate::domain::models::transaction::Transaction;
+use crate::domain::value_objects::{AccountName, Category, TransactionId, UserId};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Filters and paging for listing a single owner's transactions.
+///
+/// The owner is never part of the query itself; it is always passed
+/// separately so implementations can scope every read to that owner.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionQuery {
+    pub booked_from: Option<NaiveDate>,
+    pub booked_to: Option<NaiveDate>,
+    pub account: Option<AccountName>,
+    pub category: Option<Category>,
+    pub min_amount_minor: Option<i64>,
+    pub max_amount_minor: Option<i64>,
+    /// Case-insensitive substring matched against payee and memo.
+    pub search: Option<String>,
+    pub sort: TransactionSort,
+    pub limit: Option<u32>,
+    pub continuation: Option<String>,
+}
+
+impl TransactionQuery {
+    pub const DEFAULT_LIMIT: u32 = 50;
+    pub const MAX_LIMIT: u32 = 200;
+
+    /// Number of items to return, clamped to `1..=MAX_LIMIT`.
+    #[must_use]
+    pub fn page_size(&self) -> u32 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT)
+    }
+
+    /// Decodes the continuation token into the position the next page
+    /// starts after.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidContinuationToken` if the token was not produced by
+    /// [`TransactionCursor::encode`] for a listing with the same sort.
+    pub fn cursor(&self) -> Result<Option<TransactionCursor>, InvalidContinuationToken> {
+        match self.continuation.as_deref() {
+            None | Some("") => Ok(None),
+            Some(token) => {
+                let cursor = TransactionCursor::decode(token)?;
+                if cursor.sort() == self.sort {
+                    Ok(Some(cursor))
+                } else {
+                    Err(InvalidContinuationToken)
+                }
+            }
+        }
+    }
+
+    /// Whether `transaction` passes every filter of the query. Stores that
+    /// cannot push filters down to the database use this to stay consistent
+    /// with the ones that can.
+    #[must_use]
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        let booked_on = transaction.booked_on();
+        let amount = transaction.amount().amount_minor();
+
+        self.booked_from.is_none_or(|from| booked_on >= from)
+            && self.booked_to.is_none_or(|to| booked_on <= to)
+            && self
+                .account
+                .as_ref()
+                .is_none_or(|account| transaction.account() == account)
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|category| transaction.category() == category)
+            && self.min_amount_minor.is_none_or(|min| amount >= min)
+            && self.max_amount_minor.is_none_or(|max| amount <= max)
+            && self.search.as_deref().is_none_or(|search| {
+                let search = search.to_lowercase();
+                transaction
+                    .payee()
+                    .as_str()
+                    .to_lowercase()
+                    .contains(&search)
+                    || transaction
+                        .memo()
+                        .is_some_and(|memo| memo.as_str().to_lowercase().contains(&search))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionSortField {
+    #[default]
+    BookedOn,
+    Amount,
+    Payee,
+    CreatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+/// Sort order for transaction listings, newest booking first by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionSort {
+    pub field: TransactionSortField,
+    pub direction: SortDirection,
+}
+
+impl TransactionSort {
+    /// Orders two transactions according to this sort. Ties are broken by
+    /// id in the same direction, so every transaction has a fixed position
+    /// that a [`TransactionCursor`] can point at.
+    #[must_use]
+    pub fn compare(&self, a: &Transaction, b: &Transaction) -> Ordering {
+        self.compare_keys(
+            &SortKey::of(a, self.field),
+            a.id().as_str(),
+            &SortKey::of(b, self.field),
+            b.id().as_str(),
+        )
+    }
+
+    fn compare_keys(self, a: &SortKey, a_id: &str, b: &SortKey, b_id: &str) -> Ordering {
+        let ordering = a.cmp(b).then_with(|| a_id.cmp(b_id));
+        match self.direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// The value a transaction is sorted by.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SortKey {
+    BookedOn(NaiveDate),
+    Amount(i64),
+    Payee(String),
+    CreatedAt(DateTime<Utc>),
+}
+
+impl SortKey {
+    #[must_use]
+    pub fn of(transaction: &Transaction, field: TransactionSortField) -> Self {
+        match field {
+            TransactionSortField::BookedOn => Self::BookedOn(transaction.booked_on()),
+            TransactionSortField::Amount => Self::Amount(transaction.amount().amount_minor()),
+            TransactionSortField::Payee => Self::Payee(transaction.payee().as_str().to_string()),
+            TransactionSortField::CreatedAt => Self::CreatedAt(transaction.created_at()),
+        }
+    }
+
+    #[must_use]
+    pub fn field(&self) -> TransactionSortField {
+        match self {
+            Self::BookedOn(_) => TransactionSortField::BookedOn,
+            Self::Amount(_) => TransactionSortField::Amount,
+            Self::Payee(_) => TransactionSortField::Payee,
+            Self::CreatedAt(_) => TransactionSortField::CreatedAt,
+        }
+    }
+}
+
+/// Keyset position of the last transaction of a page: its sort key and id.
+///
+/// The next page holds the transactions that sort after this position, so
+/// rows inserted or deleted between requests never shift the pages, and
+/// stores can seek with an index instead of skipping an offset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionCursor {
+    key: SortKey,
+    id: String,
+    descending: bool,
+}
+
+impl TransactionCursor {
+    #[must_use]
+    pub fn after(transaction: &Transaction, sort: TransactionSort) -> Self {
+        Self {
+            key: SortKey::of(transaction, sort.field),
+            id: transaction.id().as_str().to_string(),
+            descending: sort.direction == SortDirection::Descending,
+        }
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &SortKey {
+        &self.key
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The sort of the listing this cursor was taken from.
+    #[must_use]
+    pub fn sort(&self) -> TransactionSort {
+        TransactionSort {
+            field: self.key.field(),
+            direction: if self.descending {
+                SortDirection::Descending
+            } else {
+                SortDirection::Ascending
+            },
+        }
+    }
+
+    /// Whether `transaction` sorts after this position.
+    #[must_use]
+    pub fn precedes(&self, transaction: &Transaction) -> bool {
+        let sort = self.sort();
+        sort.compare_keys(
+            &SortKey::of(transaction, sort.field),
+            transaction.id().as_str(),
+            &self.key,
+            &self.id,
+        ) == Ordering::Greater
+    }
+
+    /// Encodes the cursor as an opaque continuation token.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        serde_json::to_vec(self).unwrap_or_default().iter().fold(
+            String::new(),
+            |mut token, byte| {
+                let _ = write!(token, "{byte:02x}");
+                token
+            },
+        )
+    }
+
+    /// Decodes a token produced by [`TransactionCursor::encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidContinuationToken` if the token is malformed or has
+    /// been altered.
+    pub fn decode(token: &str) -> Result<Self, InvalidContinuationToken> {
+        if !token.len().is_multiple_of(2) || !token.is_ascii() {
+            return Err(InvalidContinuationToken);
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&token[index..index + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| InvalidContinuationToken)?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| InvalidContinuationToken)?;
+
+        if TransactionId::new(cursor.id.clone()).is_err() {
+            return Err(InvalidContinuationToken);
+        }
+        Ok(cursor)
+    }
+}
+
+impl FromStr for TransactionSort {
+    type Err = InvalidSort;
+
+    /// Parses `field` (ascending) or `-field` (descending), e.g. `-amount`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (direction, field) = match value.strip_prefix('-') {
+            Some(field) => (SortDirection::Descending, field),
+            None => (
+                SortDirection::Ascending,
+                value.strip_prefix('+').unwrap_or(value),
+            ),
+        };
+
+        let field = match field {
+            "booked_on" | "date" => TransactionSortField::BookedOn,
+            "amount" => TransactionSortField::Amount,
+            "payee" => TransactionSortField::Payee,
+            "created_at" => TransactionSortField::CreatedAt,
+            _ => {
+                return Err(InvalidSort {
+                    value: value.to_string(),
+                });
+            }
+        };
+
+        Ok(Self { field, direction })
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "Cannot sort by '{value}'. Use booked_on, amount, payee or created_at, optionally prefixed with '-'"
+)]
+pub struct InvalidSort {
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("Invalid continuation token")]
+pub struct InvalidContinuationToken;
+
+#[derive(Debug, Clone)]
+pub struct TransactionPage {
+    pub items: Vec<Transaction>,
+    pub continuation: Option<String>,
+}
+
+impl TransactionPage {
+    /// Builds a page from up to `page_size + 1` items in `sort` order. The
+    /// extra item only signals that another page exists and is dropped.
+    #[must_use]
+    pub fn new(mut items: Vec<Transaction>, page_size: usize, sort: TransactionSort) -> Self {
+        let continuation = if items.len() > page_size {
+            items.truncate(page_size);
+            items
+                .last()
+                .map(|last| TransactionCursor::after(last, sort).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            continuation,
+        }
+    }
+}
+
+#[async_trait]
+pub trait TransactionRepository: Send + Sync {
+    async fn create_transaction(&self, transaction: Transaction) -> Result<()>;
+    /// Creates all of `transactions` or none of them, e.g. both legs of a
+    /// transfer or every row of an import.
+    async fn create_transactions(&self, transactions: Vec<Transaction>) -> Result<()>;
+    async fn get_transaction(
+        &self,
+        owner_id: &UserId,
+        id: &TransactionId,
+    ) -> Result<Option<Transaction>>;
+    async fn update_transaction(&self, transaction: Transaction) -> Result<()>;
+    async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<bool>;
+    /// Deletes every transaction of the owner, returning how many were removed.
+    async fn delete_transactions_for_owner(&self, owner_id: &UserId) -> Result<u64>;
+    async fn list_transactions(
+        &self,
+        owner_id: &UserId,
+        query: &TransactionQuery,
+    ) -> Result<TransactionPage>;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Currency, Money};
+
+    fn transaction(owner_id: &UserId, day: u32, amount_minor: i64) -> Transaction {
+        Transaction::new(
+            owner_id.clone(),
+            "Checking",
+            Money::from_minor(amount_minor, Currency::USD),
+            NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            None,
+            "Grocery Store",
+            None,
+            "Food",
+            &[],
+        )
+        .unwrap()
+    }
+
+    fn query(sort: TransactionSort, limit: u32, continuation: Option<String>) -> TransactionQuery {
+        TransactionQuery {
+            sort,
+            limit: Some(limit),
+            continuation,
+            ..TransactionQuery::default()
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips_through_its_token() {
+        let owner_id = UserId::generate();
+        let sort = TransactionSort {
+            field: TransactionSortField::Amount,
+            direction: SortDirection::Ascending,
+        };
+        let cursor = TransactionCursor::after(&transaction(&owner_id, 3, -1250), sort);
+
+        let decoded = query(sort, 10, Some(cursor.encode()))
+            .cursor()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(decoded, cursor);
+        assert_eq!(decoded.key(), &SortKey::Amount(-1250));
+        assert_eq!(decoded.sort(), sort);
+    }
+
+    #[test]
+    fn cursor_rejects_tampered_tokens() {
+        let owner_id = UserId::generate();
+        let sort = TransactionSort::default();
+        let token = TransactionCursor::after(&transaction(&owner_id, 3, -1250), sort).encode();
+        let mut flipped = token.clone();
+        flipped.replace_range(0..2, "00");
+
+        for tampered in ["50", "zz", "abc", "7b7d", "é0", flipped.as_str()] {
+            assert!(
+                query(sort, 10, Some(tampered.to_string()))
+                    .cursor()
+                    .is_err(),
+                "accepted {tampered:?}"
+            );
+        }
+        assert!(
+            query(sort, 10, Some(String::new()))
+                .cursor()
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cursor_is_rejected_for_a_different_sort() {
+        let owner_id = UserId::generate();
+        let token = TransactionCursor::after(
+            &transaction(&owner_id, 3, -1250),
+            TransactionSort::default(),
+        )
+        .encode();
+        let ascending = TransactionSort {
+            field: TransactionSortField::BookedOn,
+            direction: SortDirection::Ascending,
+        };
+
+        assert!(query(ascending, 10, Some(token)).cursor().is_err());
+    }
+}
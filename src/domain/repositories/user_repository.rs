@@ -1,11 +1,31 @@
 // Repository traits (interfaces)
 
 use crate::domain::models::user::User;
+use crate::domain::value_objects::{Email, UserId};
 use async_trait::async_trait;
 use color_eyre::Result;
 
 #[async_trait]
-pub trait UserRepository {
+pub trait UserRepository: Send + Sync {
+    /// Stores a new user. Fails with [`DuplicateEmail`] if the email is
+    /// already taken; implementations enforce this atomically in the store.
     async fn create_user(&self, user: User) -> Result<()>;
-    async fn get_user_by_email(&self, email: &str) -> Result<Option<User>>;
+    async fn get_user_by_email(&self, email: &Email) -> Result<Option<User>>;
+    async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>>;
+    /// Saves changes to an existing user, including a new email. Fails with
+    /// [`DuplicateEmail`] if the new email belongs to another user.
+    async fn update_user(&self, user: User) -> Result<()>;
+    /// Deletes the user. Returns `false` when no such user exists.
+    async fn delete_user(&self, id: &UserId) -> Result<bool>;
+    /// Records `step` as the last accepted TOTP step of the stored user,
+    /// unless it already holds that step or a later one. The check and the
+    /// write are atomic, so of two requests with the same code only one
+    /// succeeds. Returns `false` when the step was already used.
+    async fn record_totp_step(&self, user: &User, step: i64) -> Result<bool>;
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("User with email '{email}' already exists")]
+pub struct DuplicateEmail {
+    pub email: String,
 }
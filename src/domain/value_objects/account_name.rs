@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AccountName(String);
+
+impl AccountName {
+    /// Creates a new `AccountName` from the provided string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AccountNameError` if the name:
+    /// - Is empty after trimming whitespace
+    /// - Is longer than 50 characters
+    /// - Contains control characters
+    pub fn new(name: &str) -> Result<Self, AccountNameError> {
+        let name = name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(AccountNameError::Empty);
+        }
+
+        if name.chars().count() > 50 {
+            return Err(AccountNameError::TooLong);
+        }
+
+        if name.chars().any(char::is_control) {
+            return Err(AccountNameError::InvalidCharacters);
+        }
+
+        Ok(Self(name))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountNameError {
+    #[error("Account name cannot be empty")]
+    Empty,
+    #[error("Account name is too long (max 50 characters)")]
+    TooLong,
+    #[error("Account name contains invalid characters")]
+    InvalidCharacters,
+}
+
+impl fmt::Display for AccountName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Category(String);
+
+impl Category {
+    /// Creates a new `Category` from the provided string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CategoryError` if the category:
+    /// - Is empty after trimming whitespace
+    /// - Is longer than 50 characters
+    /// - Contains punctuation other than `&`, `-`, `/`, `'` and spaces
+    pub fn new(category: &str) -> Result<Self, CategoryError> {
+        let category = category.trim().to_string();
+
+        if category.is_empty() {
+            return Err(CategoryError::Empty);
+        }
+
+        if category.chars().count() > 50 {
+            return Err(CategoryError::TooLong);
+        }
+
+        if category.chars().any(|c| {
+            c.is_control() || (c.is_ascii_punctuation() && !matches!(c, '&' | '-' | '/' | '\''))
+        }) {
+            return Err(CategoryError::InvalidCharacters);
+        }
+
+        Ok(Self(category))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CategoryError {
+    #[error("Category cannot be empty")]
+    Empty,
+    #[error("Category is too long (max 50 characters)")]
+    TooLong,
+    #[error("Category contains invalid characters")]
+    InvalidCharacters,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
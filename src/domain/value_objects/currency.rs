@@ -0,0 +1,139 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// An ISO 4217 currency together with the number of minor-unit digits
+/// (the exponent) it is accounted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: &'static str,
+    exponent: u8,
+    symbol: &'static str,
+}
+
+impl Currency {
+    pub const USD: Currency = Currency::define("USD", 2, "$");
+    pub const EUR: Currency = Currency::define("EUR", 2, "€");
+    pub const GBP: Currency = Currency::define("GBP", 2, "£");
+    pub const JPY: Currency = Currency::define("JPY", 0, "¥");
+    pub const SEK: Currency = Currency::define("SEK", 2, "kr");
+
+    const SUPPORTED: &'static [Currency] = &[
+        Currency::USD,
+        Currency::EUR,
+        Currency::GBP,
+        Currency::JPY,
+        Currency::SEK,
+        Currency::define("AUD", 2, "A$"),
+        Currency::define("BHD", 3, "BD"),
+        Currency::define("BRL", 2, "R$"),
+        Currency::define("CAD", 2, "CA$"),
+        Currency::define("CHF", 2, "CHF"),
+        Currency::define("CLP", 0, "CLP$"),
+        Currency::define("CNY", 2, "CN¥"),
+        Currency::define("CZK", 2, "Kč"),
+        Currency::define("DKK", 2, "kr."),
+        Currency::define("HKD", 2, "HK$"),
+        Currency::define("HUF", 2, "Ft"),
+        Currency::define("ILS", 2, "₪"),
+        Currency::define("INR", 2, "₹"),
+        Currency::define("ISK", 0, "kr"),
+        Currency::define("JOD", 3, "JD"),
+        Currency::define("KRW", 0, "₩"),
+        Currency::define("KWD", 3, "KD"),
+        Currency::define("MXN", 2, "MX$"),
+        Currency::define("NOK", 2, "kr"),
+        Currency::define("NZD", 2, "NZ$"),
+        Currency::define("OMR", 3, "OMR"),
+        Currency::define("PLN", 2, "zł"),
+        Currency::define("SGD", 2, "S$"),
+        Currency::define("TND", 3, "DT"),
+        Currency::define("TRY", 2, "₺"),
+        Currency::define("ZAR", 2, "R"),
+    ];
+
+    const fn define(code: &'static str, exponent: u8, symbol: &'static str) -> Self {
+        Self {
+            code,
+            exponent,
+            symbol,
+        }
+    }
+
+    /// Looks up a supported currency by its ISO 4217 alphabetic code.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CurrencyError` if:
+    /// - The code is empty after trimming
+    /// - The code is not three ASCII letters
+    /// - The code is not a currency this application supports
+    pub fn from_code(code: &str) -> Result<Self, CurrencyError> {
+        let code = code.trim().to_uppercase();
+
+        if code.is_empty() {
+            return Err(CurrencyError::Empty);
+        }
+
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(CurrencyError::InvalidFormat);
+        }
+
+        Self::SUPPORTED
+            .iter()
+            .find(|currency| currency.code == code)
+            .copied()
+            .ok_or(CurrencyError::Unsupported { code })
+    }
+
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Number of digits after the decimal separator, e.g. 2 for USD and 0 for JPY.
+    #[must_use]
+    pub fn exponent(&self) -> u8 {
+        self.exponent
+    }
+
+    #[must_use]
+    pub fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    /// Number of minor units in one major unit (`10^exponent`).
+    #[must_use]
+    pub fn minor_units_per_major(&self) -> i64 {
+        10_i64.pow(u32::from(self.exponent))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CurrencyError {
+    #[error("Currency code cannot be empty")]
+    Empty,
+    #[error("Currency code must be a three-letter ISO 4217 code")]
+    InvalidFormat,
+    #[error("Currency '{code}' is not supported")]
+    Unsupported { code: String },
+}
+
+// Serialized as the bare ISO code so stored documents stay readable.
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Self::from_code(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Memo(String);
+
+impl Memo {
+    /// Creates a new `Memo` from the provided string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MemoError` if the memo:
+    /// - Is empty after trimming whitespace
+    /// - Is longer than 500 characters
+    /// - Contains control characters other than line breaks and tabs
+    pub fn new(memo: &str) -> Result<Self, MemoError> {
+        let memo = memo.trim().to_string();
+
+        if memo.is_empty() {
+            return Err(MemoError::Empty);
+        }
+
+        if memo.chars().count() > 500 {
+            return Err(MemoError::TooLong);
+        }
+
+        if memo
+            .chars()
+            .any(|c| c.is_control() && c != '\n' && c != '\r' && c != '\t')
+        {
+            return Err(MemoError::InvalidCharacters);
+        }
+
+        Ok(Self(memo))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MemoError {
+    #[error("Memo cannot be empty")]
+    Empty,
+    #[error("Memo is too long (max 500 characters)")]
+    TooLong,
+    #[error("Memo contains invalid characters")]
+    InvalidCharacters,
+}
+
+impl fmt::Display for Memo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,9 +1,25 @@
+pub mod account_name;
+pub mod category;
+pub mod currency;
 pub mod email;
+pub mod memo;
+pub mod money;
 pub mod password_hash;
+pub mod payee;
+pub mod tag;
+pub mod transaction_id;
 pub mod user_id;
 pub mod user_name;
 
+pub use account_name::AccountName;
+pub use category::Category;
+pub use currency::Currency;
 pub use email::Email;
+pub use memo::Memo;
+pub use money::{Locale, Money};
 pub use password_hash::PasswordHash;
+pub use payee::Payee;
+pub use tag::Tag;
+pub use transaction_id::TransactionId;
 pub use user_id::UserId;
 pub use user_name::UserName;
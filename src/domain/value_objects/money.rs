@@ -0,0 +1,569 @@
+use crate::domain::value_objects::currency::Currency;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Number formatting conventions used when rendering and parsing amounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    EnUs,
+    EnGb,
+    DeDe,
+    FrFr,
+    FiFi,
+    SvSe,
+}
+
+impl Locale {
+    /// Resolves a BCP 47 language tag such as `"en-US"` or `"fi_FI"`.
+    #[must_use]
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.trim().replace('_', "-").to_lowercase().as_str() {
+            "en-us" | "en" => Some(Self::EnUs),
+            "en-gb" => Some(Self::EnGb),
+            "de-de" | "de" => Some(Self::DeDe),
+            "fr-fr" | "fr" => Some(Self::FrFr),
+            "fi-fi" | "fi" => Some(Self::FiFi),
+            "sv-se" | "sv" => Some(Self::SvSe),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn group_separator(self) -> char {
+        match self {
+            Self::EnUs | Self::EnGb => ',',
+            Self::DeDe => '.',
+            Self::FrFr => '\u{202f}',
+            Self::FiFi | Self::SvSe => '\u{a0}',
+        }
+    }
+
+    #[must_use]
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Self::EnUs | Self::EnGb => '.',
+            Self::DeDe | Self::FrFr | Self::FiFi | Self::SvSe => ',',
+        }
+    }
+
+    /// Whether the currency symbol is written before the number (`$1.00`)
+    /// rather than after it (`1,00 €`).
+    #[must_use]
+    pub fn symbol_first(self) -> bool {
+        matches!(self, Self::EnUs | Self::EnGb)
+    }
+}
+
+/// An exact monetary amount stored as a signed number of minor units
+/// (cents for USD, whole yen for JPY) in a specific currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Money {
+    amount_minor: i64,
+    currency: Currency,
+}
+
+impl Money {
+    #[must_use]
+    pub fn from_minor(amount_minor: i64, currency: Currency) -> Self {
+        Self {
+            amount_minor,
+            currency,
+        }
+    }
+
+    #[must_use]
+    pub fn zero(currency: Currency) -> Self {
+        Self::from_minor(0, currency)
+    }
+
+    /// Creates an amount from whole major units, e.g. `from_major(12, USD)` is `$12.00`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::Overflow` if the amount does not fit in minor units.
+    pub fn from_major(amount_major: i64, currency: Currency) -> Result<Self, MoneyError> {
+        amount_major
+            .checked_mul(currency.minor_units_per_major())
+            .map(|amount_minor| Self::from_minor(amount_minor, currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    #[must_use]
+    pub fn amount_minor(&self) -> i64 {
+        self.amount_minor
+    }
+
+    #[must_use]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.amount_minor == 0
+    }
+
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.amount_minor < 0
+    }
+
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        self.amount_minor > 0
+    }
+
+    /// Adds two amounts of the same currency.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if the currencies differ and
+    /// `MoneyError::Overflow` if the result does not fit in minor units.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        self.amount_minor
+            .checked_add(other.amount_minor)
+            .map(|amount_minor| Self::from_minor(amount_minor, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Subtracts `other` from this amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if the currencies differ and
+    /// `MoneyError::Overflow` if the result does not fit in minor units.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        self.amount_minor
+            .checked_sub(other.amount_minor)
+            .map(|amount_minor| Self::from_minor(amount_minor, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Returns the amount with its sign flipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::Overflow` for the single unrepresentable value `i64::MIN`.
+    pub fn checked_neg(&self) -> Result<Money, MoneyError> {
+        self.amount_minor
+            .checked_neg()
+            .map(|amount_minor| Self::from_minor(amount_minor, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Sums amounts that must all be in `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if any amount is in a different
+    /// currency and `MoneyError::Overflow` if the total does not fit.
+    pub fn sum<'a, I>(amounts: I, currency: Currency) -> Result<Money, MoneyError>
+    where
+        I: IntoIterator<Item = &'a Money>,
+    {
+        amounts
+            .into_iter()
+            .try_fold(Self::zero(currency), |total, amount| {
+                total.checked_add(amount)
+            })
+    }
+
+    /// Expresses this amount as a share of `whole` in basis points
+    /// (1/100 of a percent), rounded half away from zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoneyError::CurrencyMismatch` if the currencies differ,
+    /// `MoneyError::DivisionByZero` if `whole` is zero and
+    /// `MoneyError::Overflow` if the ratio does not fit.
+    pub fn basis_points_of(&self, whole: &Money) -> Result<i64, MoneyError> {
+        self.ensure_same_currency(whole)?;
+        if whole.is_zero() {
+            return Err(MoneyError::DivisionByZero);
+        }
+
+        let numerator = i128::from(self.amount_minor) * 10_000;
+        let denominator = i128::from(whole.amount_minor);
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        let rounded = if remainder.abs() * 2 >= denominator.abs() {
+            quotient + numerator.signum() * denominator.signum()
+        } else {
+            quotient
+        };
+
+        i64::try_from(rounded).map_err(|_| MoneyError::Overflow)
+    }
+
+    /// Formats the amount with the currency symbol, e.g. `-$1,234.50` or `-1.234,50 €`.
+    #[must_use]
+    pub fn format(&self, locale: Locale) -> String {
+        self.format_inner(locale, false)
+    }
+
+    /// Like [`Money::format`] but always prints the sign, e.g. `+$2,500.00`.
+    #[must_use]
+    pub fn format_signed(&self, locale: Locale) -> String {
+        self.format_inner(locale, true)
+    }
+
+    fn format_inner(&self, locale: Locale, always_sign: bool) -> String {
+        let sign = if self.is_negative() {
+            "-"
+        } else if always_sign && self.is_positive() {
+            "+"
+        } else {
+            ""
+        };
+        let number = self.format_number(locale);
+        let symbol = self.currency.symbol();
+
+        if locale.symbol_first() {
+            format!("{sign}{symbol}{number}")
+        } else {
+            format!("{sign}{number}\u{a0}{symbol}")
+        }
+    }
+
+    fn format_number(&self, locale: Locale) -> String {
+        let exponent = u32::from(self.currency.exponent());
+        let divisor = 10_u64.pow(exponent);
+        let absolute = self.amount_minor.unsigned_abs();
+        let major = (absolute / divisor).to_string();
+        let minor = absolute % divisor;
+
+        let mut grouped = String::with_capacity(major.len() + major.len() / 3);
+        for (index, digit) in major.chars().enumerate() {
+            if index > 0 && (major.len() - index).is_multiple_of(3) {
+                grouped.push(locale.group_separator());
+            }
+            grouped.push(digit);
+        }
+
+        if exponent == 0 {
+            grouped
+        } else {
+            format!(
+                "{grouped}{}{minor:0width$}",
+                locale.decimal_separator(),
+                width = exponent as usize
+            )
+        }
+    }
+
+    /// Parses a localized amount such as `"$1,234.50"`, `"-1.234,50 €"`,
+    /// `"(12.00)"` or `"1 234,5 EUR"` into an exact amount of `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MoneyError` if:
+    /// - The input contains anything other than digits, separators, a sign
+    ///   and one leading or trailing currency symbol or code, is signed
+    ///   inside parentheses, or
+    ///   has group separators anywhere but between groups of three digits
+    ///   (`InvalidFormat`)
+    /// - It has more fraction digits than the currency allows (`TooManyDecimals`)
+    /// - The amount does not fit in minor units (`Overflow`)
+    pub fn parse(input: &str, currency: Currency, locale: Locale) -> Result<Money, MoneyError> {
+        let invalid = || MoneyError::InvalidFormat {
+            input: input.to_string(),
+        };
+        let mut text = input.trim().to_string();
+        let parenthesized = text.starts_with('(') && text.ends_with(')');
+
+        if parenthesized {
+            text = text[1..text.len() - 1].to_string();
+        }
+
+        let mut text = text.trim();
+        let mut sign = None;
+        if let Some(rest) = text.strip_prefix(['-', '+']) {
+            sign = Some(text.starts_with('-'));
+            text = rest.trim_start();
+        }
+
+        // At most one symbol or code, either before or after the number
+        let markers = [currency.code(), currency.symbol()];
+        if let Some(rest) = markers.iter().find_map(|marker| text.strip_prefix(marker)) {
+            text = rest.trim_start();
+        } else if let Some(rest) = markers.iter().find_map(|marker| text.strip_suffix(marker)) {
+            text = rest.trim_end();
+        }
+
+        if sign.is_none()
+            && let Some(rest) = text.strip_prefix(['-', '+'])
+        {
+            sign = Some(text.starts_with('-'));
+            text = rest.trim_start();
+        }
+        // "(-5)" would negate twice
+        if parenthesized && sign.is_some() {
+            return Err(invalid());
+        }
+        let negative = parenthesized || sign == Some(true);
+
+        let group_separator = locale.group_separator();
+        let mut parts = text.splitn(2, locale.decimal_separator());
+        let major =
+            Self::ungroup(parts.next().unwrap_or_default(), group_separator).ok_or_else(invalid)?;
+        let minor = parts.next().unwrap_or_default();
+
+        if (major.is_empty() && minor.is_empty()) || !minor.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let exponent = usize::from(currency.exponent());
+        if minor.len() > exponent {
+            return Err(MoneyError::TooManyDecimals {
+                max: currency.exponent(),
+            });
+        }
+
+        let major_units: i64 = if major.is_empty() {
+            0
+        } else {
+            major.parse().map_err(|_| MoneyError::Overflow)?
+        };
+        let minor_units: i64 = if minor.is_empty() {
+            0
+        } else {
+            format!("{minor:0<exponent$}")
+                .parse()
+                .map_err(|_| MoneyError::Overflow)?
+        };
+
+        let amount_minor = major_units
+            .checked_mul(currency.minor_units_per_major())
+            .and_then(|amount| amount.checked_add(minor_units))
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self::from_minor(
+            if negative {
+                -amount_minor
+            } else {
+                amount_minor
+            },
+            currency,
+        ))
+    }
+
+    /// Strips group separators from the integer part of an amount, accepting
+    /// them only between groups of three digits (`1,234,567`). Locales that
+    /// group with a space also accept an ordinary space.
+    fn ungroup(major: &str, group_separator: char) -> Option<String> {
+        let is_separator = |c: char| {
+            c == group_separator || (group_separator.is_whitespace() && c.is_whitespace())
+        };
+        let groups: Vec<&str> = major.split(is_separator).collect();
+        let well_formed = groups
+            .iter()
+            .all(|group| group.chars().all(|c| c.is_ascii_digit()))
+            && (groups.len() == 1
+                || ((1..=3).contains(&groups[0].len())
+                    && groups[1..].iter().all(|group| group.len() == 3)));
+
+        well_formed.then(|| groups.concat())
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                left: self.currency.code().to_string(),
+                right: other.currency.code().to_string(),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum MoneyError {
+    #[error("Cannot combine amounts in {left} and {right}")]
+    CurrencyMismatch { left: String, right: String },
+    #[error("Amount is out of range")]
+    Overflow,
+    #[error("Cannot divide by a zero amount")]
+    DivisionByZero,
+    #[error("'{input}' is not a valid amount")]
+    InvalidFormat { input: String },
+    #[error("Amount has too many decimal places (max {max})")]
+    TooManyDecimals { max: u8 },
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        write!(
+            f,
+            "{sign}{} {}",
+            self.format_number(Locale::EnUs),
+            self.currency
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn usd(amount_minor: i64) -> Money {
+        Money::from_minor(amount_minor, Currency::USD)
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let total = Money::sum(&[usd(10), usd(20), usd(-5)], Currency::USD).unwrap();
+        assert_eq!(total, usd(25));
+        assert_eq!(usd(30).checked_sub(&usd(10)).unwrap(), usd(20));
+        assert_eq!(usd(-8950).checked_neg().unwrap(), usd(8950));
+        assert_eq!(Money::from_major(12, Currency::USD).unwrap(), usd(1200));
+        assert_eq!(
+            Money::from_major(12, Currency::JPY).unwrap().amount_minor(),
+            12
+        );
+    }
+
+    #[test]
+    fn arithmetic_rejects_mixed_currencies() {
+        let euros = Money::from_minor(100, Currency::EUR);
+        assert_eq!(
+            usd(100).checked_add(&euros),
+            Err(MoneyError::CurrencyMismatch {
+                left: "USD".to_string(),
+                right: "EUR".to_string(),
+            })
+        );
+        assert!(Money::sum(&[usd(1), euros], Currency::USD).is_err());
+    }
+
+    #[test]
+    fn arithmetic_reports_overflow() {
+        assert_eq!(
+            usd(i64::MAX).checked_add(&usd(1)),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(
+            usd(i64::MIN).checked_sub(&usd(1)),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(usd(i64::MIN).checked_neg(), Err(MoneyError::Overflow));
+        assert_eq!(
+            Money::from_major(i64::MAX, Currency::USD),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn basis_points_round_half_away_from_zero() {
+        assert_eq!(usd(1).basis_points_of(&usd(3)).unwrap(), 3333);
+        assert_eq!(usd(2).basis_points_of(&usd(3)).unwrap(), 6667);
+        assert_eq!(usd(1).basis_points_of(&usd(20_000)).unwrap(), 1);
+        assert_eq!(usd(-1).basis_points_of(&usd(20_000)).unwrap(), -1);
+        assert_eq!(
+            usd(1).basis_points_of(&usd(0)),
+            Err(MoneyError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn formats_by_locale() {
+        let amount = usd(-123_450);
+        assert_eq!(amount.format(Locale::EnUs), "-$1,234.50");
+        assert_eq!(
+            Money::from_minor(123_450, Currency::EUR).format(Locale::DeDe),
+            "1.234,50\u{a0}€"
+        );
+        assert_eq!(usd(250_000).format_signed(Locale::EnUs), "+$2,500.00");
+        assert_eq!(usd(0).format_signed(Locale::EnUs), "$0.00");
+        assert_eq!(usd(5).format(Locale::EnUs), "$0.05");
+        assert_eq!(
+            Money::from_minor(1_234_567, Currency::JPY).format(Locale::EnUs),
+            "¥1,234,567"
+        );
+    }
+
+    #[test]
+    fn display_keeps_the_sign() {
+        assert_eq!(usd(-8950).to_string(), "-89.50 USD");
+        assert_eq!(usd(8950).to_string(), "89.50 USD");
+    }
+
+    #[test]
+    fn parses_localized_amounts() {
+        let parse = |input, currency, locale| Money::parse(input, currency, locale).unwrap();
+
+        assert_eq!(
+            parse("$1,234.50", Currency::USD, Locale::EnUs),
+            usd(123_450)
+        );
+        assert_eq!(parse("-$0.5", Currency::USD, Locale::EnUs), usd(-50));
+        assert_eq!(parse("(12.00)", Currency::USD, Locale::EnUs), usd(-1200));
+        assert_eq!(parse("+7 USD", Currency::USD, Locale::EnUs), usd(700));
+        assert_eq!(parse("$-5", Currency::USD, Locale::EnUs), usd(-500));
+        assert_eq!(
+            parse("-1.234,50 €", Currency::EUR, Locale::DeDe),
+            Money::from_minor(-123_450, Currency::EUR)
+        );
+        assert_eq!(
+            parse("1 234,5 EUR", Currency::EUR, Locale::FrFr),
+            Money::from_minor(123_450, Currency::EUR)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_an_embedded_symbol() {
+        for input in ["1€2,50", "12EUR,50", "1.2€34,50"] {
+            assert!(
+                matches!(
+                    Money::parse(input, Currency::EUR, Locale::DeDe),
+                    Err(MoneyError::InvalidFormat { .. })
+                ),
+                "{input:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_round_trips_formatting() {
+        for locale in [Locale::EnUs, Locale::DeDe, Locale::FrFr, Locale::FiFi] {
+            let amount = Money::from_minor(-987_654_321, Currency::EUR);
+            assert_eq!(
+                Money::parse(&amount.format(locale), Currency::EUR, locale).unwrap(),
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed_amounts() {
+        for input in [
+            "", "-", "abc", "1,2,3.00", "12,34.00", ",123", "1,", "(-5)", "(+5)", "--5", "1.2.3",
+            "$", "1$2.50", "12USD.50", "$5$", "$5 USD", "-$-5",
+        ] {
+            assert!(
+                matches!(
+                    Money::parse(input, Currency::USD, Locale::EnUs),
+                    Err(MoneyError::InvalidFormat { .. })
+                ),
+                "{input:?} should not parse"
+            );
+        }
+        assert_eq!(
+            Money::parse("1.234", Currency::USD, Locale::EnUs),
+            Err(MoneyError::TooManyDecimals { max: 2 })
+        );
+        assert_eq!(
+            Money::parse("1.5", Currency::JPY, Locale::EnUs),
+            Err(MoneyError::TooManyDecimals { max: 0 })
+        );
+        assert_eq!(
+            Money::parse("99999999999999999999", Currency::USD, Locale::EnUs),
+            Err(MoneyError::Overflow)
+        );
+    }
+}
@@ -13,7 +13,8 @@ impl PasswordHash {
     /// - The hash is empty or contains only whitespace
     /// - The hash is shorter than 8 characters
     /// - The hash is longer than 255 characters
-    /// - The hash doesn't have a valid bcrypt format (doesn't start with "$2")
+    /// - The hash is neither an Argon2 PHC string (starting with "$argon2")
+    ///   nor a bcrypt hash (starting with "$2")
     pub fn new(hash: String) -> Result<Self, PasswordHashError> {
         if hash.trim().is_empty() {
             return Err(PasswordHashError::Empty);
@@ -27,8 +28,8 @@ impl PasswordHash {
             return Err(PasswordHashError::TooLong);
         }
 
-        // Basic check for bcrypt format
-        if !hash.starts_with("$2") {
+        // Basic check for Argon2 or bcrypt format
+        if !hash.starts_with("$argon2") && !hash.starts_with("$2") {
             return Err(PasswordHashError::InvalidFormat);
         }
 
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payee(String);
+
+impl Payee {
+    /// Creates a new `Payee` from the provided string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PayeeError` if the payee:
+    /// - Is empty after trimming whitespace
+    /// - Is longer than 100 characters
+    /// - Contains control characters
+    pub fn new(payee: &str) -> Result<Self, PayeeError> {
+        let payee = payee.trim().to_string();
+
+        if payee.is_empty() {
+            return Err(PayeeError::Empty);
+        }
+
+        if payee.chars().count() > 100 {
+            return Err(PayeeError::TooLong);
+        }
+
+        if payee.chars().any(char::is_control) {
+            return Err(PayeeError::InvalidCharacters);
+        }
+
+        Ok(Self(payee))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PayeeError {
+    #[error("Payee cannot be empty")]
+    Empty,
+    #[error("Payee is too long (max 100 characters)")]
+    TooLong,
+    #[error("Payee contains invalid characters")]
+    InvalidCharacters,
+}
+
+impl fmt::Display for Payee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
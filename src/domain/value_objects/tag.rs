@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Tag(String);
+
+impl Tag {
+    /// Creates a new `Tag` from the provided string. Tags are stored lowercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TagError` if the tag:
+    /// - Is empty after trimming whitespace
+    /// - Is longer than 30 characters
+    /// - Contains anything other than letters, digits, `-` and `_`
+    pub fn new(tag: &str) -> Result<Self, TagError> {
+        let tag = tag.trim().to_lowercase();
+
+        if tag.is_empty() {
+            return Err(TagError::Empty);
+        }
+
+        if tag.chars().count() > 30 {
+            return Err(TagError::TooLong);
+        }
+
+        if !tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(TagError::InvalidCharacters);
+        }
+
+        Ok(Self(tag))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TagError {
+    #[error("Tag cannot be empty")]
+    Empty,
+    #[error("Tag is too long (max 30 characters)")]
+    TooLong,
+    #[error("Tag may only contain letters, digits, '-' and '_'")]
+    InvalidCharacters,
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TransactionId(String);
+
+impl TransactionId {
+    /// Creates a new `TransactionId` from the provided string.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransactionIdError::Empty` if the provided string is empty or contains only whitespace.
+    /// Returns `TransactionIdError::TooLong` if the provided string is longer than 36 characters.
+    pub fn new(id: String) -> Result<Self, TransactionIdError> {
+        if id.trim().is_empty() {
+            return Err(TransactionIdError::Empty);
+        }
+
+        if id.len() > 36 {
+            return Err(TransactionIdError::TooLong);
+        }
+
+        Ok(Self(id))
+    }
+
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionIdError {
+    #[error("Transaction ID cannot be empty")]
+    Empty,
+    #[error("Transaction ID is too long (max 36 characters)")]
+    TooLong,
+}
+
+impl fmt::Display for TransactionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
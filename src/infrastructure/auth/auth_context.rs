@@ -36,10 +36,10 @@ impl AuthState {
     }
 
     pub fn store_refresh_token(&self, refresh_token: &str) {
-        if let Some(window) = leptos::web_sys::window() {
-            if let Ok(Some(storage)) = window.local_storage() {
-                let _ = storage.set_item("refresh_token", refresh_token);
-            }
+        if let Some(window) = leptos::web_sys::window()
+            && let Ok(Some(storage)) = window.local_storage()
+        {
+            let _ = storage.set_item("refresh_token", refresh_token);
         }
     }
 
@@ -53,10 +53,10 @@ impl AuthState {
     }
 
     pub fn clear_refresh_token(&self) {
-        if let Some(window) = leptos::web_sys::window() {
-            if let Ok(Some(storage)) = window.local_storage() {
-                let _ = storage.remove_item("refresh_token");
-            }
+        if let Some(window) = leptos::web_sys::window()
+            && let Ok(Some(storage)) = window.local_storage()
+        {
+            let _ = storage.remove_item("refresh_token");
         }
     }
 
@@ -111,16 +111,14 @@ impl AuthState {
     }
 
     pub async fn logout(&self) {
-        // Optional: Call logout endpoint to invalidate refresh token on server
-        if let Some(refresh_token) = self.get_refresh_token() {
-            if let Ok(response) = Request::post("/api/auth/logout")
+        // Revoke the refresh token on the server; local state is cleared
+        // regardless so a network failure never leaves the user logged in.
+        if let Some(refresh_token) = self.get_refresh_token()
+            && let Ok(request) = Request::post("/api/auth/logout")
                 .json(&serde_json::json!({ "refresh_token": refresh_token }))
-            {
-                response.json::<serde_json::Value>().await.ok(); // Handle response if needed
-            // Successfully logged out
-            } else {
-                // Handle error if needed
-            }
+            && let Err(e) = request.send().await
+        {
+            leptos::logging::warn!("Logout request failed: {e}");
         }
 
         self.clear();
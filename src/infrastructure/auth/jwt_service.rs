@@ -1,10 +1,44 @@
 #[cfg(feature = "ssr")]
-use crate::{application::user_service::UserService, domain::models::user::User};
+use crate::infrastructure::auth::signing_keys::{JwtKeyRing, SigningKeyError};
 #[cfg(feature = "ssr")]
-use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use crate::infrastructure::config::app_config::{
+    AuthConfig, PasswordHashAlgorithm, PasswordHashingConfig, TokenConfig,
+};
+#[cfg(feature = "ssr")]
+use crate::infrastructure::web::middleware::client_info::ClientInfo;
+#[cfg(feature = "ssr")]
+use crate::{
+    application::user_service::UserService,
+    domain::{
+        models::one_time_token::{OneTimeToken, TokenPurpose},
+        models::personal_access_token::{PersonalAccessToken, TokenScope},
+        models::refresh_token::RefreshTokenRecord,
+        models::session::Session,
+        models::user::User,
+        value_objects::UserId,
+    },
+};
+#[cfg(feature = "ssr")]
+use argon2::password_hash::{
+    self, PasswordHash as PhcHash, PasswordHasher, PasswordVerifier, SaltString,
+};
+#[cfg(feature = "ssr")]
+use argon2::{Argon2, Params};
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use jsonwebtoken::jwk::JwkSet;
+#[cfg(feature = "ssr")]
+use jsonwebtoken::{Validation, decode, decode_header, encode};
+#[cfg(feature = "ssr")]
+use rand::RngCore;
 #[cfg(feature = "ssr")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "ssr")]
+use std::sync::Arc;
+#[cfg(feature = "ssr")]
 use thiserror::Error;
 
 #[cfg(feature = "ssr")]
@@ -12,15 +46,32 @@ use thiserror::Error;
 pub struct Claims {
     pub sub: String,
     pub email: String,
+    /// Issuer; tokens from another environment are rejected.
+    pub iss: String,
+    /// Audience; tokens meant for another audience are rejected.
+    pub aud: String,
+    pub iat: usize,
+    pub nbf: usize,
     pub exp: usize,
+    /// Unique token id; refresh tokens are tracked server-side by this value.
+    pub jti: String,
+    /// Login session (refresh-token family) the token belongs to. Set on
+    /// refresh tokens and on the access tokens issued with them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_id: Option<String>,
     pub token_type: TokenType,
 }
 
 #[cfg(feature = "ssr")]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TokenType {
     Access,
     Refresh,
+    /// Sent by email to prove the recipient controls the address.
+    EmailVerification,
+    /// Proves the password step of a two-factor sign-in succeeded; exchanged
+    /// for a token pair together with a second-factor code.
+    MfaChallenge,
 }
 
 #[cfg(feature = "ssr")]
@@ -29,6 +80,36 @@ pub struct TokenPair {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_in: u64, // Access token expiration in seconds
+    pub refresh_token_id: String,
+    pub family_id: String,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl TokenPair {
+    /// Builds the server-side record that must be stored before the refresh
+    /// token is handed to the client.
+    #[must_use]
+    pub fn refresh_token_record(&self, user_id: &UserId) -> RefreshTokenRecord {
+        RefreshTokenRecord::new(
+            self.refresh_token_id.clone(),
+            self.family_id.clone(),
+            user_id.clone(),
+            self.refresh_expires_at,
+        )
+    }
+
+    /// Builds the session this token pair starts for `client`.
+    #[must_use]
+    pub fn session(&self, user_id: &UserId, client: &ClientInfo) -> Session {
+        Session::new(
+            self.family_id.clone(),
+            user_id.clone(),
+            client.user_agent.clone(),
+            client.ip.map(|ip| ip.to_string()),
+            self.refresh_expires_at,
+        )
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -40,142 +121,496 @@ pub enum AuthError {
     #[error("Bcrypt error: {0}")]
     BcryptError(#[from] bcrypt::BcryptError),
 
+    #[error("Argon2 error: {0}")]
+    Argon2Error(#[from] password_hash::Error),
+
     #[error("Invalid token type: expected {expected}, got {actual}")]
     InvalidTokenType { expected: String, actual: String },
 
+    #[error("Token was not signed by a known key")]
+    UnknownSigningKey,
+
     #[error("Token has expired")]
     TokenExpired,
 
     #[error("Refresh token not found or invalid")]
     InvalidRefreshToken,
+
+    #[error("Token store error: {0}")]
+    TokenStoreError(String),
+
+    #[error("Secure random number generator failed: {0}")]
+    RandomError(#[from] rand::Error),
 }
 
 #[cfg(feature = "ssr")]
 #[derive(Clone, Debug)]
-pub struct AuthService {}
+pub struct AuthService {
+    keys: Arc<JwtKeyRing>,
+    tokens: Arc<TokenConfig>,
+}
 
 #[cfg(feature = "ssr")]
 impl AuthService {
-    const ACCESS_TOKEN_DURATION_MINUTES: u64 = 15; // 15 minutes
-    const REFRESH_TOKEN_DURATION_DAYS: u64 = 30; // 30 days
+    pub const EMAIL_VERIFICATION_TOKEN_DURATION_HOURS: i64 = 24; // 24 hours
+    pub const PASSWORD_RESET_TOKEN_DURATION_MINUTES: i64 = 30; // 30 minutes
+    const MFA_CHALLENGE_TOKEN_DURATION_MINUTES: u64 = 5; // 5 minutes
+    /// Marks personal access tokens, so they are told apart from JWTs and
+    /// are easy to find with secret scanners.
+    pub const PERSONAL_ACCESS_TOKEN_PREFIX: &'static str = "ftpat_";
+
+    #[must_use]
+    pub fn new(keys: JwtKeyRing, tokens: TokenConfig) -> Self {
+        Self {
+            keys: Arc::new(keys),
+            tokens: Arc::new(tokens),
+        }
+    }
 
-    /// Generates a token pair (access + refresh tokens) for the given user.
+    /// Creates the service with the signing and verification keys and the
+    /// token settings from the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SigningKeyError` if no key is configured or a key file
+    /// cannot be loaded.
+    pub fn from_config(config: &AuthConfig) -> Result<Self, SigningKeyError> {
+        JwtKeyRing::from_config(config).map(|keys| Self::new(keys, config.tokens.clone()))
+    }
+
+    /// The public keys other services can verify our tokens with.
+    #[must_use]
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
+    }
+
+    /// Signs `claims` with the current signing key.
+    fn sign(&self, claims: &Claims) -> Result<String, AuthError> {
+        encode(&self.keys.header(), claims, self.keys.encoding_key()).map_err(AuthError::from)
+    }
+
+    /// Generates a token pair (access + refresh tokens) for the given user,
+    /// starting a new refresh-token family.
     ///
     /// # Errors
     ///
     /// Returns an `AuthError` if token generation fails.
-    pub fn generate_token_pair(user: &User, secret: &str) -> Result<TokenPair, AuthError> {
-        let access_token = Self::generate_access_token(user, secret)?;
-        let refresh_token = Self::generate_refresh_token(user, secret)?;
+    pub fn generate_token_pair(&self, user: &User) -> Result<TokenPair, AuthError> {
+        self.generate_token_pair_in_family(user, &uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Generates a token pair whose refresh token belongs to an existing family.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if token generation fails.
+    pub fn generate_token_pair_in_family(
+        &self,
+        user: &User,
+        family_id: &str,
+    ) -> Result<TokenPair, AuthError> {
+        let access_token = self.generate_access_token(user, Some(family_id))?;
+        let refresh_token_id = uuid::Uuid::new_v4().to_string();
+        let (refresh_token, refresh_expires_at) =
+            self.generate_refresh_token(user, &refresh_token_id, family_id)?;
 
         Ok(TokenPair {
             access_token,
             refresh_token,
-            expires_in: (Self::ACCESS_TOKEN_DURATION_MINUTES * 60),
+            expires_in: u64::from(self.tokens.access_token_minutes) * 60,
+            refresh_token_id,
+            family_id: family_id.to_string(),
+            refresh_expires_at,
         })
     }
 
-    /// Generates a short-lived access token.
-    fn generate_access_token(user: &User, secret: &str) -> Result<String, AuthError> {
-        let expiration = chrono::Utc::now()
-            .checked_add_signed(chrono::Duration::minutes(
-                Self::ACCESS_TOKEN_DURATION_MINUTES.try_into().unwrap_or(15),
-            ))
-            .ok_or_else(|| {
-                AuthError::JwtError(jsonwebtoken::errors::Error::from(
-                    jsonwebtoken::errors::ErrorKind::InvalidToken,
-                ))
-            })?
-            .timestamp()
-            .try_into()
-            .map_err(|_| {
-                AuthError::JwtError(jsonwebtoken::errors::Error::from(
-                    jsonwebtoken::errors::ErrorKind::InvalidToken,
-                ))
-            })?;
+    /// Computes an expiry `duration` from now as both a timestamp and the
+    /// `exp` claim value.
+    fn expiration(duration: chrono::Duration) -> Result<(DateTime<Utc>, usize), AuthError> {
+        let expires_at = Utc::now()
+            .checked_add_signed(duration)
+            .ok_or_else(Self::invalid_timestamp)?;
 
-        let claims = Claims {
-            sub: user.id().to_string(),
-            email: user.email().to_string(),
-            exp: expiration,
-            token_type: TokenType::Access,
-        };
+        Ok((expires_at, Self::timestamp(expires_at)?))
+    }
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_ref()),
-        )
-        .map_err(AuthError::from)
-    }
-
-    /// Generates a long-lived refresh token.
-    fn generate_refresh_token(user: &User, secret: &str) -> Result<String, AuthError> {
-        let expiration = chrono::Utc::now()
-            .checked_add_signed(chrono::Duration::days(
-                Self::REFRESH_TOKEN_DURATION_DAYS.try_into().unwrap_or(30),
-            ))
-            .ok_or_else(|| {
-                AuthError::JwtError(jsonwebtoken::errors::Error::from(
-                    jsonwebtoken::errors::ErrorKind::InvalidToken,
-                ))
-            })?
-            .timestamp()
+    /// Converts `at` to a claim value in seconds since the epoch.
+    fn timestamp(at: DateTime<Utc>) -> Result<usize, AuthError> {
+        at.timestamp()
             .try_into()
-            .map_err(|_| {
-                AuthError::JwtError(jsonwebtoken::errors::Error::from(
-                    jsonwebtoken::errors::ErrorKind::InvalidToken,
-                ))
-            })?;
+            .map_err(|_| Self::invalid_timestamp())
+    }
+
+    fn invalid_timestamp() -> AuthError {
+        AuthError::JwtError(jsonwebtoken::errors::Error::from(
+            jsonwebtoken::errors::ErrorKind::InvalidToken,
+        ))
+    }
+
+    /// Builds the claims of a `token_type` token for `user` that is valid
+    /// for `lifetime` from now, and returns them with the expiry.
+    fn claims(
+        &self,
+        user: &User,
+        token_type: TokenType,
+        jti: String,
+        family_id: Option<String>,
+        lifetime: chrono::Duration,
+    ) -> Result<(Claims, DateTime<Utc>), AuthError> {
+        let issued_at = Self::timestamp(Utc::now())?;
+        let (expires_at, exp) = Self::expiration(lifetime)?;
 
         let claims = Claims {
             sub: user.id().to_string(),
             email: user.email().to_string(),
-            exp: expiration,
-            token_type: TokenType::Refresh,
+            iss: self.tokens.issuer.clone(),
+            aud: self.tokens.audience.clone(),
+            iat: issued_at,
+            nbf: issued_at,
+            exp,
+            jti,
+            family_id,
+            token_type,
         };
+        Ok((claims, expires_at))
+    }
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_ref()),
-        )
-        .map_err(AuthError::from)
+    /// Generates a short-lived access token, tied to the session
+    /// `family_id` when there is one.
+    fn generate_access_token(
+        &self,
+        user: &User,
+        family_id: Option<&str>,
+    ) -> Result<String, AuthError> {
+        let (claims, _) = self.claims(
+            user,
+            TokenType::Access,
+            uuid::Uuid::new_v4().to_string(),
+            family_id.map(str::to_string),
+            chrono::Duration::minutes(i64::from(self.tokens.access_token_minutes)),
+        )?;
+
+        self.sign(&claims)
     }
 
-    /// Refreshes an access token using a valid refresh token.
+    /// Generates a long-lived refresh token and returns it with its expiry.
+    fn generate_refresh_token(
+        &self,
+        user: &User,
+        token_id: &str,
+        family_id: &str,
+    ) -> Result<(String, DateTime<Utc>), AuthError> {
+        let (claims, expires_at) = self.claims(
+            user,
+            TokenType::Refresh,
+            token_id.to_string(),
+            Some(family_id.to_string()),
+            chrono::Duration::days(i64::from(self.tokens.refresh_token_days)),
+        )?;
+
+        let token = self.sign(&claims)?;
+
+        Ok((token, expires_at))
+    }
+
+    /// Generates a signed email verification token for the user's current
+    /// address, together with the one-time record that must be stored before
+    /// the token is sent. The token can be redeemed once, and only while the
+    /// address is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if token generation fails.
+    pub fn generate_email_verification_token(
+        &self,
+        user: &User,
+    ) -> Result<(String, OneTimeToken), AuthError> {
+        let token_id = uuid::Uuid::new_v4().to_string();
+        let (claims, expires_at) = self.claims(
+            user,
+            TokenType::EmailVerification,
+            token_id.clone(),
+            None,
+            chrono::Duration::hours(Self::EMAIL_VERIFICATION_TOKEN_DURATION_HOURS),
+        )?;
+
+        let token = self.sign(&claims)?;
+
+        let record = OneTimeToken::new(
+            token_id,
+            user.id().clone(),
+            TokenPurpose::EmailVerification,
+            expires_at,
+        );
+        Ok((token, record))
+    }
+
+    /// Generates the short-lived token handed out after the password step of
+    /// a two-factor sign-in, together with the one-time record that must be
+    /// stored before the token is returned and the token's lifetime in
+    /// seconds. The challenge can be completed once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if token generation fails.
+    pub fn generate_mfa_challenge_token(
+        &self,
+        user: &User,
+    ) -> Result<(String, OneTimeToken, u64), AuthError> {
+        let token_id = uuid::Uuid::new_v4().to_string();
+        let (claims, expires_at) = self.claims(
+            user,
+            TokenType::MfaChallenge,
+            token_id.clone(),
+            None,
+            chrono::Duration::minutes(
+                Self::MFA_CHALLENGE_TOKEN_DURATION_MINUTES
+                    .try_into()
+                    .unwrap_or(5),
+            ),
+        )?;
+
+        let token = self.sign(&claims)?;
+
+        let record = OneTimeToken::new(
+            token_id,
+            user.id().clone(),
+            TokenPurpose::MfaChallenge,
+            expires_at,
+        );
+        Ok((token, record, Self::MFA_CHALLENGE_TOKEN_DURATION_MINUTES * 60))
+    }
+
+    /// Generates a random password reset token together with the one-time
+    /// record that must be stored before the token is sent. The record is
+    /// keyed by the token's digest, so the store alone cannot be used to
+    /// reset a password.
     ///
     /// # Errors
     ///
-    /// Returns an `AuthError` if the refresh token is invalid or if token generation fails.
+    /// Returns an `AuthError` if the system random number generator fails.
+    pub fn generate_password_reset_token(user: &User) -> Result<(String, OneTimeToken), AuthError> {
+        let mut bytes = [0u8; 32];
+        rand::rngs::OsRng.try_fill_bytes(&mut bytes)?;
+        let token = hex::encode(bytes);
+
+        let (expires_at, _) = Self::expiration(chrono::Duration::minutes(
+            Self::PASSWORD_RESET_TOKEN_DURATION_MINUTES,
+        ))?;
+        let record = OneTimeToken::new(
+            Self::hash_one_time_token(&token),
+            user.id().clone(),
+            TokenPurpose::PasswordReset,
+            expires_at,
+        );
+        Ok((token, record))
+    }
+
+    /// Generates a personal access token together with the record that
+    /// must be stored before the token is returned. The token reads
+    /// `ftpat_<id>_<secret>`; only its digest is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the system random number generator fails.
+    pub fn generate_personal_access_token(
+        user_id: &UserId,
+        name: String,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(String, PersonalAccessToken), AuthError> {
+        let mut bytes = [0u8; 32];
+        rand::rngs::OsRng.try_fill_bytes(&mut bytes)?;
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let token = format!(
+            "{}{id}_{}",
+            Self::PERSONAL_ACCESS_TOKEN_PREFIX,
+            hex::encode(bytes)
+        );
+
+        let record = PersonalAccessToken::new(
+            id,
+            user_id.clone(),
+            name,
+            Self::hash_one_time_token(&token),
+            scopes,
+            expires_at,
+        );
+        Ok((token, record))
+    }
+
+    /// The record id a personal access token carries, or `None` if `token`
+    /// is not shaped like one.
+    #[must_use]
+    pub fn personal_access_token_id(token: &str) -> Option<&str> {
+        let (id, secret) = token
+            .strip_prefix(Self::PERSONAL_ACCESS_TOKEN_PREFIX)?
+            .split_once('_')?;
+        (!id.is_empty() && !secret.is_empty()).then_some(id)
+    }
+
+    /// Key under which an opaque one-time token is stored: the hex-encoded
+    /// SHA-256 digest of the token. Personal access tokens are stored under
+    /// the same digest.
+    #[must_use]
+    pub fn hash_one_time_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Verifies a refresh token's signature and type and loads its
+    /// server-side record.
+    async fn load_refresh_token_record<T>(
+        &self,
+        refresh_token: &str,
+        user_service: &T,
+    ) -> Result<(Claims, RefreshTokenRecord), AuthError>
+    where
+        T: for<'a> UserService<'a>,
+    {
+        let claims = self.verify_token_of_type(refresh_token, TokenType::Refresh)?;
+
+        let user_id =
+            UserId::new(claims.sub.clone()).map_err(|_| AuthError::InvalidRefreshToken)?;
+
+        let record = user_service
+            .get_refresh_token(&user_id, &claims.jti)
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))?
+            .ok_or(AuthError::InvalidRefreshToken)?;
+
+        Ok((claims, record))
+    }
+
+    /// Exchanges a refresh token for a new token pair.
+    ///
+    /// Refresh tokens are single-use: the presented token is marked as used
+    /// and replaced by a new one in the same family. Presenting a token that
+    /// was already used indicates it has been stolen, so the whole family is
+    /// revoked. The family's session is marked as seen from `client`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the refresh token is invalid, reused or
+    /// revoked, or if token generation or storage fails.
     pub async fn refresh_access_token<T>(
+        &self,
         refresh_token: &str,
-        secret: &str,
+        client: &ClientInfo,
         user_service: &T,
     ) -> Result<TokenPair, AuthError>
     where
         T: for<'a> UserService<'a>,
     {
-        // Verify the refresh token
-        let claims = Self::verify_token(refresh_token, secret)?;
+        let (claims, record) = self
+            .load_refresh_token_record(refresh_token, user_service)
+            .await?;
 
-        // Ensure it's actually a refresh token
-        if !matches!(claims.token_type, TokenType::Refresh) {
-            return Err(AuthError::InvalidTokenType {
-                expected: "Refresh".to_string(),
-                actual: format!("{:?}", claims.token_type),
-            });
+        if record.is_used() {
+            Self::revoke_family(user_service, &record).await?;
+            return Err(AuthError::InvalidRefreshToken);
         }
 
-        // Get the user to ensure they still exist and are active
+        if !record.is_active() {
+            return Err(AuthError::InvalidRefreshToken);
+        }
+
+        // Load the user by id, which unlike the email claim never changes,
+        // to ensure they still exist
+        let user_id = UserId::new(claims.sub).map_err(|_| AuthError::InvalidRefreshToken)?;
+        if record.user_id() != &user_id {
+            return Err(AuthError::InvalidRefreshToken);
+        }
         let user = user_service
-            .get_user_by_email(&claims.email)
+            .get_user_by_id(&user_id)
             .await
             .map_err(|_| AuthError::InvalidRefreshToken)?
             .ok_or(AuthError::InvalidRefreshToken)?;
 
-        // Generate new token pair
-        Self::generate_token_pair(&user, secret)
+        let token_pair = self.generate_token_pair_in_family(&user, record.family_id())?;
+
+        // Losing this race means another request exchanged the same token
+        // first, which is treated as reuse.
+        let marked = user_service
+            .mark_refresh_token_used(record.user_id(), record.id(), &token_pair.refresh_token_id)
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))?;
+        if !marked {
+            Self::revoke_family(user_service, &record).await?;
+            return Err(AuthError::InvalidRefreshToken);
+        }
+
+        user_service
+            .store_refresh_token(token_pair.refresh_token_record(user.id()))
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))?;
+
+        Self::record_session_activity(user_service, &token_pair, user.id(), client).await?;
+
+        Ok(token_pair)
+    }
+
+    /// Marks the session of a refreshed token pair as seen. Families
+    /// started before sessions were tracked get their session created here.
+    async fn record_session_activity<T>(
+        user_service: &T,
+        token_pair: &TokenPair,
+        user_id: &UserId,
+        client: &ClientInfo,
+    ) -> Result<(), AuthError>
+    where
+        T: for<'a> UserService<'a>,
+    {
+        let found = user_service
+            .record_session_activity(
+                user_id,
+                &token_pair.family_id,
+                Utc::now(),
+                client.ip.map(|ip| ip.to_string()),
+                token_pair.refresh_expires_at,
+            )
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))?;
+        if found {
+            return Ok(());
+        }
+
+        user_service
+            .create_session(token_pair.session(user_id, client))
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))
+    }
+
+    async fn revoke_family<T>(
+        user_service: &T,
+        record: &RefreshTokenRecord,
+    ) -> Result<(), AuthError>
+    where
+        T: for<'a> UserService<'a>,
+    {
+        user_service
+            .revoke_refresh_token_family(record.user_id(), record.family_id())
+            .await
+            .map_err(|e| AuthError::TokenStoreError(e.to_string()))
+    }
+
+    /// Revokes the refresh token and every other token of its family.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the token is invalid or the store fails.
+    pub async fn revoke_refresh_token<T>(
+        &self,
+        refresh_token: &str,
+        user_service: &T,
+    ) -> Result<(), AuthError>
+    where
+        T: for<'a> UserService<'a>,
+    {
+        let (_, record) = self
+            .load_refresh_token_record(refresh_token, user_service)
+            .await?;
+
+        Self::revoke_family(user_service, &record).await
     }
 
     /// Legacy method for backward compatibility - generates only access token.
@@ -183,42 +618,47 @@ impl AuthService {
     /// # Errors
     ///
     /// Returns a `jsonwebtoken::errors::Error` if the token encoding fails.
-    pub fn generate_token(
-        user: &User,
-        secret: &str,
-    ) -> Result<String, jsonwebtoken::errors::Error> {
-        Self::generate_access_token(user, secret).map_err(|e| match e {
+    pub fn generate_token(&self, user: &User) -> Result<String, jsonwebtoken::errors::Error> {
+        self.generate_access_token(user, None).map_err(|e| match e {
             AuthError::JwtError(jwt_err) => jwt_err,
             _ => jsonwebtoken::errors::Error::from(jsonwebtoken::errors::ErrorKind::InvalidToken),
         })
     }
 
-    /// Verifies a JWT token and extracts claims.
+    /// Verifies a JWT token and extracts claims. The token must be signed
+    /// by one of the known keys, with that key's algorithm, and carry the
+    /// configured issuer and audience. Clock skew up to the configured
+    /// leeway is tolerated.
     ///
     /// # Errors
     ///
     /// Returns an `AuthError` if the token is invalid, expired, or malformed.
-    pub fn verify_token(token: &str, secret: &str) -> Result<Claims, AuthError> {
-        decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(secret.as_ref()),
-            &Validation::new(Algorithm::HS256),
-        )
-        .map(|data| data.claims)
-        .map_err(AuthError::from)
+    pub fn verify_token(&self, token: &str) -> Result<Claims, AuthError> {
+        let header = decode_header(token)?;
+        let (key, algorithm) = self
+            .keys
+            .decoding_key(&header)
+            .ok_or(AuthError::UnknownSigningKey)?;
+
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = self.tokens.leeway_seconds;
+        validation.validate_nbf = true;
+        validation.set_issuer(&[&self.tokens.issuer]);
+        validation.set_audience(&[&self.tokens.audience]);
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud"]);
+
+        decode::<Claims>(token, key, &validation)
+            .map(|data| data.claims)
+            .map_err(AuthError::from)
     }
 
-    /// Verifies an access token specifically.
-    ///
-    /// # Errors
-    ///
-    /// Returns an `AuthError` if the token is invalid or not an access token.
-    pub fn verify_access_token(token: &str, secret: &str) -> Result<Claims, AuthError> {
-        let claims = Self::verify_token(token, secret)?;
+    /// Verifies a token and checks that it is of the `expected` type.
+    fn verify_token_of_type(&self, token: &str, expected: TokenType) -> Result<Claims, AuthError> {
+        let claims = self.verify_token(token)?;
 
-        if !matches!(claims.token_type, TokenType::Access) {
+        if claims.token_type != expected {
             return Err(AuthError::InvalidTokenType {
-                expected: "Access".to_string(),
+                expected: format!("{expected:?}"),
                 actual: format!("{:?}", claims.token_type),
             });
         }
@@ -226,21 +666,296 @@ impl AuthService {
         Ok(claims)
     }
 
-    /// Hashes a password using bcrypt with the default cost.
+    /// Verifies an access token specifically.
     ///
     /// # Errors
     ///
-    /// Returns an `AuthError` if the hashing process fails.
-    pub fn hash_password(password: &str) -> Result<String, AuthError> {
-        bcrypt::hash(password, bcrypt::DEFAULT_COST).map_err(AuthError::from)
+    /// Returns an `AuthError` if the token is invalid or not an access token.
+    pub fn verify_access_token(&self, token: &str) -> Result<Claims, AuthError> {
+        self.verify_token_of_type(token, TokenType::Access)
     }
 
-    /// Verifies if a password matches the provided hash.
+    /// Verifies an email verification token's signature and type. The
+    /// caller still has to consume its one-time record.
     ///
     /// # Errors
     ///
-    /// Returns an `AuthError` if the verification process fails.
+    /// Returns an `AuthError` if the token is invalid or not an email
+    /// verification token.
+    pub fn verify_email_verification_token(&self, token: &str) -> Result<Claims, AuthError> {
+        self.verify_token_of_type(token, TokenType::EmailVerification)
+    }
+
+    /// Verifies the challenge token from the password step of a two-factor
+    /// sign-in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the token is invalid, expired or not an MFA
+    /// challenge token.
+    pub fn verify_mfa_challenge_token(&self, token: &str) -> Result<Claims, AuthError> {
+        self.verify_token_of_type(token, TokenType::MfaChallenge)
+    }
+
+    /// Hashes a password with the configured algorithm and parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the parameters are invalid or the hashing
+    /// process fails.
+    pub fn hash_password(
+        password: &str,
+        config: &PasswordHashingConfig,
+    ) -> Result<String, AuthError> {
+        match config.algorithm {
+            PasswordHashAlgorithm::Argon2id => {
+                let mut salt = [0u8; 16];
+                rand::rngs::OsRng.try_fill_bytes(&mut salt)?;
+                let salt = SaltString::encode_b64(&salt)?;
+                let hash = Self::argon2(config)?.hash_password(password.as_bytes(), &salt)?;
+                Ok(hash.to_string())
+            }
+            PasswordHashAlgorithm::Bcrypt => {
+                bcrypt::hash(password, config.bcrypt_cost).map_err(AuthError::from)
+            }
+        }
+    }
+
+    /// Verifies if a password matches the provided hash, which may be an
+    /// Argon2 or a bcrypt hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the hash is malformed or the verification
+    /// process fails.
     pub fn verify_password(password: &str, hash: &str) -> Result<bool, AuthError> {
-        bcrypt::verify(password, hash).map_err(AuthError::from)
+        if !hash.starts_with("$argon2") {
+            return bcrypt::verify(password, hash).map_err(AuthError::from);
+        }
+
+        let parsed = PhcHash::new(hash)?;
+        // The algorithm and parameters are read from the hash itself
+        match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether a stored hash was made with another algorithm or other
+    /// parameters than `config` asks for, and should be replaced once the
+    /// password is known.
+    #[must_use]
+    pub fn needs_rehash(hash: &str, config: &PasswordHashingConfig) -> bool {
+        match config.algorithm {
+            PasswordHashAlgorithm::Argon2id => {
+                let Ok(parsed) = PhcHash::new(hash) else {
+                    return true;
+                };
+                let Ok(params) = Params::try_from(&parsed) else {
+                    return true;
+                };
+                parsed.algorithm != argon2::ARGON2ID_IDENT
+                    || parsed.version != Some(argon2::Version::V0x13.into())
+                    || params.m_cost() != config.argon2_memory_kib
+                    || params.t_cost() != config.argon2_iterations
+                    || params.p_cost() != config.argon2_parallelism
+            }
+            PasswordHashAlgorithm::Bcrypt => hash
+                .parse::<bcrypt::HashParts>()
+                .map_or(true, |parts| parts.get_cost() != config.bcrypt_cost),
+        }
+    }
+
+    /// Checks that `config` can be used to hash passwords, so a bad
+    /// setting fails at startup rather than on the first registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AuthError` if the parameters are out of range.
+    pub fn validate_password_hashing(config: &PasswordHashingConfig) -> Result<(), AuthError> {
+        match config.algorithm {
+            PasswordHashAlgorithm::Argon2id => Self::argon2(config).map(|_| ()),
+            PasswordHashAlgorithm::Bcrypt if (4..=31).contains(&config.bcrypt_cost) => Ok(()),
+            PasswordHashAlgorithm::Bcrypt => {
+                Err(bcrypt::BcryptError::CostNotAllowed(config.bcrypt_cost).into())
+            }
+        }
+    }
+
+    fn argon2(config: &PasswordHashingConfig) -> Result<Argon2<'static>, AuthError> {
+        let params = Params::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+            None,
+        )
+        .map_err(password_hash::Error::from)?;
+        Ok(Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            params,
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::application::user_service::tests::{auth, register, service};
+    use crate::domain::repositories::user_repository::UserRepository;
+
+    #[tokio::test]
+    async fn refresh_rotates_the_token_within_its_family() {
+        let service = service();
+        let auth = auth();
+        let signed_in = register(&service, &auth, "ada@example.com").await;
+
+        let rotated = auth
+            .refresh_access_token(&signed_in.refresh_token, &ClientInfo::default(), &service)
+            .await
+            .unwrap();
+
+        let (old_claims, old_record) = auth
+            .load_refresh_token_record(&signed_in.refresh_token, &service)
+            .await
+            .unwrap();
+        assert!(old_record.is_used());
+        assert_eq!(rotated.family_id, old_record.family_id());
+        assert_ne!(rotated.refresh_token_id, old_claims.jti);
+
+        let (_, new_record) = auth
+            .load_refresh_token_record(&rotated.refresh_token, &service)
+            .await
+            .unwrap();
+        assert!(new_record.is_active());
+    }
+
+    #[tokio::test]
+    async fn reusing_a_rotated_token_revokes_the_family() {
+        let service = service();
+        let auth = auth();
+        let signed_in = register(&service, &auth, "ada@example.com").await;
+        let client = ClientInfo::default();
+
+        let rotated = auth
+            .refresh_access_token(&signed_in.refresh_token, &client, &service)
+            .await
+            .unwrap();
+
+        let replayed = auth
+            .refresh_access_token(&signed_in.refresh_token, &client, &service)
+            .await;
+        assert!(matches!(replayed, Err(AuthError::InvalidRefreshToken)));
+
+        // The legitimate holder is signed out too, since either side may be
+        // the thief.
+        let after_reuse = auth
+            .refresh_access_token(&rotated.refresh_token, &client, &service)
+            .await;
+        assert!(matches!(after_reuse, Err(AuthError::InvalidRefreshToken)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_refreshes_of_one_token_cannot_both_succeed() {
+        let service = service();
+        let auth = auth();
+        let signed_in = register(&service, &auth, "ada@example.com").await;
+        let client = ClientInfo::default();
+
+        let (first, second) = tokio::join!(
+            auth.refresh_access_token(&signed_in.refresh_token, &client, &service),
+            auth.refresh_access_token(&signed_in.refresh_token, &client, &service),
+        );
+
+        assert!(first.is_err() || second.is_err());
+    }
+
+    #[tokio::test]
+    async fn refresh_survives_an_email_change() {
+        let service = service();
+        let auth = auth();
+        let signed_in = register(&service, &auth, "ada@example.com").await;
+        let user_id = UserId::new(signed_in.user.id).unwrap();
+        let mut user = service.get_user_by_id(&user_id).await.unwrap().unwrap();
+        user.change_email("ada.lovelace@example.com").unwrap();
+        service.update_user(user).await.unwrap();
+
+        let rotated = auth
+            .refresh_access_token(&signed_in.refresh_token, &ClientInfo::default(), &service)
+            .await
+            .unwrap();
+
+        let claims = auth.verify_access_token(&rotated.access_token).unwrap();
+        assert_eq!(claims.sub, user_id.as_str());
+        assert_eq!(claims.email, "ada.lovelace@example.com");
+    }
+
+    fn cheap_argon2() -> PasswordHashingConfig {
+        PasswordHashingConfig {
+            argon2_memory_kib: 64,
+            argon2_iterations: 1,
+            ..PasswordHashingConfig::default()
+        }
+    }
+
+    #[test]
+    fn hashes_made_with_the_current_parameters_are_kept() {
+        let config = cheap_argon2();
+        let hash = AuthService::hash_password("Tangerine-Harbor-42", &config).unwrap();
+
+        assert!(!AuthService::needs_rehash(&hash, &config));
+    }
+
+    #[test]
+    fn hashes_made_with_other_argon2_parameters_are_replaced() {
+        let config = cheap_argon2();
+        let hash = AuthService::hash_password("Tangerine-Harbor-42", &config).unwrap();
+
+        for stronger in [
+            PasswordHashingConfig {
+                argon2_memory_kib: 128,
+                ..cheap_argon2()
+            },
+            PasswordHashingConfig {
+                argon2_iterations: 2,
+                ..cheap_argon2()
+            },
+            PasswordHashingConfig {
+                argon2_parallelism: 2,
+                ..cheap_argon2()
+            },
+        ] {
+            assert!(AuthService::needs_rehash(&hash, &stronger));
+        }
+    }
+
+    #[test]
+    fn hashes_made_with_another_algorithm_are_replaced() {
+        let bcrypt = PasswordHashingConfig {
+            algorithm: PasswordHashAlgorithm::Bcrypt,
+            bcrypt_cost: 4,
+            ..PasswordHashingConfig::default()
+        };
+        let bcrypt_hash = AuthService::hash_password("Tangerine-Harbor-42", &bcrypt).unwrap();
+        let argon2_hash =
+            AuthService::hash_password("Tangerine-Harbor-42", &cheap_argon2()).unwrap();
+
+        assert!(AuthService::needs_rehash(&bcrypt_hash, &cheap_argon2()));
+        assert!(AuthService::needs_rehash(&argon2_hash, &bcrypt));
+        assert!(AuthService::needs_rehash(
+            &bcrypt_hash,
+            &PasswordHashingConfig {
+                bcrypt_cost: 5,
+                ..bcrypt
+            }
+        ));
+        assert!(AuthService::verify_password("Tangerine-Harbor-42", &bcrypt_hash).unwrap());
+    }
+
+    #[test]
+    fn unreadable_hashes_are_replaced() {
+        assert!(AuthService::needs_rehash("not-a-hash", &cheap_argon2()));
     }
 }
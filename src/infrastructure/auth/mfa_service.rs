@@ -0,0 +1,259 @@
+#[cfg(feature = "ssr")]
+use crate::{
+    domain::models::one_time_token::{OneTimeToken, TokenPurpose},
+    domain::value_objects::UserId,
+    infrastructure::auth::jwt_service::AuthService,
+};
+#[cfg(feature = "ssr")]
+use chrono::Utc;
+#[cfg(feature = "ssr")]
+use rand::RngCore;
+#[cfg(feature = "ssr")]
+use thiserror::Error;
+#[cfg(feature = "ssr")]
+use totp_rs::{Algorithm, Secret, TOTP};
+
+#[cfg(feature = "ssr")]
+#[derive(Error, Debug)]
+pub enum MfaError {
+    #[error("Invalid TOTP secret: {0}")]
+    InvalidSecret(#[from] totp_rs::SecretParseError),
+
+    #[error("Invalid TOTP parameters: {0}")]
+    InvalidParameters(#[from] totp_rs::TotpUrlError),
+
+    #[error("Secure random number generator failed: {0}")]
+    RandomError(#[from] rand::Error),
+}
+
+/// TOTP (RFC 6238) codes and recovery codes for two-factor sign-in.
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug)]
+pub struct MfaService {}
+
+#[cfg(feature = "ssr")]
+impl MfaService {
+    /// Shown next to the account in authenticator apps.
+    const ISSUER: &'static str = "Finance Tracker";
+    const DIGITS: usize = 6;
+    const STEP_SECONDS: i64 = 30;
+    /// Codes from this many steps either side of now are accepted, to allow
+    /// for clock drift on the user's device.
+    const ALLOWED_DRIFT_STEPS: i64 = 1;
+    /// 160 bits, the length RFC 4226 recommends.
+    const SECRET_BYTES: usize = 20;
+    pub const RECOVERY_CODE_COUNT: usize = 10;
+    /// 32 characters, so each random byte maps to one without bias. `l`,
+    /// `o`, `0` and `1` are left out as they are easily confused.
+    const RECOVERY_CODE_ALPHABET: &'static [u8; 32] = b"abcdefghijkmnpqrstuvwxyz23456789";
+    const RECOVERY_CODE_LENGTH: usize = 10;
+    /// Recovery codes are valid until used or replaced; the token store
+    /// needs an expiry, so they get one far in the future.
+    const RECOVERY_CODE_DURATION_DAYS: i64 = 365 * 100;
+
+    /// Generates a new shared secret, base32-encoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `MfaError` if the system random number generator fails.
+    pub fn generate_secret() -> Result<String, MfaError> {
+        let mut bytes = [0u8; Self::SECRET_BYTES];
+        rand::rngs::OsRng.try_fill_bytes(&mut bytes)?;
+        Ok(Secret::Raw(bytes.to_vec()).to_encoded().to_string())
+    }
+
+    fn totp(secret: &str, account_name: &str) -> Result<TOTP, MfaError> {
+        let secret = Secret::Encoded(secret.to_string()).to_bytes()?;
+        // Drift is handled in `verify_code`, which needs the matching step
+        TOTP::new(
+            Algorithm::SHA1,
+            Self::DIGITS,
+            0,
+            Self::STEP_SECONDS.unsigned_abs(),
+            secret,
+            Some(Self::ISSUER.to_string()),
+            account_name.to_string(),
+        )
+        .map_err(MfaError::from)
+    }
+
+    /// Builds the `otpauth://` URI that authenticator apps import, usually
+    /// from a QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `MfaError` if the secret is malformed.
+    pub fn otpauth_uri(secret: &str, account_name: &str) -> Result<String, MfaError> {
+        Ok(Self::totp(secret, account_name)?.get_url())
+    }
+
+    /// Checks `code` against the time steps around now, skipping any at or
+    /// before `last_used_step`. Returns the step the code matched, which
+    /// the caller must record so the code cannot be used again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `MfaError` if the secret is malformed.
+    pub fn verify_code(
+        secret: &str,
+        code: &str,
+        last_used_step: Option<i64>,
+    ) -> Result<Option<i64>, MfaError> {
+        let totp = Self::totp(secret, "")?;
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let current_step = Utc::now().timestamp() / Self::STEP_SECONDS;
+
+        Ok(
+            (current_step - Self::ALLOWED_DRIFT_STEPS..=current_step + Self::ALLOWED_DRIFT_STEPS)
+                .filter(|step| last_used_step.is_none_or(|last| *step > last))
+                .find(|step| {
+                    u64::try_from(step * Self::STEP_SECONDS)
+                        .is_ok_and(|time| totp.check(&code, time))
+                }),
+        )
+    }
+
+    /// Whether `code` looks like an authenticator code rather than a
+    /// recovery code.
+    #[must_use]
+    pub fn is_totp_code(code: &str) -> bool {
+        let digits: Vec<char> = code.chars().filter(|c| !c.is_whitespace()).collect();
+        digits.len() == Self::DIGITS && digits.iter().all(char::is_ascii_digit)
+    }
+
+    /// Generates a fresh set of recovery codes for the user, together with
+    /// the one-time records that must be stored before the codes are shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `MfaError` if the system random number generator fails.
+    pub fn generate_recovery_codes(
+        user_id: &UserId,
+    ) -> Result<(Vec<String>, Vec<OneTimeToken>), MfaError> {
+        let expires_at = Utc::now() + chrono::Duration::days(Self::RECOVERY_CODE_DURATION_DAYS);
+        let mut codes = Vec::with_capacity(Self::RECOVERY_CODE_COUNT);
+        let mut records = Vec::with_capacity(Self::RECOVERY_CODE_COUNT);
+
+        for _ in 0..Self::RECOVERY_CODE_COUNT {
+            let mut bytes = [0u8; Self::RECOVERY_CODE_LENGTH];
+            rand::rngs::OsRng.try_fill_bytes(&mut bytes)?;
+            let characters: String = bytes
+                .iter()
+                .map(|byte| char::from(Self::RECOVERY_CODE_ALPHABET[usize::from(byte % 32)]))
+                .collect();
+            let (first, second) = characters.split_at(Self::RECOVERY_CODE_LENGTH / 2);
+            let code = format!("{first}-{second}");
+
+            records.push(OneTimeToken::new(
+                Self::recovery_code_key(user_id, &code),
+                user_id.clone(),
+                TokenPurpose::MfaRecovery,
+                expires_at,
+            ));
+            codes.push(code);
+        }
+
+        Ok((codes, records))
+    }
+
+    /// Key under which a recovery code is stored: the digest of the user id
+    /// and the code, ignoring case, spaces and dashes. Binding the user id
+    /// means a code only ever works for the account it was issued to.
+    #[must_use]
+    pub fn recovery_code_key(user_id: &UserId, code: &str) -> String {
+        let normalized: String = code
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        AuthService::hash_one_time_token(&format!("{user_id}:{normalized}"))
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+#[allow(clippy::unwrap_used)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// The authenticator code for `secret` at `steps` steps from now.
+    pub(crate) fn code_at(secret: &str, steps: i64) -> String {
+        let step = Utc::now().timestamp() / MfaService::STEP_SECONDS + steps;
+        let time = u64::try_from(step * MfaService::STEP_SECONDS).unwrap();
+        MfaService::totp(secret, "").unwrap().generate(time)
+    }
+
+    #[test]
+    fn accepts_codes_within_the_allowed_drift() {
+        let secret = MfaService::generate_secret().unwrap();
+        let current_step = Utc::now().timestamp() / MfaService::STEP_SECONDS;
+
+        for drift in [-1, 0, 1] {
+            let step = MfaService::verify_code(&secret, &code_at(&secret, drift), None).unwrap();
+            // The clock may tick over between generating and checking.
+            assert!(step.is_some_and(|step| (step - current_step - drift).abs() <= 1));
+        }
+        assert!(
+            MfaService::verify_code(&secret, &code_at(&secret, 5), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn rejects_a_code_from_an_already_used_step() {
+        let secret = MfaService::generate_secret().unwrap();
+        let code = code_at(&secret, 0);
+
+        let step = MfaService::verify_code(&secret, &code, None)
+            .unwrap()
+            .unwrap();
+
+        assert!(
+            MfaService::verify_code(&secret, &code, Some(step))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn ignores_spaces_in_authenticator_codes() {
+        let secret = MfaService::generate_secret().unwrap();
+        let code = code_at(&secret, 0);
+        let spaced = format!("{} {}", &code[..3], &code[3..]);
+
+        assert!(MfaService::is_totp_code(&spaced));
+        assert!(!MfaService::is_totp_code("abcde-fghij"));
+        assert!(!MfaService::is_totp_code("12345"));
+        assert!(
+            MfaService::verify_code(&secret, &spaced, None)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn recovery_codes_are_distinct_and_bound_to_their_user() {
+        let user_id = UserId::generate();
+        let (codes, records) = MfaService::generate_recovery_codes(&user_id).unwrap();
+
+        assert_eq!(codes.len(), MfaService::RECOVERY_CODE_COUNT);
+        let mut unique = codes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), codes.len());
+
+        let code = &codes[0];
+        assert_eq!(
+            records[0].id(),
+            MfaService::recovery_code_key(&user_id, code)
+        );
+        assert_eq!(
+            MfaService::recovery_code_key(&user_id, &code.to_uppercase().replace('-', " ")),
+            records[0].id()
+        );
+        assert_ne!(
+            MfaService::recovery_code_key(&UserId::generate(), code),
+            records[0].id()
+        );
+    }
+}
@@ -1,2 +1,4 @@
 pub mod auth_context;
 pub mod jwt_service;
+pub mod mfa_service;
+pub mod signing_keys;
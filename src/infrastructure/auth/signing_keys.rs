@@ -0,0 +1,503 @@
+#[cfg(feature = "ssr")]
+use crate::infrastructure::config::app_config::AuthConfig;
+#[cfg(feature = "ssr")]
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+#[cfg(feature = "ssr")]
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+#[cfg(feature = "ssr")]
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header};
+#[cfg(feature = "ssr")]
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+#[cfg(feature = "ssr")]
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+#[cfg(feature = "ssr")]
+use rsa::traits::PublicKeyParts;
+#[cfg(feature = "ssr")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "ssr")]
+use std::fmt;
+#[cfg(feature = "ssr")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "ssr")]
+use thiserror::Error;
+
+#[cfg(feature = "ssr")]
+#[derive(Error, Debug)]
+pub enum SigningKeyError {
+    #[error("Failed to read key file {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{} does not contain an Ed25519 or RSA key in PEM format", path.display())]
+    UnsupportedKey { path: PathBuf },
+
+    #[error("{} holds a public key, but the signing key must be a private key", path.display())]
+    NotAPrivateKey { path: PathBuf },
+
+    #[error("The RSA key in {} has {bits} bits; at least 2048 are required", path.display())]
+    RsaKeyTooSmall { path: PathBuf, bits: usize },
+
+    #[error("Invalid signing key: {0}")]
+    JwtError(#[from] jsonwebtoken::errors::Error),
+
+    #[error("No token signing key configured. Set JWT_SIGNING_KEY_FILE or JWT_SECRET")]
+    NoSigningKey,
+}
+
+/// The public half of an asymmetric token signing key.
+#[cfg(feature = "ssr")]
+enum PublicKey {
+    Ed25519([u8; 32]),
+    Rsa { modulus: Vec<u8>, exponent: Vec<u8> },
+}
+
+#[cfg(feature = "ssr")]
+impl PublicKey {
+    const MIN_RSA_BITS: usize = 2048;
+
+    fn rsa(key: &rsa::RsaPublicKey, path: &Path) -> Result<Self, SigningKeyError> {
+        let bits = key.size() * 8;
+        if bits < Self::MIN_RSA_BITS {
+            return Err(SigningKeyError::RsaKeyTooSmall {
+                path: path.to_path_buf(),
+                bits,
+            });
+        }
+        Ok(Self::Rsa {
+            modulus: key.n().to_bytes_be(),
+            exponent: key.e().to_bytes_be(),
+        })
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Ed25519(_) => Algorithm::EdDSA,
+            Self::Rsa { .. } => Algorithm::RS256,
+        }
+    }
+
+    /// The RFC 7638 thumbprint of the key, used as its `kid`. It only
+    /// depends on the key, so every instance derives the same id.
+    fn thumbprint(&self) -> String {
+        // Members in lexicographic order, without whitespace
+        let canonical = match self {
+            Self::Ed25519(x) => format!(
+                r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#,
+                URL_SAFE_NO_PAD.encode(x)
+            ),
+            Self::Rsa { modulus, exponent } => format!(
+                r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+                URL_SAFE_NO_PAD.encode(exponent),
+                URL_SAFE_NO_PAD.encode(modulus)
+            ),
+        };
+        URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+    }
+
+    fn to_jwk(&self, kid: &str) -> Jwk {
+        let (key_algorithm, algorithm) = match self {
+            Self::Ed25519(x) => (
+                KeyAlgorithm::EdDSA,
+                AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(x),
+                }),
+            ),
+            Self::Rsa { modulus, exponent } => (
+                KeyAlgorithm::RS256,
+                AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(modulus),
+                    e: URL_SAFE_NO_PAD.encode(exponent),
+                }),
+            ),
+        };
+        Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(key_algorithm),
+                key_id: Some(kid.to_string()),
+                ..CommonParameters::default()
+            },
+            algorithm,
+        }
+    }
+}
+
+/// A key that tokens are verified with.
+#[cfg(feature = "ssr")]
+struct VerificationKey {
+    /// `None` only for the shared HS256 secret, whose tokens carry no `kid`.
+    kid: Option<String>,
+    algorithm: Algorithm,
+    decoding_key: DecodingKey,
+    /// Published in the JWKS; `None` for the shared secret.
+    jwk: Option<Jwk>,
+}
+
+/// The key new tokens are signed with and every key tokens are still
+/// accepted from.
+///
+/// Asymmetric keys (Ed25519 signing with `EdDSA`, or RSA with `RS256`) are
+/// identified by a `kid` header and published as a JWKS, so other services
+/// can verify tokens without holding a secret. To rotate, make the new key
+/// the signing key and list the old one as a verification key until the
+/// tokens it signed have expired. A shared HS256 secret signs tokens when no
+/// signing key is configured, and otherwise only verifies tokens issued
+/// before the switch.
+#[cfg(feature = "ssr")]
+pub struct JwtKeyRing {
+    signing_kid: Option<String>,
+    signing_algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    verification_keys: Vec<VerificationKey>,
+}
+
+#[cfg(feature = "ssr")]
+impl JwtKeyRing {
+    /// Signs and verifies tokens with a shared HS256 secret.
+    #[must_use]
+    pub fn hmac(secret: &str) -> Self {
+        Self {
+            signing_kid: None,
+            signing_algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            verification_keys: vec![Self::hmac_verification_key(secret)],
+        }
+    }
+
+    /// Builds the key ring from `JWT_SIGNING_KEY_FILE`,
+    /// `JWT_VERIFICATION_KEY_FILES` and `JWT_SECRET`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SigningKeyError` if no key is configured or a key file
+    /// cannot be read or parsed.
+    pub fn from_config(config: &AuthConfig) -> Result<Self, SigningKeyError> {
+        let Some(signing_key_file) = &config.jwt_signing_key_file else {
+            return config
+                .jwt_secret
+                .as_deref()
+                .map(Self::hmac)
+                .ok_or(SigningKeyError::NoSigningKey);
+        };
+
+        let (public_key, encoding_key) = Self::read_key(signing_key_file)?;
+        let encoding_key = encoding_key.ok_or_else(|| SigningKeyError::NotAPrivateKey {
+            path: signing_key_file.clone(),
+        })?;
+        let signing_key = Self::verification_key(&public_key)?;
+
+        let mut ring = Self {
+            signing_kid: signing_key.kid.clone(),
+            signing_algorithm: signing_key.algorithm,
+            encoding_key,
+            verification_keys: vec![signing_key],
+        };
+        for path in &config.jwt_verification_key_files {
+            let (public_key, _) = Self::read_key(path)?;
+            let key = Self::verification_key(&public_key)?;
+            if !ring
+                .verification_keys
+                .iter()
+                .any(|known| known.kid == key.kid)
+            {
+                ring.verification_keys.push(key);
+            }
+        }
+        if let Some(secret) = &config.jwt_secret {
+            ring.verification_keys
+                .push(Self::hmac_verification_key(secret));
+        }
+        Ok(ring)
+    }
+
+    /// Reads a PEM file holding an Ed25519 or RSA key, either private
+    /// (PKCS#8, or PKCS#1 for RSA) or public. Returns the public key, and an
+    /// encoding key if the file holds a private key.
+    fn read_key(path: &Path) -> Result<(PublicKey, Option<EncodingKey>), SigningKeyError> {
+        let pem = std::fs::read_to_string(path).map_err(|source| SigningKeyError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        if let Ok(key) = ed25519_dalek::SigningKey::from_pkcs8_pem(&pem) {
+            let public_key = PublicKey::Ed25519(key.verifying_key().to_bytes());
+            return Ok((public_key, Some(EncodingKey::from_ed_pem(pem.as_bytes())?)));
+        }
+        if let Ok(key) = rsa::RsaPrivateKey::from_pkcs8_pem(&pem)
+            .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_pem(&pem))
+        {
+            let public_key = PublicKey::rsa(&key.to_public_key(), path)?;
+            return Ok((public_key, Some(EncodingKey::from_rsa_pem(pem.as_bytes())?)));
+        }
+        if let Ok(key) = ed25519_dalek::VerifyingKey::from_public_key_pem(&pem) {
+            return Ok((PublicKey::Ed25519(key.to_bytes()), None));
+        }
+        if let Ok(key) = rsa::RsaPublicKey::from_public_key_pem(&pem)
+            .or_else(|_| rsa::RsaPublicKey::from_pkcs1_pem(&pem))
+        {
+            return Ok((PublicKey::rsa(&key, path)?, None));
+        }
+
+        Err(SigningKeyError::UnsupportedKey {
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn verification_key(public_key: &PublicKey) -> Result<VerificationKey, SigningKeyError> {
+        let kid = public_key.thumbprint();
+        let jwk = public_key.to_jwk(&kid);
+        Ok(VerificationKey {
+            kid: Some(kid),
+            algorithm: public_key.algorithm(),
+            decoding_key: DecodingKey::from_jwk(&jwk)?,
+            jwk: Some(jwk),
+        })
+    }
+
+    fn hmac_verification_key(secret: &str) -> VerificationKey {
+        VerificationKey {
+            kid: None,
+            algorithm: Algorithm::HS256,
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+        }
+    }
+
+    /// Header for a newly signed token.
+    #[must_use]
+    pub fn header(&self) -> Header {
+        Header {
+            kid: self.signing_kid.clone(),
+            ..Header::new(self.signing_algorithm)
+        }
+    }
+
+    #[must_use]
+    pub fn encoding_key(&self) -> &EncodingKey {
+        &self.encoding_key
+    }
+
+    /// The key a token with `header` must be verified with, and the only
+    /// algorithm accepted for it. Tokens naming an unknown `kid`, or an
+    /// algorithm other than their key's, get `None`.
+    #[must_use]
+    pub fn decoding_key(&self, header: &Header) -> Option<(&DecodingKey, Algorithm)> {
+        self.verification_keys
+            .iter()
+            .find(|key| key.kid == header.kid && key.algorithm == header.alg)
+            .map(|key| (&key.decoding_key, key.algorithm))
+    }
+
+    /// The public keys tokens are accepted from, for `/.well-known/jwks.json`.
+    #[must_use]
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .verification_keys
+                .iter()
+                .filter_map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl fmt::Debug for JwtKeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<_> = self
+            .verification_keys
+            .iter()
+            .map(|key| (key.kid.as_deref(), key.algorithm))
+            .collect();
+        f.debug_struct("JwtKeyRing")
+            .field("signing_kid", &self.signing_kid)
+            .field("signing_algorithm", &self.signing_algorithm)
+            .field("verification_keys", &keys)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::domain::models::login_throttle::LoginThrottlePolicy;
+    use crate::domain::models::password_policy::PasswordPolicy;
+    use crate::infrastructure::config::app_config::{
+        LoginThrottleStore, PasswordHashingConfig, TokenConfig,
+    };
+    use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+    use ed25519_dalek::pkcs8::{EncodePrivateKey, EncodePublicKey};
+    use rand::RngCore;
+
+    /// Writes the private and public PEM files of an Ed25519 key into a
+    /// fresh temporary directory.
+    fn ed25519_key_files() -> (PathBuf, PathBuf) {
+        let mut seed = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut seed);
+        let key = ed25519_dalek::SigningKey::from_bytes(&seed);
+        let dir = std::env::temp_dir().join(format!("signing-keys-{}", hex::encode(&seed[..8])));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let private = dir.join("private.pem");
+        let public = dir.join("public.pem");
+        std::fs::write(
+            &private,
+            key.to_pkcs8_pem(LineEnding::LF).unwrap().as_bytes(),
+        )
+        .unwrap();
+        std::fs::write(
+            &public,
+            key.verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        )
+        .unwrap();
+        (private, public)
+    }
+
+    fn config(
+        signing_key_file: Option<PathBuf>,
+        verification_key_files: Vec<PathBuf>,
+        secret: Option<&str>,
+    ) -> AuthConfig {
+        AuthConfig {
+            jwt_secret: secret.map(str::to_string),
+            jwt_signing_key_file: signing_key_file,
+            jwt_verification_key_files: verification_key_files,
+            tokens: TokenConfig::default(),
+            password_policy: PasswordPolicy::default(),
+            password_hashing: PasswordHashingConfig::default(),
+            require_email_verification: false,
+            login_throttle: LoginThrottlePolicy::default(),
+            login_throttle_store: LoginThrottleStore::default(),
+        }
+    }
+
+    fn header(alg: Algorithm, kid: Option<&str>) -> Header {
+        Header {
+            kid: kid.map(str::to_string),
+            ..Header::new(alg)
+        }
+    }
+
+    #[test]
+    fn signs_with_the_key_thumbprint_as_kid() {
+        let (private, _) = ed25519_key_files();
+        let ring = JwtKeyRing::from_config(&config(Some(private), Vec::new(), None)).unwrap();
+
+        let header = ring.header();
+        let jwks = ring.jwks();
+
+        assert_eq!(header.alg, Algorithm::EdDSA);
+        assert_eq!(jwks.keys.len(), 1);
+        assert_eq!(jwks.keys[0].common.key_id, header.kid);
+        assert!(ring.decoding_key(&header).is_some());
+    }
+
+    #[test]
+    fn keeps_accepting_retired_keys_after_rotation() {
+        let (old_private, old_public) = ed25519_key_files();
+        let (new_private, _) = ed25519_key_files();
+        let old_ring =
+            JwtKeyRing::from_config(&config(Some(old_private), Vec::new(), None)).unwrap();
+        let ring = JwtKeyRing::from_config(&config(
+            Some(new_private),
+            vec![old_public],
+            Some("test-secret-that-is-long-enough-for-hs256"),
+        ))
+        .unwrap();
+
+        let kids: Vec<_> = ring
+            .jwks()
+            .keys
+            .into_iter()
+            .map(|key| key.common.key_id)
+            .collect();
+
+        // The shared secret verifies old tokens but is never published.
+        assert_eq!(kids, vec![ring.header().kid, old_ring.header().kid]);
+        assert!(ring.decoding_key(&old_ring.header()).is_some());
+        assert!(ring.decoding_key(&header(Algorithm::HS256, None)).is_some());
+    }
+
+    #[test]
+    fn rejects_unknown_kids_and_mismatched_algorithms() {
+        let (private, _) = ed25519_key_files();
+        let ring = JwtKeyRing::from_config(&config(
+            Some(private),
+            Vec::new(),
+            Some("test-secret-that-is-long-enough-for-hs256"),
+        ))
+        .unwrap();
+        let kid = ring.header().kid.unwrap();
+
+        assert!(
+            ring.decoding_key(&header(Algorithm::EdDSA, Some("unknown")))
+                .is_none()
+        );
+        assert!(ring.decoding_key(&header(Algorithm::EdDSA, None)).is_none());
+        // A token must not be able to pick HMAC and use a public key as the secret.
+        assert!(
+            ring.decoding_key(&header(Algorithm::HS256, Some(&kid)))
+                .is_none()
+        );
+        assert!(
+            ring.decoding_key(&header(Algorithm::RS256, Some(&kid)))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn verifies_tokens_signed_by_the_ring() {
+        let (private, _) = ed25519_key_files();
+        let ring = JwtKeyRing::from_config(&config(Some(private), Vec::new(), None)).unwrap();
+        let claims = serde_json::json!({ "sub": "ada" });
+
+        let token = jsonwebtoken::encode(&ring.header(), &claims, ring.encoding_key()).unwrap();
+        let header = jsonwebtoken::decode_header(&token).unwrap();
+        let (key, algorithm) = ring.decoding_key(&header).unwrap();
+        let mut validation = jsonwebtoken::Validation::new(algorithm);
+        validation.required_spec_claims.clear();
+
+        let decoded = jsonwebtoken::decode::<serde_json::Value>(&token, key, &validation).unwrap();
+        assert_eq!(decoded.claims, claims);
+    }
+
+    #[test]
+    fn refuses_a_public_key_as_the_signing_key() {
+        let (_, public) = ed25519_key_files();
+
+        let result = JwtKeyRing::from_config(&config(Some(public), Vec::new(), None));
+
+        assert!(matches!(
+            result,
+            Err(SigningKeyError::NotAPrivateKey { .. })
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_the_shared_secret() {
+        let ring = JwtKeyRing::from_config(&config(
+            None,
+            Vec::new(),
+            Some("test-secret-that-is-long-enough-for-hs256"),
+        ))
+        .unwrap();
+
+        assert_eq!(ring.header().alg, Algorithm::HS256);
+        assert!(ring.jwks().keys.is_empty());
+        assert!(matches!(
+            JwtKeyRing::from_config(&config(None, Vec::new(), None)),
+            Err(SigningKeyError::NoSigningKey)
+        ));
+    }
+}
@@ -2,11 +2,15 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
+        use crate::application::errors::transaction_service_errors::TransactionServiceError;
         use crate::application::errors::user_service_errors::UserServiceError;
-        use axum::http::StatusCode;
+        use crate::domain::errors::common::FieldError;
+        use crate::domain::models::personal_access_token::TokenScope;
+        use crate::infrastructure::auth::jwt_service::AuthError;
+        use axum::http::{HeaderValue, StatusCode, header};
         use axum::response::{IntoResponse, Response};
         use axum::Json;
-        use serde_json::json;
+        use serde_json::{json, Value};
         use thiserror::Error;
 
         #[derive(Error, Debug)]
@@ -17,6 +21,12 @@ cfg_if! {
                 source: UserServiceError,
             },
 
+            #[error("Transaction service error")]
+            Transaction {
+                #[from]
+                source: TransactionServiceError,
+            },
+
             #[error("Invalid request format")]
             InvalidRequest { message: String },
 
@@ -26,6 +36,15 @@ cfg_if! {
             #[error("Invalid token")]
             InvalidToken,
 
+            /// A personal access token was used without the scope the
+            /// endpoint requires.
+            #[error("Token lacks scope {scope}")]
+            InsufficientScope { scope: TokenScope },
+
+            /// The endpoint needs a signed-in session, not an access token.
+            #[error("Session required")]
+            SessionRequired,
+
             #[error("Request timeout")]
             Timeout,
         }
@@ -35,15 +54,42 @@ cfg_if! {
             pub fn to_status_code(&self) -> StatusCode {
                 match self {
                     WebError::UserService { source } => match source {
-                        UserServiceError::UserAlreadyExists { .. } => StatusCode::CONFLICT,
-                        UserServiceError::UserNotFound { .. } => StatusCode::NOT_FOUND,
-                        UserServiceError::InvalidCredentials => StatusCode::UNAUTHORIZED,
-                        UserServiceError::DomainValidation { .. } => StatusCode::BAD_REQUEST,
-                        UserServiceError::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+                        UserServiceError::UserAlreadyExists { .. }
+                        | UserServiceError::MfaAlreadyEnabled
+                        | UserServiceError::MfaNotEnrolled
+                        | UserServiceError::AccessTokenLimitReached { .. } => StatusCode::CONFLICT,
+                        UserServiceError::AccountNotFound { .. }
+                        | UserServiceError::AccessTokenNotFound { .. }
+                        | UserServiceError::SessionNotFound { .. } => StatusCode::NOT_FOUND,
+                        UserServiceError::InvalidCredentials
+                        | UserServiceError::InvalidMfaChallenge
+                        | UserServiceError::InvalidAccessToken => StatusCode::UNAUTHORIZED,
+                        // Not 401: the session, or for an unverified email the
+                        // password, was accepted
+                        UserServiceError::IncorrectPassword { .. }
+                        | UserServiceError::EmailNotVerified => StatusCode::FORBIDDEN,
+                        UserServiceError::DomainValidation { .. }
+                        | UserServiceError::InvalidVerificationToken
+                        | UserServiceError::InvalidResetToken
+                        | UserServiceError::InvalidMfaCode => StatusCode::BAD_REQUEST,
+                        UserServiceError::AccountLocked { .. } => StatusCode::TOO_MANY_REQUESTS,
+                        UserServiceError::ServiceUnavailable
+                        | UserServiceError::RepositoryError { .. } => StatusCode::SERVICE_UNAVAILABLE,
                         _ => StatusCode::INTERNAL_SERVER_ERROR,
                     },
+                    WebError::Transaction { source } => match source {
+                        TransactionServiceError::NotFound { .. } => StatusCode::NOT_FOUND,
+                        TransactionServiceError::InvalidQuery { .. }
+                        | TransactionServiceError::DomainValidation { .. } => StatusCode::BAD_REQUEST,
+                        TransactionServiceError::RepositoryError { .. } => {
+                            StatusCode::SERVICE_UNAVAILABLE
+                        }
+                    },
                     WebError::InvalidRequest { .. } => StatusCode::BAD_REQUEST,
                     WebError::MissingAuth | WebError::InvalidToken => StatusCode::UNAUTHORIZED,
+                    WebError::InsufficientScope { .. } | WebError::SessionRequired => {
+                        StatusCode::FORBIDDEN
+                    }
                     WebError::Timeout => StatusCode::REQUEST_TIMEOUT,
                 }
             }
@@ -55,34 +101,169 @@ cfg_if! {
                         UserServiceError::UserAlreadyExists { .. } => {
                             "An account with this email already exists".to_string()
                         }
-                        UserServiceError::UserNotFound { .. } | UserServiceError::InvalidCredentials => {
+                        UserServiceError::InvalidCredentials => {
                             "Invalid email or password".to_string()
                         }
+                        UserServiceError::AccountNotFound { .. } => "Account not found".to_string(),
+                        UserServiceError::IncorrectPassword { .. } => {
+                            "Current password is incorrect".to_string()
+                        }
+                        UserServiceError::EmailNotVerified => {
+                            "Please verify your email address before signing in".to_string()
+                        }
+                        UserServiceError::InvalidVerificationToken => {
+                            "This verification link is invalid or has expired".to_string()
+                        }
+                        UserServiceError::InvalidResetToken => {
+                            "This password reset link is invalid or has expired".to_string()
+                        }
+                        UserServiceError::MfaAlreadyEnabled => {
+                            "Two-factor authentication is already enabled".to_string()
+                        }
+                        UserServiceError::MfaNotEnrolled => {
+                            "Two-factor authentication is not set up for this account".to_string()
+                        }
+                        UserServiceError::InvalidMfaCode => {
+                            "Invalid authentication code".to_string()
+                        }
+                        UserServiceError::InvalidMfaChallenge => {
+                            "Your sign-in attempt has expired. Please sign in again".to_string()
+                        }
+                        UserServiceError::AccountLocked { .. } => {
+                            "Too many failed sign-in attempts. Please try again later".to_string()
+                        }
+                        UserServiceError::InvalidAccessToken => {
+                            "Invalid authentication token".to_string()
+                        }
+                        UserServiceError::AccessTokenNotFound { .. } => {
+                            "Access token not found".to_string()
+                        }
+                        UserServiceError::SessionNotFound { .. } => {
+                            "Session not found".to_string()
+                        }
+                        UserServiceError::AccessTokenLimitReached { limit } => {
+                            format!("You can have at most {limit} access tokens. Revoke one to create another")
+                        }
                         UserServiceError::DomainValidation { source } => source.to_string(),
-                        UserServiceError::ServiceUnavailable => {
+                        UserServiceError::ServiceUnavailable
+                        | UserServiceError::RepositoryError { .. } => {
                             "Service temporarily unavailable. Please try again later".to_string()
                         }
                         _ => "An error occurred. Please try again".to_string(),
                     },
+                    WebError::Transaction { source } => match source {
+                        TransactionServiceError::NotFound { .. } => {
+                            "Transaction not found".to_string()
+                        }
+                        TransactionServiceError::InvalidQuery { message } => message.clone(),
+                        TransactionServiceError::DomainValidation { source } => source.to_string(),
+                        TransactionServiceError::RepositoryError { .. } => {
+                            "Service temporarily unavailable. Please try again later".to_string()
+                        }
+                    },
                     WebError::InvalidRequest { message } => message.clone(),
                     WebError::MissingAuth => "Authentication required".to_string(),
                     WebError::InvalidToken => "Invalid authentication token".to_string(),
+                    WebError::InsufficientScope { scope } => {
+                        format!("This access token does not have the '{scope}' scope")
+                    }
+                    WebError::SessionRequired => {
+                        "Access tokens cannot be used here. Please sign in".to_string()
+                    }
                     WebError::Timeout => "Request timed out. Please try again".to_string(),
                 }
             }
+
+            /// Field-level validation errors to return alongside the message.
+            #[must_use]
+            pub fn field_errors(&self) -> Vec<FieldError> {
+                match self {
+                    WebError::UserService {
+                        source: UserServiceError::DomainValidation { source },
+                    } => source.field_errors(),
+                    WebError::UserService {
+                        source: UserServiceError::IncorrectPassword { field },
+                    } => vec![FieldError::new(
+                        field,
+                        "incorrect_password",
+                        "Current password is incorrect",
+                    )],
+                    WebError::UserService {
+                        source: UserServiceError::InvalidMfaCode,
+                    } => vec![FieldError::new(
+                        "code",
+                        "invalid_mfa_code",
+                        "Invalid authentication code",
+                    )],
+                    _ => Vec::new(),
+                }
+            }
+
+            /// Whether the client may retry the same request later.
+            #[must_use]
+            pub fn is_retryable(&self) -> bool {
+                match self {
+                    WebError::UserService {
+                        source: UserServiceError::AccountLocked { .. },
+                    }
+                    | WebError::Timeout => true,
+                    WebError::UserService { source } => source.is_transient(),
+                    WebError::Transaction { source } => source.is_transient(),
+                    _ => false,
+                }
+            }
+        }
+
+        impl From<AuthError> for WebError {
+            fn from(error: AuthError) -> Self {
+                match error {
+                    AuthError::JwtError(_)
+                    | AuthError::InvalidTokenType { .. }
+                    | AuthError::UnknownSigningKey
+                    | AuthError::TokenExpired
+                    | AuthError::InvalidRefreshToken => WebError::InvalidToken,
+                    other => WebError::UserService {
+                        source: UserServiceError::AuthServiceError {
+                            source: Box::new(other),
+                        },
+                    },
+                }
+            }
         }
 
         impl IntoResponse for WebError {
             fn into_response(self) -> Response {
                 let status = self.to_status_code();
+                let retry_after = match &self {
+                    WebError::UserService {
+                        source: UserServiceError::AccountLocked { retry_after_seconds },
+                    } => Some(*retry_after_seconds),
+                    _ => None,
+                };
                 let message = self.user_message();
 
-                let body = Json(json!({
+                let mut body = json!({
                     "error": message,
                     "code": status.as_u16()
-                }));
+                });
+                let errors = self.field_errors();
+                if !errors.is_empty() {
+                    body["errors"] = json!(errors);
+                }
+                if self.is_retryable() {
+                    body["retryable"] = Value::Bool(true);
+                }
+                if let Some(seconds) = retry_after {
+                    body["retry_after"] = json!(seconds);
+                }
 
-                (status, body).into_response()
+                let mut response = (status, Json(body)).into_response();
+                if let Some(seconds) = retry_after {
+                    response
+                        .headers_mut()
+                        .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+                }
+                response
             }
         }
     }
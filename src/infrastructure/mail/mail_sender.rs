@@ -0,0 +1,61 @@
+// Outgoing email abstraction and construction of the configured sender
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::sync::Arc;
+        use async_trait::async_trait;
+        use color_eyre::Result;
+        use lettre::message::Mailbox;
+        use lettre::message::header::ContentType;
+        use lettre::Message;
+        use crate::infrastructure::config::app_config::{MailConfig, MailTransport};
+        use crate::infrastructure::mail::mail_sender_file::FileMailSender;
+        use crate::infrastructure::mail::mail_sender_smtp::SmtpMailSender;
+        use crate::infrastructure::mail::mail_sender_stdout::StdoutMailSender;
+
+        /// A plain-text email to a single recipient.
+        #[derive(Debug, Clone)]
+        pub struct MailMessage {
+            pub to: String,
+            pub subject: String,
+            pub body: String,
+        }
+
+        impl MailMessage {
+            /// Builds the RFC 5322 message sent from `from`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the recipient is not a valid address.
+            pub fn to_lettre(&self, from: &Mailbox) -> Result<Message> {
+                Ok(Message::builder()
+                    .from(from.clone())
+                    .to(self.to.parse()?)
+                    .subject(self.subject.clone())
+                    .header(ContentType::TEXT_PLAIN)
+                    .body(self.body.clone())?)
+            }
+        }
+
+        #[async_trait]
+        pub trait MailSender: Send + Sync {
+            async fn send(&self, message: MailMessage) -> Result<()>;
+        }
+
+        /// Creates the sender selected by `config.transport`.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the sender address is invalid, the SMTP relay
+        /// cannot be configured, or the mail directory cannot be created.
+        pub fn mail_sender_from_config(config: &MailConfig) -> Result<Arc<dyn MailSender>> {
+            let from: Mailbox = config.from.parse()?;
+            Ok(match config.transport {
+                MailTransport::Stdout => Arc::new(StdoutMailSender::new(from)),
+                MailTransport::File => Arc::new(FileMailSender::new(&config.file_dir, from)?),
+                MailTransport::Smtp => Arc::new(SmtpMailSender::new(config, from)?),
+            })
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// MailSender that writes messages as .eml files
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use async_trait::async_trait;
+        use color_eyre::Result;
+        use lettre::message::Mailbox;
+        use lettre::{AsyncFileTransport, AsyncTransport, Tokio1Executor};
+        use crate::infrastructure::mail::mail_sender::{MailMessage, MailSender};
+
+        /// Writes every message to `<dir>/<uuid>.eml`. Useful in tests and
+        /// when no SMTP catcher is running.
+        pub struct FileMailSender {
+            transport: AsyncFileTransport<Tokio1Executor>,
+            from: Mailbox,
+        }
+
+        impl FileMailSender {
+            /// Creates `dir` if needed.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the directory cannot be created.
+            pub fn new(dir: &str, from: Mailbox) -> Result<Self> {
+                std::fs::create_dir_all(dir)?;
+                Ok(Self {
+                    transport: AsyncFileTransport::new(dir),
+                    from,
+                })
+            }
+        }
+
+        #[async_trait]
+        impl MailSender for FileMailSender {
+            async fn send(&self, message: MailMessage) -> Result<()> {
+                self.transport.send(message.to_lettre(&self.from)?).await?;
+                Ok(())
+            }
+        }
+    }
+}
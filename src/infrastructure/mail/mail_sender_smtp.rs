@@ -0,0 +1,55 @@
+// MailSender that delivers through an SMTP server
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use async_trait::async_trait;
+        use color_eyre::Result;
+        use lettre::message::Mailbox;
+        use lettre::transport::smtp::authentication::Credentials;
+        use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+        use crate::infrastructure::config::app_config::{MailConfig, SmtpTls};
+        use crate::infrastructure::mail::mail_sender::{MailMessage, MailSender};
+
+        /// Delivers through the configured SMTP server over a pooled
+        /// connection.
+        pub struct SmtpMailSender {
+            transport: AsyncSmtpTransport<Tokio1Executor>,
+            from: Mailbox,
+        }
+
+        impl SmtpMailSender {
+            /// No connection is made until the first message is sent.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if a TLS relay cannot be set up for
+            /// `config.smtp_host`.
+            pub fn new(config: &MailConfig, from: Mailbox) -> Result<Self> {
+                let host = config.smtp_host.as_str();
+                let builder = match config.smtp_tls {
+                    SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+                    SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+                    SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+                };
+                let mut builder = builder.port(config.smtp_port);
+                if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+                    builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+                }
+
+                Ok(Self {
+                    transport: builder.build(),
+                    from,
+                })
+            }
+        }
+
+        #[async_trait]
+        impl MailSender for SmtpMailSender {
+            async fn send(&self, message: MailMessage) -> Result<()> {
+                self.transport.send(message.to_lettre(&self.from)?).await?;
+                Ok(())
+            }
+        }
+    }
+}
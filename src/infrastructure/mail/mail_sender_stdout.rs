@@ -0,0 +1,39 @@
+// MailSender that writes messages to the server log
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use async_trait::async_trait;
+        use color_eyre::Result;
+        use leptos::logging;
+        use lettre::message::Mailbox;
+        use crate::infrastructure::mail::mail_sender::{MailMessage, MailSender};
+
+        /// Logs every message instead of delivering it, so links can be
+        /// copied from the console during local development.
+        pub struct StdoutMailSender {
+            from: Mailbox,
+        }
+
+        impl StdoutMailSender {
+            #[must_use]
+            pub fn new(from: Mailbox) -> Self {
+                Self { from }
+            }
+        }
+
+        #[async_trait]
+        impl MailSender for StdoutMailSender {
+            async fn send(&self, message: MailMessage) -> Result<()> {
+                logging::log!(
+                    "📧 From: {}\nTo: {}\nSubject: {}\n\n{}",
+                    self.from,
+                    message.to,
+                    message.subject,
+                    message.body
+                );
+                Ok(())
+            }
+        }
+    }
+}
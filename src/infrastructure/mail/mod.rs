@@ -0,0 +1,4 @@
+pub mod mail_sender;
+pub mod mail_sender_file;
+pub mod mail_sender_smtp;
+pub mod mail_sender_stdout;
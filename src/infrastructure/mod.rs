@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod config;
 pub mod errors;
+pub mod mail;
 pub mod persistence;
 pub mod web;
@@ -0,0 +1,63 @@
+// Shared helpers for optimistic concurrency on Cosmos DB items
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::http::headers::{Headers, ETAG, IF_MATCH};
+        use azure_core::http::policies::CustomHeaders;
+        use azure_core::http::{ClientMethodOptions, Context, StatusCode};
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{ItemOptions, PartitionKey};
+        use color_eyre::Result;
+        use serde::de::DeserializeOwned;
+
+        /// Reads an item together with its `ETag`, or `None` if it does not
+        /// exist.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the read fails for any reason other than the
+        /// item not existing, or if the response carries no `ETag`.
+        pub async fn read_item_with_etag<T: DeserializeOwned>(
+            container: &ContainerClient,
+            partition_key: PartitionKey,
+            item_id: &str,
+        ) -> Result<Option<(T, String)>> {
+            match container.read_item(partition_key, item_id, None).await {
+                Ok(response) => {
+                    let etag = response
+                        .headers()
+                        .get_optional_string(&ETAG)
+                        .ok_or_else(|| color_eyre::eyre::eyre!("Item {item_id} was returned without an ETag"))?;
+                    Ok(Some((response.into_json_body::<T>().await?, etag)))
+                }
+                Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                Err(e) => Err(color_eyre::eyre::eyre!(
+                    "Error reading item {item_id} from Cosmos DB: {e}"
+                )),
+            }
+        }
+
+        /// Options that make a write fail with `412 Precondition Failed`
+        /// unless the item still has `etag`, i.e. nobody wrote it since it
+        /// was read.
+        #[must_use]
+        pub fn if_match(etag: String) -> ItemOptions<'static> {
+            let mut headers = Headers::new();
+            headers.insert(IF_MATCH, etag);
+            ItemOptions {
+                method_options: ClientMethodOptions {
+                    context: Context::new().with_value(CustomHeaders::from(headers)),
+                },
+                ..Default::default()
+            }
+        }
+
+        /// Whether a write was rejected because its `If-Match` precondition
+        /// no longer held.
+        #[must_use]
+        pub fn is_precondition_failed(error: &azure_core::Error) -> bool {
+            error.http_status() == Some(StatusCode::PreconditionFailed)
+        }
+    }
+}
@@ -0,0 +1,352 @@
+// Shared builder for parameterized Cosmos DB SQL queries
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_data_cosmos::Query;
+        use color_eyre::Result;
+        use serde::Serialize;
+        use serde_json::Value;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Comparison {
+            Eq,
+            Gte,
+            Lte,
+        }
+
+        impl Comparison {
+            const fn as_sql(self) -> &'static str {
+                match self {
+                    Comparison::Eq => "=",
+                    Comparison::Gte => ">=",
+                    Comparison::Lte => "<=",
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Direction {
+            Ascending,
+            Descending,
+        }
+
+        impl Direction {
+            const fn as_sql(self) -> &'static str {
+                match self {
+                    Direction::Ascending => "ASC",
+                    Direction::Descending => "DESC",
+                }
+            }
+
+            /// The comparison that keeps values sorting after a given one.
+            const fn after(self) -> &'static str {
+                match self {
+                    Direction::Ascending => ">",
+                    Direction::Descending => "<",
+                }
+            }
+        }
+
+        /// A document property a query may reference. Fields can only be
+        /// one of the constants below, so the SQL text never contains a
+        /// path that was not written here.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Field(&'static str);
+
+        impl Field {
+            pub const ID: Self = Self("c.id");
+            pub const EMAIL: Self = Self("c.email");
+            pub const USER_ID: Self = Self("c.user_id");
+            pub const OWNER_ID: Self = Self("c.owner_id");
+            pub const FAMILY_ID: Self = Self("c.family_id");
+            pub const REVOKED: Self = Self("c.revoked");
+            pub const PURPOSE: Self = Self("c.purpose");
+            pub const USED_AT: Self = Self("c.used_at");
+            pub const LAST_SEEN_AT: Self = Self("c.last_seen_at");
+            pub const ACCOUNT: Self = Self("c.account");
+            pub const AMOUNT_MINOR: Self = Self("c.amount.amount_minor");
+            pub const BOOKED_ON: Self = Self("c.booked_on");
+            pub const CATEGORY: Self = Self("c.category");
+            pub const CREATED_AT: Self = Self("c.created_at");
+            pub const MEMO: Self = Self("c.memo");
+            pub const PAYEE: Self = Self("c.payee");
+
+            #[cfg(test)]
+            pub(crate) const ALL: [Self; 16] = [
+                Self::ID,
+                Self::EMAIL,
+                Self::USER_ID,
+                Self::OWNER_ID,
+                Self::FAMILY_ID,
+                Self::REVOKED,
+                Self::PURPOSE,
+                Self::USED_AT,
+                Self::LAST_SEEN_AT,
+                Self::ACCOUNT,
+                Self::AMOUNT_MINOR,
+                Self::BOOKED_ON,
+                Self::CATEGORY,
+                Self::CREATED_AT,
+                Self::MEMO,
+                Self::PAYEE,
+            ];
+
+            #[must_use]
+            pub const fn path(self) -> &'static str {
+                self.0
+            }
+        }
+
+        /// Builds `SELECT * FROM c` queries whose values are always sent as
+        /// parameters.
+        ///
+        /// Fields are [`Field`] constants, so only code can put text into the
+        /// SQL itself. Anything that comes from a request has to go through a
+        /// value argument, which is bound as a named parameter.
+        ///
+        /// ```ignore
+        /// let query = CosmosQueryBuilder::new()
+        ///     .filter(Field::EMAIL, Comparison::Eq, email.as_str())?
+        ///     .build()?;
+        /// ```
+        #[derive(Debug, Default)]
+        pub struct CosmosQueryBuilder {
+            clauses: Vec<String>,
+            parameters: Vec<(String, Value)>,
+            order_by: Vec<(Field, Direction)>,
+            limit: Option<u32>,
+        }
+
+        impl CosmosQueryBuilder {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Adds `field <comparison> @value` to the `WHERE` clause.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `value` cannot be serialized to JSON.
+            pub fn filter(
+                mut self,
+                field: Field,
+                comparison: Comparison,
+                value: impl Serialize,
+            ) -> Result<Self> {
+                let parameter = self.bind(value)?;
+                self.clauses
+                    .push(format!("{} {} {parameter}", field.path(), comparison.as_sql()));
+                Ok(self)
+            }
+
+            /// Adds a case-insensitive substring match of `value` against any of
+            /// `fields` to the `WHERE` clause.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `value` cannot be serialized to JSON.
+            pub fn contains_any(mut self, fields: &[Field], value: impl Serialize) -> Result<Self> {
+                let parameter = self.bind(value)?;
+                let matches = fields
+                    .iter()
+                    .map(|field| format!("CONTAINS({}, {parameter}, true)", field.path()))
+                    .collect::<Vec<_>>();
+                self.clauses.push(format!("({})", matches.join(" OR ")));
+                Ok(self)
+            }
+
+            /// Appends `field` to the `ORDER BY` clause; later calls break ties
+            /// left by earlier ones.
+            #[must_use]
+            pub fn order_by(mut self, field: Field, direction: Direction) -> Self {
+                self.order_by.push((field, direction));
+                self
+            }
+
+            /// Keeps only documents that sort after `(key, id)` when ordered by
+            /// `field` and then `id_field`, both in `direction`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `key` or `id` cannot be serialized to JSON.
+            pub fn after(
+                mut self,
+                field: Field,
+                key: impl Serialize,
+                id_field: Field,
+                id: impl Serialize,
+                direction: Direction,
+            ) -> Result<Self> {
+                let (field, id_field, after) = (field.path(), id_field.path(), direction.after());
+                let key = self.bind(key)?;
+                let id = self.bind(id)?;
+                self.clauses.push(format!(
+                    "({field} {after} {key} OR ({field} = {key} AND {id_field} {after} {id}))"
+                ));
+                Ok(self)
+            }
+
+            /// Returns at most `limit` documents.
+            #[must_use]
+            pub fn limit(mut self, limit: u32) -> Self {
+                self.limit = Some(limit);
+                self
+            }
+
+            /// Assembles the SQL text and attaches every bound parameter.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if a parameter cannot be attached to the query.
+            pub fn build(self) -> Result<Query> {
+                let mut sql = String::from("SELECT * FROM c");
+                if !self.clauses.is_empty() {
+                    sql.push_str(" WHERE ");
+                    sql.push_str(&self.clauses.join(" AND "));
+                }
+                if !self.order_by.is_empty() {
+                    let order_by = self
+                        .order_by
+                        .iter()
+                        .map(|(field, direction)| format!("{} {}", field.path(), direction.as_sql()))
+                        .collect::<Vec<_>>();
+                    sql.push_str(" ORDER BY ");
+                    sql.push_str(&order_by.join(", "));
+                }
+
+                if self.limit.is_some() {
+                    sql.push_str(" OFFSET 0 LIMIT @limit");
+                }
+
+                let mut query = Query::from(sql);
+                if let Some(limit) = self.limit {
+                    query = query.with_parameter("@limit", limit)?;
+                }
+                for (name, value) in self.parameters {
+                    query = query.with_parameter(name, value)?;
+                }
+                Ok(query)
+            }
+
+            fn bind(&mut self, value: impl Serialize) -> Result<String> {
+                let name = format!("@p{}", self.parameters.len());
+                self.parameters.push((name.clone(), serde_json::to_value(value)?));
+                Ok(name)
+            }
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        pub(crate) mod tests {
+            use super::*;
+
+            const INJECTION: &str = "x' OR 1=1 --";
+
+            /// The SQL text and the `(name, value)` parameters of `query`.
+            pub(crate) fn parts(query: &Query) -> (String, Vec<(String, Value)>) {
+                let serialized = serde_json::to_value(query).unwrap();
+                let text = serialized["query"].as_str().unwrap().to_string();
+                let parameters = serialized
+                    .get("parameters")
+                    .and_then(Value::as_array)
+                    .map(|parameters| {
+                        parameters
+                            .iter()
+                            .map(|parameter| {
+                                (parameter["name"].as_str().unwrap().to_string(), parameter["value"].clone())
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (text, parameters)
+            }
+
+            /// Every `c.` path in the SQL text, which must all be allow-listed.
+            pub(crate) fn paths(text: &str) -> Vec<&str> {
+                text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                    .filter(|word| word.starts_with("c."))
+                    .collect()
+            }
+
+            #[test]
+            fn allow_listed_fields_are_plain_property_paths() {
+                for field in Field::ALL {
+                    let mut segments = field.path().split('.');
+                    assert_eq!(segments.next(), Some("c"), "{field:?}");
+                    assert!(
+                        segments.all(|segment| !segment.is_empty()
+                            && segment.chars().all(|c| c.is_ascii_lowercase() || c == '_')),
+                        "{field:?}"
+                    );
+                }
+            }
+
+            #[test]
+            fn user_values_are_only_sent_as_parameters() {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::EMAIL, Comparison::Eq, INJECTION)
+                    .unwrap()
+                    .contains_any(&[Field::PAYEE, Field::MEMO], INJECTION)
+                    .unwrap()
+                    .after(Field::PAYEE, INJECTION, Field::ID, INJECTION, Direction::Ascending)
+                    .unwrap()
+                    .build()
+                    .unwrap();
+
+                let (text, parameters) = parts(&query);
+
+                assert_eq!(
+                    text,
+                    "SELECT * FROM c WHERE c.email = @p0 AND (CONTAINS(c.payee, @p1, true) OR CONTAINS(c.memo, @p1, true)) \
+                     AND (c.payee > @p2 OR (c.payee = @p2 AND c.id > @p3))"
+                );
+                assert!(!text.contains('\''));
+                let names: Vec<_> = parameters.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(names, ["@p0", "@p1", "@p2", "@p3"]);
+                assert!(parameters.iter().all(|(_, value)| value == INJECTION));
+            }
+
+            #[test]
+            fn queries_only_reference_allow_listed_fields() {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::OWNER_ID, Comparison::Eq, "c.secret")
+                    .unwrap()
+                    .filter(Field::AMOUNT_MINOR, Comparison::Gte, -100)
+                    .unwrap()
+                    .order_by(Field::CREATED_AT, Direction::Descending)
+                    .order_by(Field::ID, Direction::Descending)
+                    .limit(10)
+                    .build()
+                    .unwrap();
+
+                let (text, parameters) = parts(&query);
+
+                assert_eq!(
+                    text,
+                    "SELECT * FROM c WHERE c.owner_id = @p0 AND c.amount.amount_minor >= @p1 \
+                     ORDER BY c.created_at DESC, c.id DESC OFFSET 0 LIMIT @limit"
+                );
+                assert!(paths(&text).iter().all(|path| Field::ALL.iter().any(|field| field.path() == *path)));
+                assert!(parameters.contains(&("@limit".to_string(), Value::from(10))));
+            }
+
+            #[test]
+            fn descending_seeks_to_smaller_keys() {
+                let query = CosmosQueryBuilder::new()
+                    .after(Field::BOOKED_ON, "2025-03-01", Field::ID, "abc", Direction::Descending)
+                    .unwrap()
+                    .build()
+                    .unwrap();
+
+                let (text, _) = parts(&query);
+
+                assert_eq!(
+                    text,
+                    "SELECT * FROM c WHERE (c.booked_on < @p0 OR (c.booked_on = @p0 AND c.id < @p1))"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Cosmos DB implementation of the LoginAttemptRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey};
+        use std::sync::Arc;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use crate::domain::models::login_throttle::LoginAttempts;
+        use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+        use async_trait::async_trait;
+
+        /// Login attempts are only ever looked up by key, so they are
+        /// partitioned by `id`. Updates are read-modify-write, so concurrent
+        /// failures for the same key may occasionally be counted once.
+        pub struct CosmosDbLoginAttemptRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbLoginAttemptRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+        }
+
+        #[async_trait]
+        impl LoginAttemptRepository for CosmosDbLoginAttemptRepository {
+            async fn get_login_attempts(&self, key: &str) -> Result<Option<LoginAttempts>> {
+                match self
+                    .get_container()
+                    .read_item(PartitionKey::from(key.to_string()), key, None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<LoginAttempts>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading login attempts from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn record_failed_login(
+                &self,
+                key: &str,
+                now: DateTime<Utc>,
+                window_start: DateTime<Utc>,
+            ) -> Result<LoginAttempts> {
+                let record = match self.get_login_attempts(key).await? {
+                    Some(mut record) => {
+                        record.record_failure(now, window_start);
+                        record
+                    }
+                    None => LoginAttempts::first_failure(key.to_string(), now),
+                };
+                self.get_container()
+                    .upsert_item(PartitionKey::from(key.to_string()), record.clone(), None)
+                    .await?;
+                Ok(record)
+            }
+
+            async fn lock_login(&self, key: &str, until: DateTime<Utc>) -> Result<()> {
+                let Some(mut record) = self.get_login_attempts(key).await? else {
+                    return Ok(());
+                };
+                record.lock_until(until);
+                self.get_container()
+                    .replace_item(PartitionKey::from(key.to_string()), key, record, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn clear_login_attempts(&self, key: &str) -> Result<()> {
+                match self
+                    .get_container()
+                    .delete_item(PartitionKey::from(key.to_string()), key, None)
+                    .await
+                {
+                    Ok(_) => Ok(()),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(()),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error clearing login attempts in Cosmos DB: {e}"
+                    )),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// In-memory implementation of the LoginAttemptRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use crate::domain::models::login_throttle::LoginAttempts;
+        use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Failed sign-in attempts keyed by throttling key. Counters reset
+        /// when the process restarts.
+        #[derive(Default)]
+        pub struct InMemoryLoginAttemptRepository {
+            attempts: RwLock<HashMap<String, LoginAttempts>>,
+        }
+
+        impl InMemoryLoginAttemptRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl LoginAttemptRepository for InMemoryLoginAttemptRepository {
+            async fn get_login_attempts(&self, key: &str) -> Result<Option<LoginAttempts>> {
+                Ok(self.attempts.read().await.get(key).cloned())
+            }
+
+            async fn record_failed_login(
+                &self,
+                key: &str,
+                now: DateTime<Utc>,
+                window_start: DateTime<Utc>,
+            ) -> Result<LoginAttempts> {
+                let mut attempts = self.attempts.write().await;
+                let record = attempts
+                    .entry(key.to_string())
+                    .and_modify(|record| record.record_failure(now, window_start))
+                    .or_insert_with(|| LoginAttempts::first_failure(key.to_string(), now))
+                    .clone();
+                drop(attempts);
+                Ok(record)
+            }
+
+            async fn lock_login(&self, key: &str, until: DateTime<Utc>) -> Result<()> {
+                if let Some(record) = self.attempts.write().await.get_mut(key) {
+                    record.lock_until(until);
+                }
+                Ok(())
+            }
+
+            async fn clear_login_attempts(&self, key: &str) -> Result<()> {
+                self.attempts.write().await.remove(key);
+                Ok(())
+            }
+        }
+    }
+}
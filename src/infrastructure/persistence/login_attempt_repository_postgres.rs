@@ -0,0 +1,100 @@
+// PostgreSQL implementation of the LoginAttemptRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use sqlx::PgPool;
+        use crate::domain::models::login_throttle::LoginAttempts;
+        use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+
+        #[derive(sqlx::FromRow)]
+        struct LoginAttemptsRow {
+            id: String,
+            failed_count: i64,
+            last_failed_at: DateTime<Utc>,
+            locked_until: Option<DateTime<Utc>>,
+        }
+
+        impl From<LoginAttemptsRow> for LoginAttempts {
+            fn from(row: LoginAttemptsRow) -> Self {
+                LoginAttempts::restore(
+                    row.id,
+                    u32::try_from(row.failed_count).unwrap_or(u32::MAX),
+                    row.last_failed_at,
+                    row.locked_until,
+                )
+            }
+        }
+
+        pub struct PostgresLoginAttemptRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresLoginAttemptRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait]
+        impl LoginAttemptRepository for PostgresLoginAttemptRepository {
+            async fn get_login_attempts(&self, key: &str) -> Result<Option<LoginAttempts>> {
+                Ok(sqlx::query_as::<_, LoginAttemptsRow>(
+                    "SELECT id, failed_count, last_failed_at, locked_until
+                     FROM login_attempts WHERE id = $1",
+                )
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(LoginAttempts::from))
+            }
+
+            async fn record_failed_login(
+                &self,
+                key: &str,
+                now: DateTime<Utc>,
+                window_start: DateTime<Utc>,
+            ) -> Result<LoginAttempts> {
+                // A single upsert, so concurrent failures are all counted
+                let row = sqlx::query_as::<_, LoginAttemptsRow>(
+                    "INSERT INTO login_attempts (id, failed_count, last_failed_at, locked_until)
+                     VALUES ($1, 1, $2, NULL)
+                     ON CONFLICT (id) DO UPDATE SET
+                         failed_count = CASE
+                             WHEN login_attempts.last_failed_at < $3 THEN 1
+                             ELSE login_attempts.failed_count + 1
+                         END,
+                         last_failed_at = excluded.last_failed_at
+                     RETURNING id, failed_count, last_failed_at, locked_until",
+                )
+                .bind(key)
+                .bind(now)
+                .bind(window_start)
+                .fetch_one(&self.pool)
+                .await?;
+                Ok(LoginAttempts::from(row))
+            }
+
+            async fn lock_login(&self, key: &str, until: DateTime<Utc>) -> Result<()> {
+                sqlx::query("UPDATE login_attempts SET locked_until = $1 WHERE id = $2")
+                    .bind(until)
+                    .bind(key)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn clear_login_attempts(&self, key: &str) -> Result<()> {
+                sqlx::query("DELETE FROM login_attempts WHERE id = $1")
+                    .bind(key)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -1 +1,31 @@
+pub mod cosmos_conditional;
+pub mod cosmos_query;
+pub mod login_attempt_repository_cosmosdb;
+pub mod login_attempt_repository_in_memory;
+pub mod login_attempt_repository_postgres;
+pub mod login_attempt_repository_sqlite;
+pub mod one_time_token_repository_cosmosdb;
+pub mod one_time_token_repository_in_memory;
+pub mod one_time_token_repository_postgres;
+pub mod one_time_token_repository_sqlite;
+pub mod personal_access_token_repository_cosmosdb;
+pub mod personal_access_token_repository_in_memory;
+pub mod personal_access_token_repository_postgres;
+pub mod personal_access_token_repository_sqlite;
+pub mod refresh_token_repository_cosmosdb;
+pub mod refresh_token_repository_in_memory;
+pub mod refresh_token_repository_postgres;
+pub mod refresh_token_repository_sqlite;
+pub mod repositories;
+pub mod session_repository_cosmosdb;
+pub mod session_repository_in_memory;
+pub mod session_repository_postgres;
+pub mod session_repository_sqlite;
+pub mod transaction_repository_cosmosdb;
+pub mod transaction_repository_in_memory;
+pub mod transaction_repository_postgres;
+pub mod transaction_repository_sqlite;
 pub mod user_repository_cosmosdb;
+pub mod user_repository_in_memory;
+pub mod user_repository_postgres;
+pub mod user_repository_sqlite;
@@ -0,0 +1,164 @@
+// Cosmos DB implementation of the OneTimeTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey};
+        use std::sync::Arc;
+        use color_eyre::Result;
+        use crate::domain::models::one_time_token::{OneTimeToken, TokenPurpose};
+        use crate::domain::repositories::one_time_token_repository::OneTimeTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use crate::infrastructure::persistence::cosmos_conditional::{
+            if_match, is_precondition_failed, read_item_with_etag,
+        };
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Field};
+        use async_trait::async_trait;
+        use futures_util::stream::TryStreamExt;
+
+        /// One-time tokens are only ever looked up by id, so they are
+        /// partitioned by `id`.
+        pub struct CosmosDbOneTimeTokenRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbOneTimeTokenRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+        }
+
+        #[async_trait]
+        impl OneTimeTokenRepository for CosmosDbOneTimeTokenRepository {
+            async fn store_one_time_token(&self, token: OneTimeToken) -> Result<()> {
+                let partition_key = PartitionKey::from(token.id().to_string());
+                self.get_container()
+                    .create_item(partition_key, token, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_one_time_token(&self, token_id: &str) -> Result<Option<OneTimeToken>> {
+                match self
+                    .get_container()
+                    .read_item(PartitionKey::from(token_id.to_string()), token_id, None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<OneTimeToken>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading one-time token from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn consume_one_time_token(
+                &self,
+                token_id: &str,
+                purpose: TokenPurpose,
+            ) -> Result<Option<OneTimeToken>> {
+                let container = self.get_container();
+                let partition_key = PartitionKey::from(token_id.to_string());
+                let Some((mut token, etag)) = read_item_with_etag::<OneTimeToken>(
+                    &container,
+                    partition_key.clone(),
+                    token_id,
+                )
+                .await?
+                else {
+                    return Ok(None);
+                };
+                if token.purpose() != purpose || !token.is_usable() {
+                    return Ok(None);
+                }
+
+                // A concurrent consume changes the ETag, so only one of them
+                // can replace the unused token.
+                token.mark_used();
+                match container
+                    .replace_item(partition_key, token_id, token.clone(), Some(if_match(etag)))
+                    .await
+                {
+                    Ok(_) => Ok(Some(token)),
+                    Err(e) if is_precondition_failed(&e) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error consuming one-time token in Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn revoke_one_time_tokens(&self, user_id: &UserId, purpose: TokenPurpose) -> Result<()> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .filter(Field::PURPOSE, Comparison::Eq, purpose)?
+                    .filter(Field::USED_AT, Comparison::Eq, serde_json::Value::Null)?
+                    .build()?;
+                // Tokens are partitioned by id, so this fans out across partitions.
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<OneTimeToken>(query, (), None)?;
+
+                let mut tokens = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    tokens.extend(feed_page.into_items());
+                }
+
+                for mut token in tokens {
+                    token.mark_used();
+                    let id = token.id().to_string();
+                    container
+                        .replace_item(PartitionKey::from(id.clone()), &id, token, None)
+                        .await?;
+                }
+
+                Ok(())
+            }
+
+            async fn delete_all_one_time_tokens(&self, user_id: &UserId) -> Result<()> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .build()?;
+                // Tokens are partitioned by id, so this fans out across partitions.
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<OneTimeToken>(query, (), None)?;
+
+                let mut ids = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    ids.extend(feed_page.into_items().into_iter().map(|token| token.id().to_string()));
+                }
+
+                for id in &ids {
+                    container
+                        .delete_item(PartitionKey::from(id.clone()), id, None)
+                        .await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// In-memory implementation of the OneTimeTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use color_eyre::Result;
+        use crate::domain::models::one_time_token::{OneTimeToken, TokenPurpose};
+        use crate::domain::repositories::one_time_token_repository::OneTimeTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// One-time tokens keyed by token id.
+        #[derive(Default)]
+        pub struct InMemoryOneTimeTokenRepository {
+            tokens: RwLock<HashMap<String, OneTimeToken>>,
+        }
+
+        impl InMemoryOneTimeTokenRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl OneTimeTokenRepository for InMemoryOneTimeTokenRepository {
+            async fn store_one_time_token(&self, token: OneTimeToken) -> Result<()> {
+                self.tokens.write().await.insert(token.id().to_string(), token);
+                Ok(())
+            }
+
+            async fn get_one_time_token(&self, token_id: &str) -> Result<Option<OneTimeToken>> {
+                Ok(self.tokens.read().await.get(token_id).cloned())
+            }
+
+            async fn consume_one_time_token(
+                &self,
+                token_id: &str,
+                purpose: TokenPurpose,
+            ) -> Result<Option<OneTimeToken>> {
+                let mut tokens = self.tokens.write().await;
+                match tokens.get_mut(token_id) {
+                    Some(token) if token.purpose() == purpose && token.is_usable() => {
+                        token.mark_used();
+                        Ok(Some(token.clone()))
+                    }
+                    _ => Ok(None),
+                }
+            }
+
+            async fn revoke_one_time_tokens(&self, user_id: &UserId, purpose: TokenPurpose) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .values_mut()
+                    .filter(|token| token.user_id() == user_id && token.purpose() == purpose)
+                    .for_each(OneTimeToken::mark_used);
+                Ok(())
+            }
+
+            async fn delete_all_one_time_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .retain(|_, token| token.user_id() != user_id);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// PostgreSQL implementation of the OneTimeTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::PgPool;
+        use crate::domain::models::one_time_token::{OneTimeToken, TokenPurpose};
+        use crate::domain::repositories::one_time_token_repository::OneTimeTokenRepository;
+        use crate::domain::value_objects::UserId;
+
+        #[derive(sqlx::FromRow)]
+        struct OneTimeTokenRow {
+            id: String,
+            user_id: String,
+            purpose: String,
+            created_at: DateTime<Utc>,
+            expires_at: DateTime<Utc>,
+            used_at: Option<DateTime<Utc>>,
+        }
+
+        impl TryFrom<OneTimeTokenRow> for OneTimeToken {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: OneTimeTokenRow) -> Result<Self> {
+                let user_id = UserId::new(row.user_id)
+                    .map_err(|e| eyre!("Invalid one-time token row {}: {e}", row.id))?;
+                Ok(OneTimeToken::restore(
+                    row.id,
+                    user_id,
+                    row.purpose.parse()?,
+                    row.created_at,
+                    row.expires_at,
+                    row.used_at,
+                ))
+            }
+        }
+
+        pub struct PostgresOneTimeTokenRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresOneTimeTokenRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait]
+        impl OneTimeTokenRepository for PostgresOneTimeTokenRepository {
+            async fn store_one_time_token(&self, token: OneTimeToken) -> Result<()> {
+                sqlx::query(
+                    "INSERT INTO one_time_tokens (id, user_id, purpose, created_at, expires_at, used_at)
+                     VALUES ($1, $2, $3, $4, $5, $6)",
+                )
+                .bind(token.id())
+                .bind(token.user_id().as_str())
+                .bind(token.purpose().as_str())
+                .bind(token.created_at())
+                .bind(token.expires_at())
+                .bind(token.used_at())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn get_one_time_token(&self, token_id: &str) -> Result<Option<OneTimeToken>> {
+                sqlx::query_as::<_, OneTimeTokenRow>(
+                    "SELECT id, user_id, purpose, created_at, expires_at, used_at
+                     FROM one_time_tokens WHERE id = $1",
+                )
+                .bind(token_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(OneTimeToken::try_from)
+                .transpose()
+            }
+
+            async fn consume_one_time_token(
+                &self,
+                token_id: &str,
+                purpose: TokenPurpose,
+            ) -> Result<Option<OneTimeToken>> {
+                let now = Utc::now();
+                // Checking and marking in one statement leaves a single
+                // winner when the same token is submitted concurrently.
+                sqlx::query_as::<_, OneTimeTokenRow>(
+                    "UPDATE one_time_tokens SET used_at = $1
+                     WHERE id = $2 AND purpose = $3 AND used_at IS NULL AND expires_at > $1
+                     RETURNING id, user_id, purpose, created_at, expires_at, used_at",
+                )
+                .bind(now)
+                .bind(token_id)
+                .bind(purpose.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(OneTimeToken::try_from)
+                .transpose()
+            }
+
+            async fn revoke_one_time_tokens(&self, user_id: &UserId, purpose: TokenPurpose) -> Result<()> {
+                sqlx::query(
+                    "UPDATE one_time_tokens SET used_at = $1
+                     WHERE user_id = $2 AND purpose = $3 AND used_at IS NULL",
+                )
+                .bind(Utc::now())
+                .bind(user_id.as_str())
+                .bind(purpose.as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn delete_all_one_time_tokens(&self, user_id: &UserId) -> Result<()> {
+                sqlx::query("DELETE FROM one_time_tokens WHERE user_id = $1")
+                    .bind(user_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+// Cosmos DB implementation of the PersonalAccessTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey};
+        use chrono::{DateTime, Utc};
+        use std::sync::Arc;
+        use color_eyre::Result;
+        use crate::domain::models::personal_access_token::PersonalAccessToken;
+        use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Field};
+        use async_trait::async_trait;
+        use futures_util::stream::TryStreamExt;
+
+        /// Tokens are looked up by id on every API request they authenticate,
+        /// so they are partitioned by `id`. Listing a user's tokens is the
+        /// rare cross-partition query.
+        pub struct CosmosDbPersonalAccessTokenRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbPersonalAccessTokenRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+
+            async fn delete_by_id(&self, token_id: &str) -> Result<bool> {
+                match self
+                    .get_container()
+                    .delete_item(PartitionKey::from(token_id.to_string()), token_id, None)
+                    .await
+                {
+                    Ok(_) => Ok(true),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(false),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error deleting personal access token from Cosmos DB: {e}"
+                    )),
+                }
+            }
+        }
+
+        #[async_trait]
+        impl PersonalAccessTokenRepository for CosmosDbPersonalAccessTokenRepository {
+            async fn create_access_token(&self, token: PersonalAccessToken) -> Result<()> {
+                let partition_key = PartitionKey::from(token.id().to_string());
+                self.get_container()
+                    .create_item(partition_key, token, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_access_token(&self, token_id: &str) -> Result<Option<PersonalAccessToken>> {
+                match self
+                    .get_container()
+                    .read_item(PartitionKey::from(token_id.to_string()), token_id, None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<PersonalAccessToken>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading personal access token from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<PersonalAccessToken>> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .build()?;
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<PersonalAccessToken>(query, (), None)?;
+
+                let mut tokens = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    tokens.extend(feed_page.into_items());
+                }
+                // Sorted here: ORDER BY across partitions needs a query plan.
+                tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at()));
+                Ok(tokens)
+            }
+
+            async fn delete_access_token(&self, user_id: &UserId, token_id: &str) -> Result<bool> {
+                // Partitioned by id, so ownership is checked before deleting.
+                match self.get_access_token(token_id).await? {
+                    Some(token) if token.user_id() == user_id => self.delete_by_id(token_id).await,
+                    _ => Ok(false),
+                }
+            }
+
+            async fn delete_all_access_tokens(&self, user_id: &UserId) -> Result<()> {
+                for token in self.list_access_tokens(user_id).await? {
+                    self.delete_by_id(token.id()).await?;
+                }
+                Ok(())
+            }
+
+            async fn record_access_token_use(&self, token_id: &str, used_at: DateTime<Utc>) -> Result<()> {
+                let Some(mut token) = self.get_access_token(token_id).await? else {
+                    return Ok(());
+                };
+                token.record_use(used_at);
+                self.get_container()
+                    .replace_item(PartitionKey::from(token_id.to_string()), token_id, token, None)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// In-memory implementation of the PersonalAccessTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use crate::domain::models::personal_access_token::PersonalAccessToken;
+        use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Personal access tokens keyed by token id.
+        #[derive(Default)]
+        pub struct InMemoryPersonalAccessTokenRepository {
+            tokens: RwLock<HashMap<String, PersonalAccessToken>>,
+        }
+
+        impl InMemoryPersonalAccessTokenRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl PersonalAccessTokenRepository for InMemoryPersonalAccessTokenRepository {
+            async fn create_access_token(&self, token: PersonalAccessToken) -> Result<()> {
+                self.tokens.write().await.insert(token.id().to_string(), token);
+                Ok(())
+            }
+
+            async fn get_access_token(&self, token_id: &str) -> Result<Option<PersonalAccessToken>> {
+                Ok(self.tokens.read().await.get(token_id).cloned())
+            }
+
+            async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<PersonalAccessToken>> {
+                let mut tokens: Vec<PersonalAccessToken> = self
+                    .tokens
+                    .read()
+                    .await
+                    .values()
+                    .filter(|token| token.user_id() == user_id)
+                    .cloned()
+                    .collect();
+                tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at()));
+                Ok(tokens)
+            }
+
+            async fn delete_access_token(&self, user_id: &UserId, token_id: &str) -> Result<bool> {
+                let mut tokens = self.tokens.write().await;
+                let owned = tokens.get(token_id).is_some_and(|token| token.user_id() == user_id);
+                if owned {
+                    tokens.remove(token_id);
+                }
+                drop(tokens);
+                Ok(owned)
+            }
+
+            async fn delete_all_access_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .retain(|_, token| token.user_id() != user_id);
+                Ok(())
+            }
+
+            async fn record_access_token_use(&self, token_id: &str, used_at: DateTime<Utc>) -> Result<()> {
+                if let Some(token) = self.tokens.write().await.get_mut(token_id) {
+                    token.record_use(used_at);
+                }
+                Ok(())
+            }
+        }
+    }
+}
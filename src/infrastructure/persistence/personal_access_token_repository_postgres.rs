@@ -0,0 +1,133 @@
+// PostgreSQL implementation of the PersonalAccessTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::PgPool;
+        use crate::domain::models::personal_access_token::PersonalAccessToken;
+        use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+        use crate::domain::value_objects::UserId;
+
+        #[derive(sqlx::FromRow)]
+        struct PersonalAccessTokenRow {
+            id: String,
+            user_id: String,
+            name: String,
+            token_hash: String,
+            scopes: String,
+            created_at: DateTime<Utc>,
+            expires_at: Option<DateTime<Utc>>,
+            last_used_at: Option<DateTime<Utc>>,
+        }
+
+        impl TryFrom<PersonalAccessTokenRow> for PersonalAccessToken {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: PersonalAccessTokenRow) -> Result<Self> {
+                let user_id = UserId::new(row.user_id)
+                    .map_err(|e| eyre!("Invalid personal access token row {}: {e}", row.id))?;
+                Ok(PersonalAccessToken::restore(
+                    row.id,
+                    user_id,
+                    row.name,
+                    row.token_hash,
+                    PersonalAccessToken::parse_scopes(&row.scopes)?,
+                    row.created_at,
+                    row.expires_at,
+                    row.last_used_at,
+                ))
+            }
+        }
+
+        pub struct PostgresPersonalAccessTokenRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresPersonalAccessTokenRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait]
+        impl PersonalAccessTokenRepository for PostgresPersonalAccessTokenRepository {
+            async fn create_access_token(&self, token: PersonalAccessToken) -> Result<()> {
+                sqlx::query(
+                    "INSERT INTO personal_access_tokens
+                         (id, user_id, name, token_hash, scopes, created_at, expires_at, last_used_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                )
+                .bind(token.id())
+                .bind(token.user_id().as_str())
+                .bind(token.name())
+                .bind(token.token_hash())
+                .bind(PersonalAccessToken::scopes_to_string(token.scopes()))
+                .bind(token.created_at())
+                .bind(token.expires_at())
+                .bind(token.last_used_at())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn get_access_token(&self, token_id: &str) -> Result<Option<PersonalAccessToken>> {
+                sqlx::query_as::<_, PersonalAccessTokenRow>(
+                    "SELECT id, user_id, name, token_hash, scopes, created_at, expires_at, last_used_at
+                     FROM personal_access_tokens WHERE id = $1",
+                )
+                .bind(token_id)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(PersonalAccessToken::try_from)
+                .transpose()
+            }
+
+            async fn list_access_tokens(&self, user_id: &UserId) -> Result<Vec<PersonalAccessToken>> {
+                sqlx::query_as::<_, PersonalAccessTokenRow>(
+                    "SELECT id, user_id, name, token_hash, scopes, created_at, expires_at, last_used_at
+                     FROM personal_access_tokens WHERE user_id = $1
+                     ORDER BY created_at DESC",
+                )
+                .bind(user_id.as_str())
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(PersonalAccessToken::try_from)
+                .collect()
+            }
+
+            async fn delete_access_token(&self, user_id: &UserId, token_id: &str) -> Result<bool> {
+                let result = sqlx::query(
+                    "DELETE FROM personal_access_tokens WHERE id = $1 AND user_id = $2",
+                )
+                .bind(token_id)
+                .bind(user_id.as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete_all_access_tokens(&self, user_id: &UserId) -> Result<()> {
+                sqlx::query("DELETE FROM personal_access_tokens WHERE user_id = $1")
+                    .bind(user_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn record_access_token_use(&self, token_id: &str, used_at: DateTime<Utc>) -> Result<()> {
+                sqlx::query("UPDATE personal_access_tokens SET last_used_at = $1 WHERE id = $2")
+                    .bind(used_at)
+                    .bind(token_id)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
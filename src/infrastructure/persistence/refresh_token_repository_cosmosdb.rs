@@ -0,0 +1,188 @@
+// Cosmos DB implementation of the RefreshTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey, Query};
+        use std::sync::Arc;
+        use color_eyre::Result;
+        use crate::domain::models::refresh_token::RefreshTokenRecord;
+        use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use crate::infrastructure::persistence::cosmos_conditional::{
+            if_match, is_precondition_failed, read_item_with_etag,
+        };
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Field};
+        use async_trait::async_trait;
+        use futures_util::stream::TryStreamExt;
+
+        /// Refresh tokens are partitioned by `user_id`.
+        pub struct CosmosDbRefreshTokenRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbRefreshTokenRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+
+            /// Revokes every record in the user's partition matched by `query`.
+            async fn revoke_matching(&self, user_id: &UserId, query: Query) -> Result<()> {
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<RefreshTokenRecord>(
+                    query,
+                    PartitionKey::from(user_id.to_string()),
+                    None,
+                )?;
+
+                let mut records = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    records.extend(feed_page.into_items());
+                }
+
+                for mut record in records {
+                    record.revoke();
+                    let id = record.id().to_string();
+                    container
+                        .replace_item(PartitionKey::from(user_id.to_string()), &id, record, None)
+                        .await?;
+                }
+
+                Ok(())
+            }
+        }
+
+        #[async_trait]
+        impl RefreshTokenRepository for CosmosDbRefreshTokenRepository {
+            async fn store_refresh_token(&self, record: RefreshTokenRecord) -> Result<()> {
+                let partition_key = PartitionKey::from(record.user_id().to_string());
+                self.get_container()
+                    .upsert_item(partition_key, record, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_refresh_token(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+            ) -> Result<Option<RefreshTokenRecord>> {
+                let partition_key = PartitionKey::from(user_id.to_string());
+                match self
+                    .get_container()
+                    .read_item(partition_key, token_id, None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<RefreshTokenRecord>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading refresh token from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn mark_refresh_token_used(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+                replaced_by: &str,
+            ) -> Result<bool> {
+                let container = self.get_container();
+                let partition_key = PartitionKey::from(user_id.to_string());
+                let Some((mut record, etag)) = read_item_with_etag::<RefreshTokenRecord>(
+                    &container,
+                    partition_key.clone(),
+                    token_id,
+                )
+                .await?
+                else {
+                    return Ok(false);
+                };
+                if record.is_used() {
+                    return Ok(false);
+                }
+
+                // The replace only succeeds if nobody rotated the token since
+                // it was read, so two concurrent refreshes cannot both win.
+                record.mark_used(replaced_by);
+                match container
+                    .replace_item(partition_key, token_id, record, Some(if_match(etag)))
+                    .await
+                {
+                    Ok(_) => Ok(true),
+                    Err(e) if is_precondition_failed(&e) => Ok(false),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error marking refresh token {token_id} as used in Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn revoke_refresh_token_family(&self, user_id: &UserId, family_id: &str) -> Result<()> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .filter(Field::FAMILY_ID, Comparison::Eq, family_id)?
+                    .filter(Field::REVOKED, Comparison::Eq, false)?
+                    .build()?;
+                self.revoke_matching(user_id, query).await
+            }
+
+            async fn revoke_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .filter(Field::REVOKED, Comparison::Eq, false)?
+                    .build()?;
+                self.revoke_matching(user_id, query).await
+            }
+
+            async fn delete_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .build()?;
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<RefreshTokenRecord>(
+                    query,
+                    PartitionKey::from(user_id.to_string()),
+                    None,
+                )?;
+
+                let mut ids = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    ids.extend(feed_page.into_items().into_iter().map(|record| record.id().to_string()));
+                }
+
+                for id in &ids {
+                    container
+                        .delete_item(PartitionKey::from(user_id.to_string()), id, None)
+                        .await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// In-memory implementation of the RefreshTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use color_eyre::Result;
+        use crate::domain::models::refresh_token::RefreshTokenRecord;
+        use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+        use crate::domain::value_objects::UserId;
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Refresh tokens keyed by `(user_id, token_id)`.
+        #[derive(Default)]
+        pub struct InMemoryRefreshTokenRepository {
+            tokens: RwLock<HashMap<(String, String), RefreshTokenRecord>>,
+        }
+
+        impl InMemoryRefreshTokenRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl RefreshTokenRepository for InMemoryRefreshTokenRepository {
+            async fn store_refresh_token(&self, record: RefreshTokenRecord) -> Result<()> {
+                let key = (record.user_id().to_string(), record.id().to_string());
+                self.tokens.write().await.insert(key, record);
+                Ok(())
+            }
+
+            async fn get_refresh_token(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+            ) -> Result<Option<RefreshTokenRecord>> {
+                let key = (user_id.to_string(), token_id.to_string());
+                Ok(self.tokens.read().await.get(&key).cloned())
+            }
+
+            async fn mark_refresh_token_used(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+                replaced_by: &str,
+            ) -> Result<bool> {
+                let key = (user_id.to_string(), token_id.to_string());
+                let mut tokens = self.tokens.write().await;
+                match tokens.get_mut(&key) {
+                    Some(record) if !record.is_used() => {
+                        record.mark_used(replaced_by);
+                        Ok(true)
+                    }
+                    _ => Ok(false),
+                }
+            }
+
+            async fn revoke_refresh_token_family(&self, user_id: &UserId, family_id: &str) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .values_mut()
+                    .filter(|record| record.user_id() == user_id && record.family_id() == family_id)
+                    .for_each(RefreshTokenRecord::revoke);
+                Ok(())
+            }
+
+            async fn delete_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .retain(|_, record| record.user_id() != user_id);
+                Ok(())
+            }
+
+            async fn revoke_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                self.tokens
+                    .write()
+                    .await
+                    .values_mut()
+                    .filter(|record| record.user_id() == user_id)
+                    .for_each(RefreshTokenRecord::revoke);
+                Ok(())
+            }
+        }
+    }
+}
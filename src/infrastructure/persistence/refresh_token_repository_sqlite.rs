@@ -0,0 +1,149 @@
+// SQLite implementation of the RefreshTokenRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "sqlite")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::SqlitePool;
+        use crate::domain::models::refresh_token::RefreshTokenRecord;
+        use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+        use crate::domain::value_objects::UserId;
+
+        #[derive(sqlx::FromRow)]
+        struct RefreshTokenRow {
+            id: String,
+            family_id: String,
+            user_id: String,
+            issued_at: DateTime<Utc>,
+            expires_at: DateTime<Utc>,
+            revoked_at: Option<DateTime<Utc>>,
+            used_at: Option<DateTime<Utc>>,
+            replaced_by: Option<String>,
+        }
+
+        impl TryFrom<RefreshTokenRow> for RefreshTokenRecord {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: RefreshTokenRow) -> Result<Self> {
+                let user_id = UserId::new(row.user_id)
+                    .map_err(|e| eyre!("Invalid refresh token row {}: {e}", row.id))?;
+                Ok(RefreshTokenRecord::restore(
+                    row.id,
+                    row.family_id,
+                    user_id,
+                    row.issued_at,
+                    row.expires_at,
+                    row.revoked_at,
+                    row.used_at,
+                    row.replaced_by,
+                ))
+            }
+        }
+
+        pub struct SqliteRefreshTokenRepository {
+            pool: SqlitePool,
+        }
+
+        impl SqliteRefreshTokenRepository {
+            #[must_use]
+            pub fn new(pool: SqlitePool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait]
+        impl RefreshTokenRepository for SqliteRefreshTokenRepository {
+            async fn store_refresh_token(&self, record: RefreshTokenRecord) -> Result<()> {
+                sqlx::query(
+                    "INSERT OR REPLACE INTO refresh_tokens
+                        (id, family_id, user_id, issued_at, expires_at, revoked_at, used_at, replaced_by)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(record.id())
+                .bind(record.family_id())
+                .bind(record.user_id().as_str())
+                .bind(record.issued_at())
+                .bind(record.expires_at())
+                .bind(record.revoked_at())
+                .bind(record.used_at())
+                .bind(record.replaced_by())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn get_refresh_token(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+            ) -> Result<Option<RefreshTokenRecord>> {
+                sqlx::query_as::<_, RefreshTokenRow>(
+                    "SELECT id, family_id, user_id, issued_at, expires_at, revoked_at, used_at, replaced_by
+                     FROM refresh_tokens WHERE id = ? AND user_id = ?",
+                )
+                .bind(token_id)
+                .bind(user_id.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(RefreshTokenRecord::try_from)
+                .transpose()
+            }
+
+            async fn mark_refresh_token_used(
+                &self,
+                user_id: &UserId,
+                token_id: &str,
+                replaced_by: &str,
+            ) -> Result<bool> {
+                // The `used_at IS NULL` guard makes concurrent exchanges of
+                // the same token race for a single winner.
+                let result = sqlx::query(
+                    "UPDATE refresh_tokens SET used_at = ?, replaced_by = ?
+                     WHERE id = ? AND user_id = ? AND used_at IS NULL",
+                )
+                .bind(Utc::now())
+                .bind(replaced_by)
+                .bind(token_id)
+                .bind(user_id.as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn revoke_refresh_token_family(&self, user_id: &UserId, family_id: &str) -> Result<()> {
+                sqlx::query(
+                    "UPDATE refresh_tokens SET revoked_at = ?
+                     WHERE user_id = ? AND family_id = ? AND revoked_at IS NULL",
+                )
+                .bind(Utc::now())
+                .bind(user_id.as_str())
+                .bind(family_id)
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn revoke_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                sqlx::query(
+                    "UPDATE refresh_tokens SET revoked_at = ? WHERE user_id = ? AND revoked_at IS NULL",
+                )
+                .bind(Utc::now())
+                .bind(user_id.as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn delete_all_refresh_tokens(&self, user_id: &UserId) -> Result<()> {
+                sqlx::query("DELETE FROM refresh_tokens WHERE user_id = ?")
+                    .bind(user_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
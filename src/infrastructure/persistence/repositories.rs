@@ -0,0 +1,434 @@
+// Construction of the repositories for the configured storage backend
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::sync::Arc;
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::CosmosClient;
+        use azure_data_cosmos::models::{
+            CompositeIndex, CompositeIndexOrder, CompositeIndexProperty, ContainerProperties,
+            IndexingMode, IndexingPolicy, PropertyPath, UniqueKey, UniqueKeyPolicy,
+        };
+        use color_eyre::Result;
+        use leptos::logging;
+        use color_eyre::eyre::eyre;
+        use crate::domain::repositories::login_attempt_repository::LoginAttemptRepository;
+        use crate::domain::repositories::one_time_token_repository::OneTimeTokenRepository;
+        use crate::domain::repositories::personal_access_token_repository::PersonalAccessTokenRepository;
+        use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+        use crate::domain::repositories::session_repository::SessionRepository;
+        use crate::domain::repositories::transaction_repository::TransactionRepository;
+        use crate::domain::repositories::user_repository::UserRepository;
+        use crate::infrastructure::config::app_config::{AppConfig, LoginThrottleStore, StorageBackend};
+        use crate::infrastructure::persistence::login_attempt_repository_cosmosdb::CosmosDbLoginAttemptRepository;
+        use crate::infrastructure::persistence::login_attempt_repository_in_memory::InMemoryLoginAttemptRepository;
+        use crate::infrastructure::persistence::one_time_token_repository_cosmosdb::CosmosDbOneTimeTokenRepository;
+        use crate::infrastructure::persistence::one_time_token_repository_in_memory::InMemoryOneTimeTokenRepository;
+        use crate::infrastructure::persistence::personal_access_token_repository_cosmosdb::CosmosDbPersonalAccessTokenRepository;
+        use crate::infrastructure::persistence::personal_access_token_repository_in_memory::InMemoryPersonalAccessTokenRepository;
+        use crate::infrastructure::persistence::refresh_token_repository_cosmosdb::CosmosDbRefreshTokenRepository;
+        use crate::infrastructure::persistence::refresh_token_repository_in_memory::InMemoryRefreshTokenRepository;
+        use crate::infrastructure::persistence::session_repository_cosmosdb::CosmosDbSessionRepository;
+        use crate::infrastructure::persistence::session_repository_in_memory::InMemorySessionRepository;
+        use crate::infrastructure::persistence::transaction_repository_cosmosdb::CosmosDbTransactionRepository;
+        use crate::infrastructure::persistence::transaction_repository_in_memory::InMemoryTransactionRepository;
+        use crate::infrastructure::persistence::user_repository_cosmosdb::CosmosDbUserRepository;
+        use crate::infrastructure::persistence::user_repository_in_memory::InMemoryUserRepository;
+        #[cfg(feature = "sqlite")]
+        use crate::infrastructure::persistence::{
+            login_attempt_repository_sqlite::SqliteLoginAttemptRepository,
+            one_time_token_repository_sqlite::SqliteOneTimeTokenRepository,
+            personal_access_token_repository_sqlite::SqlitePersonalAccessTokenRepository,
+            refresh_token_repository_sqlite::SqliteRefreshTokenRepository,
+            session_repository_sqlite::SqliteSessionRepository,
+            transaction_repository_sqlite::SqliteTransactionRepository,
+            user_repository_sqlite::SqliteUserRepository,
+        };
+        #[cfg(feature = "postgres")]
+        use crate::infrastructure::persistence::{
+            login_attempt_repository_postgres::PostgresLoginAttemptRepository,
+            one_time_token_repository_postgres::PostgresOneTimeTokenRepository,
+            personal_access_token_repository_postgres::PostgresPersonalAccessTokenRepository,
+            refresh_token_repository_postgres::PostgresRefreshTokenRepository,
+            session_repository_postgres::PostgresSessionRepository,
+            transaction_repository_postgres::PostgresTransactionRepository,
+            user_repository_postgres::PostgresUserRepository,
+        };
+
+        /// Every repository the application needs, backed by a single store.
+        #[derive(Clone)]
+        pub struct Repositories {
+            pub users: Arc<dyn UserRepository>,
+            pub refresh_tokens: Arc<dyn RefreshTokenRepository>,
+            pub sessions: Arc<dyn SessionRepository>,
+            pub one_time_tokens: Arc<dyn OneTimeTokenRepository>,
+            pub login_attempts: Arc<dyn LoginAttemptRepository>,
+            pub access_tokens: Arc<dyn PersonalAccessTokenRepository>,
+            pub transactions: Arc<dyn TransactionRepository>,
+        }
+
+        impl Repositories {
+            /// Creates the repositories for `config.storage.backend`, running
+            /// pending schema migrations for SQL backends. Failed sign-in
+            /// attempts stay in memory unless `config.auth.login_throttle_store`
+            /// asks for the database.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the selected backend is missing configuration,
+            /// was not compiled in, or cannot be connected to or migrated.
+            pub async fn from_config(config: &AppConfig) -> Result<Self> {
+                let mut repositories = match config.storage.backend {
+                    StorageBackend::CosmosDb => Self::cosmos_db(config).await,
+                    StorageBackend::InMemory => Ok(Self::in_memory()),
+                    #[cfg(feature = "sqlite")]
+                    StorageBackend::Sqlite => Self::sqlite(config).await,
+                    #[cfg(not(feature = "sqlite"))]
+                    StorageBackend::Sqlite => Err(eyre!(
+                        "The sqlite storage backend requires building with the `sqlite` feature"
+                    )),
+                    #[cfg(feature = "postgres")]
+                    StorageBackend::Postgres => Self::postgres(config).await,
+                    #[cfg(not(feature = "postgres"))]
+                    StorageBackend::Postgres => Err(eyre!(
+                        "The postgres storage backend requires building with the `postgres` feature"
+                    )),
+                }?;
+
+                if config.auth.login_throttle_store == LoginThrottleStore::Memory {
+                    repositories.login_attempts = Arc::new(InMemoryLoginAttemptRepository::new());
+                }
+                Ok(repositories)
+            }
+
+            #[must_use]
+            pub fn in_memory() -> Self {
+                Self {
+                    users: Arc::new(InMemoryUserRepository::new()),
+                    refresh_tokens: Arc::new(InMemoryRefreshTokenRepository::new()),
+                    sessions: Arc::new(InMemorySessionRepository::new()),
+                    one_time_tokens: Arc::new(InMemoryOneTimeTokenRepository::new()),
+                    login_attempts: Arc::new(InMemoryLoginAttemptRepository::new()),
+                    access_tokens: Arc::new(InMemoryPersonalAccessTokenRepository::new()),
+                    transactions: Arc::new(InMemoryTransactionRepository::new()),
+                }
+            }
+
+            #[cfg(feature = "sqlite")]
+            async fn sqlite(config: &AppConfig) -> Result<Self> {
+                use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+                use std::str::FromStr;
+
+                let options = SqliteConnectOptions::from_str(&config.storage.database_url)?
+                    .create_if_missing(true)
+                    .foreign_keys(true);
+                let pool = SqlitePoolOptions::new()
+                    .max_connections(config.storage.max_connections)
+                    .connect_with(options)
+                    .await?;
+
+                Self::sqlite_pool(pool).await
+            }
+
+            /// Migrates `pool` and builds the `SQLite` repositories on it.
+            #[cfg(feature = "sqlite")]
+            async fn sqlite_pool(pool: sqlx::SqlitePool) -> Result<Self> {
+                sqlx::migrate!("./migrations/sqlite").run(&pool).await?;
+
+                Ok(Self {
+                    users: Arc::new(SqliteUserRepository::new(pool.clone())),
+                    refresh_tokens: Arc::new(SqliteRefreshTokenRepository::new(pool.clone())),
+                    sessions: Arc::new(SqliteSessionRepository::new(pool.clone())),
+                    one_time_tokens: Arc::new(SqliteOneTimeTokenRepository::new(pool.clone())),
+                    login_attempts: Arc::new(SqliteLoginAttemptRepository::new(pool.clone())),
+                    access_tokens: Arc::new(SqlitePersonalAccessTokenRepository::new(pool.clone())),
+                    transactions: Arc::new(SqliteTransactionRepository::new(pool)),
+                })
+            }
+
+            /// `SQLite` repositories over a fresh, migrated `sqlite::memory:`
+            /// database. Each connection would see its own empty database,
+            /// so the pool holds exactly one and never recycles it.
+            #[cfg(all(test, feature = "sqlite"))]
+            #[allow(clippy::missing_panics_doc, clippy::unwrap_used)]
+            pub(crate) async fn sqlite_in_memory() -> Self {
+                use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+                use std::str::FromStr;
+
+                let options = SqliteConnectOptions::from_str("sqlite::memory:")
+                    .unwrap()
+                    .foreign_keys(true);
+                let pool = SqlitePoolOptions::new()
+                    .max_connections(1)
+                    .idle_timeout(None)
+                    .max_lifetime(None)
+                    .connect_with(options)
+                    .await
+                    .unwrap();
+                Self::sqlite_pool(pool).await.unwrap()
+            }
+
+            #[cfg(feature = "postgres")]
+            async fn postgres(config: &AppConfig) -> Result<Self> {
+                use sqlx::postgres::PgPoolOptions;
+
+                let pool = PgPoolOptions::new()
+                    .max_connections(config.storage.max_connections)
+                    .connect(&config.storage.database_url)
+                    .await?;
+
+                Self::postgres_pool(pool).await
+            }
+
+            /// Migrates `pool` and builds the Postgres repositories on it.
+            #[cfg(feature = "postgres")]
+            async fn postgres_pool(pool: sqlx::PgPool) -> Result<Self> {
+                sqlx::migrate!("./migrations/postgres").run(&pool).await?;
+
+                Ok(Self {
+                    users: Arc::new(PostgresUserRepository::new(pool.clone())),
+                    refresh_tokens: Arc::new(PostgresRefreshTokenRepository::new(pool.clone())),
+                    sessions: Arc::new(PostgresSessionRepository::new(pool.clone())),
+                    one_time_tokens: Arc::new(PostgresOneTimeTokenRepository::new(pool.clone())),
+                    login_attempts: Arc::new(PostgresLoginAttemptRepository::new(pool.clone())),
+                    access_tokens: Arc::new(PostgresPersonalAccessTokenRepository::new(pool.clone())),
+                    transactions: Arc::new(PostgresTransactionRepository::new(pool)),
+                })
+            }
+
+            /// Postgres repositories in a fresh schema of the database at
+            /// `TEST_POSTGRES_URL`, so tests never see each other's rows.
+            /// The schemas are left behind; point it at a throwaway database.
+            #[cfg(all(test, feature = "postgres"))]
+            #[allow(clippy::missing_panics_doc, clippy::unwrap_used)]
+            pub(crate) async fn postgres_isolated() -> Self {
+                use sqlx::Executor;
+                use sqlx::postgres::PgPoolOptions;
+
+                let url = std::env::var("TEST_POSTGRES_URL").unwrap();
+                let schema = format!("test_{}", uuid::Uuid::new_v4().simple());
+                sqlx::PgPool::connect(&url)
+                    .await
+                    .unwrap()
+                    .execute(format!("CREATE SCHEMA {schema}").as_str())
+                    .await
+                    .unwrap();
+
+                let pool = PgPoolOptions::new()
+                    .max_connections(2)
+                    .after_connect(move |connection, _| {
+                        let search_path = format!("SET search_path TO {schema}");
+                        Box::pin(async move {
+                            connection.execute(search_path.as_str()).await?;
+                            Ok(())
+                        })
+                    })
+                    .connect(&url)
+                    .await
+                    .unwrap();
+                Self::postgres_pool(pool).await.unwrap()
+            }
+
+            async fn cosmos_db(config: &AppConfig) -> Result<Self> {
+                let cosmos = &config.cosmos;
+                Self::ensure_cosmos_containers(config).await?;
+
+                let container_name = |container_type: &str| -> Result<String> {
+                    config
+                        .get_container_config(container_type)
+                        .map(|container| container.name.clone())
+                        .ok_or_else(|| eyre!("Container config for '{container_type}' not found"))
+                };
+
+                Ok(Self {
+                    users: Arc::new(CosmosDbUserRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("users")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    refresh_tokens: Arc::new(CosmosDbRefreshTokenRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("refresh_tokens")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    sessions: Arc::new(CosmosDbSessionRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("sessions")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    one_time_tokens: Arc::new(CosmosDbOneTimeTokenRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("one_time_tokens")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    login_attempts: Arc::new(CosmosDbLoginAttemptRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("login_attempts")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    access_tokens: Arc::new(CosmosDbPersonalAccessTokenRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("personal_access_tokens")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                    transactions: Arc::new(CosmosDbTransactionRepository::new(
+                        cosmos.database_name.clone(),
+                        container_name("transactions")?,
+                        &cosmos.uri,
+                        cosmos.primary_key.clone(),
+                    )),
+                })
+            }
+
+            /// Creates any configured container that does not exist yet, with
+            /// its partition key, unique key policy and the composite indexes its
+            /// listings need. Existing containers are left untouched, but must
+            /// already enforce every configured unique key.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if a container cannot be created or read, or if
+            /// an existing one lacks a configured unique key. Cosmos DB cannot
+            /// add unique keys later; see "Migrating Cosmos DB containers" in
+            /// the README.
+            async fn ensure_cosmos_containers(config: &AppConfig) -> Result<()> {
+                let cosmos = &config.cosmos;
+                let client =
+                    CosmosClient::with_key(&cosmos.uri, Secret::from(cosmos.primary_key.clone()), None)?;
+                let database = client.database_client(&cosmos.database_name);
+
+                for container in cosmos.containers.values() {
+                    let unique_key_policy = (!container.unique_keys.is_empty()).then(|| UniqueKeyPolicy {
+                        unique_keys: container
+                            .unique_keys
+                            .iter()
+                            .map(|path| UniqueKey {
+                                paths: vec![path.clone()],
+                            })
+                            .collect(),
+                    });
+                    // ORDER BY over two paths needs a composite index per direction.
+                    let indexing_policy = (!container.sort_paths.is_empty()).then(|| IndexingPolicy {
+                        automatic: true,
+                        indexing_mode: Some(IndexingMode::Consistent),
+                        included_paths: vec![PropertyPath::from("/*")],
+                        composite_indexes: container
+                            .sort_paths
+                            .iter()
+                            .flat_map(|path| {
+                                [CompositeIndexOrder::Ascending, CompositeIndexOrder::Descending]
+                                    .map(|order| CompositeIndex {
+                                        properties: vec![
+                                            CompositeIndexProperty {
+                                                path: path.clone(),
+                                                order: order.clone(),
+                                            },
+                                            CompositeIndexProperty {
+                                                path: "/id".to_string(),
+                                                order,
+                                            },
+                                        ],
+                                    })
+                            })
+                            .collect(),
+                        ..Default::default()
+                    });
+                    let properties = ContainerProperties {
+                        id: container.name.clone().into(),
+                        partition_key: container.partition_key.clone().into(),
+                        unique_key_policy,
+                        indexing_policy,
+                        ..Default::default()
+                    };
+
+                    match database.create_container(properties, None).await {
+                        Ok(_) => logging::log!("Created Cosmos DB container '{}'", container.name),
+                        Err(e) if e.http_status() == Some(StatusCode::Conflict) => {
+                            let existing: ContainerProperties = database
+                                .container_client(&container.name)
+                                .read(None)
+                                .await?
+                                .into_json_body()
+                                .await?;
+                            let missing = missing_unique_keys(&container.unique_keys, existing.unique_key_policy.as_ref());
+                            if !missing.is_empty() {
+                                return Err(eyre!(
+                                    "Cosmos DB container '{}' exists without the unique keys {missing:?}; \
+                                     recreate it as described in the README",
+                                    container.name
+                                ));
+                            }
+                        }
+                        Err(e) => {
+                            return Err(eyre!(
+                                "Failed to create Cosmos DB container '{}': {e}",
+                                container.name
+                            ));
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        /// The configured unique key paths that `existing` does not enforce.
+        fn missing_unique_keys<'a>(configured: &'a [String], existing: Option<&UniqueKeyPolicy>) -> Vec<&'a str> {
+            configured
+                .iter()
+                .filter(|path| {
+                    !existing.is_some_and(|policy| {
+                        policy
+                            .unique_keys
+                            .iter()
+                            .any(|key| key.paths.len() == 1 && key.paths[0] == **path)
+                    })
+                })
+                .map(String::as_str)
+                .collect()
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            fn policy(keys: &[&[&str]]) -> UniqueKeyPolicy {
+                UniqueKeyPolicy {
+                    unique_keys: keys
+                        .iter()
+                        .map(|paths| UniqueKey {
+                            paths: paths.iter().map(ToString::to_string).collect(),
+                        })
+                        .collect(),
+                }
+            }
+
+            #[test]
+            fn containers_without_the_configured_unique_key_are_reported() {
+                let configured = vec!["/email".to_string()];
+
+                assert_eq!(missing_unique_keys(&configured, None), ["/email"]);
+                assert_eq!(missing_unique_keys(&configured, Some(&policy(&[]))), ["/email"]);
+                assert_eq!(missing_unique_keys(&configured, Some(&policy(&[&["/name"]]))), ["/email"]);
+                // A composite key only makes the combination unique.
+                assert_eq!(missing_unique_keys(&configured, Some(&policy(&[&["/email", "/name"]]))), ["/email"]);
+            }
+
+            #[test]
+            fn containers_with_the_configured_unique_key_pass() {
+                let configured = vec!["/email".to_string()];
+
+                assert!(missing_unique_keys(&configured, Some(&policy(&[&["/email"]]))).is_empty());
+                assert!(missing_unique_keys(&configured, Some(&policy(&[&["/name"], &["/email"]]))).is_empty());
+                assert!(missing_unique_keys(&[], None).is_empty());
+            }
+        }
+    }
+}
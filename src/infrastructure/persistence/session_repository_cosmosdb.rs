@@ -0,0 +1,137 @@
+// Cosmos DB implementation of the SessionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey};
+        use chrono::{DateTime, Utc};
+        use std::sync::Arc;
+        use color_eyre::Result;
+        use crate::domain::models::session::Session;
+        use crate::domain::repositories::session_repository::SessionRepository;
+        use crate::domain::value_objects::UserId;
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Direction, Field};
+        use async_trait::async_trait;
+        use futures_util::stream::TryStreamExt;
+
+        /// Sessions are partitioned by `user_id`.
+        pub struct CosmosDbSessionRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbSessionRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+        }
+
+        #[async_trait]
+        impl SessionRepository for CosmosDbSessionRepository {
+            async fn create_session(&self, session: Session) -> Result<()> {
+                let partition_key = PartitionKey::from(session.user_id().to_string());
+                self.get_container()
+                    .create_item(partition_key, session, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_session(&self, user_id: &UserId, session_id: &str) -> Result<Option<Session>> {
+                match self
+                    .get_container()
+                    .read_item(PartitionKey::from(user_id.to_string()), session_id, None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<Session>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading session from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>> {
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::USER_ID, Comparison::Eq, user_id.as_str())?
+                    .order_by(Field::LAST_SEEN_AT, Direction::Descending)
+                    .build()?;
+                let container = self.get_container();
+                let mut query_stream = container.query_items::<Session>(
+                    query,
+                    PartitionKey::from(user_id.to_string()),
+                    None,
+                )?;
+
+                let mut sessions = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    sessions.extend(feed_page.into_items());
+                }
+                Ok(sessions)
+            }
+
+            async fn record_session_activity(
+                &self,
+                user_id: &UserId,
+                session_id: &str,
+                seen_at: DateTime<Utc>,
+                ip_address: Option<String>,
+                expires_at: DateTime<Utc>,
+            ) -> Result<bool> {
+                let Some(mut session) = self.get_session(user_id, session_id).await? else {
+                    return Ok(false);
+                };
+                session.record_activity(seen_at, ip_address, expires_at);
+                self.get_container()
+                    .replace_item(PartitionKey::from(user_id.to_string()), session_id, session, None)
+                    .await?;
+                Ok(true)
+            }
+
+            async fn delete_session(&self, user_id: &UserId, session_id: &str) -> Result<()> {
+                match self
+                    .get_container()
+                    .delete_item(PartitionKey::from(user_id.to_string()), session_id, None)
+                    .await
+                {
+                    Ok(_) => Ok(()),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(()),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error deleting session from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn delete_all_sessions(&self, user_id: &UserId) -> Result<()> {
+                for session in self.list_sessions(user_id).await? {
+                    self.delete_session(user_id, session.id()).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// In-memory implementation of the SessionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use crate::domain::models::session::Session;
+        use crate::domain::repositories::session_repository::SessionRepository;
+        use crate::domain::value_objects::UserId;
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Sessions keyed by session id.
+        #[derive(Default)]
+        pub struct InMemorySessionRepository {
+            sessions: RwLock<HashMap<String, Session>>,
+        }
+
+        impl InMemorySessionRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl SessionRepository for InMemorySessionRepository {
+            async fn create_session(&self, session: Session) -> Result<()> {
+                self.sessions.write().await.insert(session.id().to_string(), session);
+                Ok(())
+            }
+
+            async fn get_session(&self, user_id: &UserId, session_id: &str) -> Result<Option<Session>> {
+                Ok(self
+                    .sessions
+                    .read()
+                    .await
+                    .get(session_id)
+                    .filter(|session| session.user_id() == user_id)
+                    .cloned())
+            }
+
+            async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>> {
+                let mut sessions: Vec<Session> = self
+                    .sessions
+                    .read()
+                    .await
+                    .values()
+                    .filter(|session| session.user_id() == user_id)
+                    .cloned()
+                    .collect();
+                sessions.sort_by_key(|session| std::cmp::Reverse(session.last_seen_at()));
+                Ok(sessions)
+            }
+
+            async fn record_session_activity(
+                &self,
+                user_id: &UserId,
+                session_id: &str,
+                seen_at: DateTime<Utc>,
+                ip_address: Option<String>,
+                expires_at: DateTime<Utc>,
+            ) -> Result<bool> {
+                let mut sessions = self.sessions.write().await;
+                let session = sessions
+                    .get_mut(session_id)
+                    .filter(|session| session.user_id() == user_id);
+                let found = session.is_some();
+                if let Some(session) = session {
+                    session.record_activity(seen_at, ip_address, expires_at);
+                }
+                drop(sessions);
+                Ok(found)
+            }
+
+            async fn delete_session(&self, user_id: &UserId, session_id: &str) -> Result<()> {
+                let mut sessions = self.sessions.write().await;
+                if sessions
+                    .get(session_id)
+                    .is_some_and(|session| session.user_id() == user_id)
+                {
+                    sessions.remove(session_id);
+                }
+                drop(sessions);
+                Ok(())
+            }
+
+            async fn delete_all_sessions(&self, user_id: &UserId) -> Result<()> {
+                self.sessions
+                    .write()
+                    .await
+                    .retain(|_, session| session.user_id() != user_id);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// PostgreSQL implementation of the SessionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::PgPool;
+        use crate::domain::models::session::Session;
+        use crate::domain::repositories::session_repository::SessionRepository;
+        use crate::domain::value_objects::UserId;
+
+        #[derive(sqlx::FromRow)]
+        struct SessionRow {
+            id: String,
+            user_id: String,
+            user_agent: Option<String>,
+            ip_address: Option<String>,
+            created_at: DateTime<Utc>,
+            last_seen_at: DateTime<Utc>,
+            expires_at: DateTime<Utc>,
+        }
+
+        impl TryFrom<SessionRow> for Session {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: SessionRow) -> Result<Self> {
+                let user_id = UserId::new(row.user_id)
+                    .map_err(|e| eyre!("Invalid session row {}: {e}", row.id))?;
+                Ok(Session::restore(
+                    row.id,
+                    user_id,
+                    row.user_agent,
+                    row.ip_address,
+                    row.created_at,
+                    row.last_seen_at,
+                    row.expires_at,
+                ))
+            }
+        }
+
+        pub struct PostgresSessionRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresSessionRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+        }
+
+        #[async_trait]
+        impl SessionRepository for PostgresSessionRepository {
+            async fn create_session(&self, session: Session) -> Result<()> {
+                sqlx::query(
+                    "INSERT INTO sessions
+                         (id, user_id, user_agent, ip_address, created_at, last_seen_at, expires_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                )
+                .bind(session.id())
+                .bind(session.user_id().as_str())
+                .bind(session.user_agent())
+                .bind(session.ip_address())
+                .bind(session.created_at())
+                .bind(session.last_seen_at())
+                .bind(session.expires_at())
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+
+            async fn get_session(&self, user_id: &UserId, session_id: &str) -> Result<Option<Session>> {
+                sqlx::query_as::<_, SessionRow>(
+                    "SELECT id, user_id, user_agent, ip_address, created_at, last_seen_at, expires_at
+                     FROM sessions WHERE id = $1 AND user_id = $2",
+                )
+                .bind(session_id)
+                .bind(user_id.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(Session::try_from)
+                .transpose()
+            }
+
+            async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>> {
+                sqlx::query_as::<_, SessionRow>(
+                    "SELECT id, user_id, user_agent, ip_address, created_at, last_seen_at, expires_at
+                     FROM sessions WHERE user_id = $1
+                     ORDER BY last_seen_at DESC",
+                )
+                .bind(user_id.as_str())
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(Session::try_from)
+                .collect()
+            }
+
+            async fn record_session_activity(
+                &self,
+                user_id: &UserId,
+                session_id: &str,
+                seen_at: DateTime<Utc>,
+                ip_address: Option<String>,
+                expires_at: DateTime<Utc>,
+            ) -> Result<bool> {
+                let result = sqlx::query(
+                    "UPDATE sessions
+                     SET last_seen_at = $1, ip_address = COALESCE($2, ip_address), expires_at = $3
+                     WHERE id = $4 AND user_id = $5",
+                )
+                .bind(seen_at)
+                .bind(ip_address)
+                .bind(expires_at)
+                .bind(session_id)
+                .bind(user_id.as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete_session(&self, user_id: &UserId, session_id: &str) -> Result<()> {
+                sqlx::query("DELETE FROM sessions WHERE id = $1 AND user_id = $2")
+                    .bind(session_id)
+                    .bind(user_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn delete_all_sessions(&self, user_id: &UserId) -> Result<()> {
+                sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+                    .bind(user_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
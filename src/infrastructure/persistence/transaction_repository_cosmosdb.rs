@@ -0,0 +1,318 @@
+// Cosmos DB implementation of the TransactionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
+        use azure_data_cosmos::clients::ContainerClient;
+        use azure_data_cosmos::{CosmosClient, PartitionKey, Query};
+        use std::sync::Arc;
+        use color_eyre::Result;
+        use crate::domain::models::transaction::{Transaction, sortable_timestamp};
+        use crate::domain::repositories::transaction_repository::{
+            SortDirection, SortKey, TransactionCursor, TransactionPage, TransactionQuery,
+            TransactionRepository, TransactionSortField,
+        };
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Direction, Field};
+        use crate::domain::value_objects::{TransactionId, UserId};
+        use async_trait::async_trait;
+        use leptos::leptos_dom::logging;
+        use futures_util::stream::TryStreamExt;
+
+        /// Transactions are partitioned by `owner_id`, and every read, write
+        /// and query is scoped to the caller's partition so one user can never
+        /// reach another user's documents.
+        pub struct CosmosDbTransactionRepository {
+            client: Arc<CosmosClient>,
+            database_name: String,
+            container_name: String,
+        }
+
+        impl CosmosDbTransactionRepository {
+            #[allow(clippy::missing_panics_doc)]
+            #[must_use]
+            pub fn new(
+                database_name: String,
+                container_name: String,
+                uri: &str,
+                key: String,
+            ) -> Self {
+                #[allow(clippy::expect_used)]
+                let client = CosmosClient::with_key(uri, Secret::from(key), None)
+                    .expect("Failed to create Cosmos client");
+                Self {
+                    client: Arc::new(client),
+                    database_name,
+                    container_name,
+                }
+            }
+
+            #[must_use]
+            pub fn get_container(&self) -> ContainerClient {
+                self.client
+                    .database_client(&self.database_name)
+                    .container_client(&self.container_name)
+            }
+
+            fn build_list_query(
+                owner_id: &UserId,
+                query: &TransactionQuery,
+                cursor: Option<&TransactionCursor>,
+            ) -> Result<Query> {
+                let mut builder = CosmosQueryBuilder::new()
+                    .filter(Field::OWNER_ID, Comparison::Eq, owner_id.as_str())?;
+                if let Some(booked_from) = query.booked_from {
+                    builder = builder.filter(Field::BOOKED_ON, Comparison::Gte, booked_from)?;
+                }
+                if let Some(booked_to) = query.booked_to {
+                    builder = builder.filter(Field::BOOKED_ON, Comparison::Lte, booked_to)?;
+                }
+                if let Some(account) = &query.account {
+                    builder = builder.filter(Field::ACCOUNT, Comparison::Eq, account.as_str())?;
+                }
+                if let Some(category) = &query.category {
+                    builder = builder.filter(Field::CATEGORY, Comparison::Eq, category.as_str())?;
+                }
+                if let Some(min_amount) = query.min_amount_minor {
+                    builder = builder.filter(Field::AMOUNT_MINOR, Comparison::Gte, min_amount)?;
+                }
+                if let Some(max_amount) = query.max_amount_minor {
+                    builder = builder.filter(Field::AMOUNT_MINOR, Comparison::Lte, max_amount)?;
+                }
+                if let Some(search) = &query.search {
+                    builder = builder.contains_any(&[Field::PAYEE, Field::MEMO], search)?;
+                }
+
+                // Sort columns come from a closed enum, never from user input.
+                let order_by = match query.sort.field {
+                    TransactionSortField::BookedOn => Field::BOOKED_ON,
+                    TransactionSortField::Amount => Field::AMOUNT_MINOR,
+                    TransactionSortField::Payee => Field::PAYEE,
+                    TransactionSortField::CreatedAt => Field::CREATED_AT,
+                };
+                let direction = match query.sort.direction {
+                    SortDirection::Ascending => Direction::Ascending,
+                    SortDirection::Descending => Direction::Descending,
+                };
+
+                // Seek past the last document of the previous page.
+                if let Some(cursor) = cursor {
+                    let id = cursor.id();
+                    builder = match cursor.key() {
+                        SortKey::BookedOn(booked_on) => builder.after(order_by, booked_on, Field::ID, id, direction)?,
+                        SortKey::Amount(amount_minor) => builder.after(order_by, amount_minor, Field::ID, id, direction)?,
+                        SortKey::Payee(payee) => builder.after(order_by, payee, Field::ID, id, direction)?,
+                        SortKey::CreatedAt(created_at) => {
+                            builder.after(order_by, sortable_timestamp::format(created_at), Field::ID, id, direction)?
+                        }
+                    };
+                }
+
+                // Ties are broken by id so paging is stable between requests.
+                builder
+                    .order_by(order_by, direction)
+                    .order_by(Field::ID, direction)
+                    // One extra row tells us whether another page exists.
+                    .limit(query.page_size() + 1)
+                    .build()
+            }
+        }
+
+        #[async_trait]
+        impl TransactionRepository for CosmosDbTransactionRepository {
+            async fn create_transaction(&self, transaction: Transaction) -> Result<()> {
+                let partition_key = PartitionKey::from(transaction.owner_id().to_string());
+                self.get_container()
+                    .create_item(partition_key, transaction, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn create_transactions(&self, transactions: Vec<Transaction>) -> Result<()> {
+                // Documents are created one by one; if any create fails the
+                // ones already written are deleted again so the batch is not
+                // left half-applied.
+                let container = self.get_container();
+                let mut created: Vec<(UserId, TransactionId)> = Vec::with_capacity(transactions.len());
+                for transaction in transactions {
+                    let key = (transaction.owner_id().clone(), transaction.id().clone());
+                    let partition_key = PartitionKey::from(transaction.owner_id().to_string());
+                    if let Err(e) = container.create_item(partition_key, transaction, None).await {
+                        for (owner_id, id) in &created {
+                            if let Err(cleanup_error) = container
+                                .delete_item(PartitionKey::from(owner_id.to_string()), id.as_str(), None)
+                                .await
+                            {
+                                logging::console_error(&format!(
+                                    "Failed to roll back transaction {id}: {cleanup_error}"
+                                ));
+                            }
+                        }
+                        return Err(color_eyre::eyre::eyre!(
+                            "Error creating transactions in Cosmos DB: {e}"
+                        ));
+                    }
+                    created.push(key);
+                }
+                Ok(())
+            }
+
+            async fn get_transaction(
+                &self,
+                owner_id: &UserId,
+                id: &TransactionId,
+            ) -> Result<Option<Transaction>> {
+                let partition_key = PartitionKey::from(owner_id.to_string());
+                match self
+                    .get_container()
+                    .read_item(partition_key, id.as_str(), None)
+                    .await
+                {
+                    Ok(response) => Ok(Some(response.into_json_body::<Transaction>().await?)),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(None),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error reading transaction {id} from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn update_transaction(&self, transaction: Transaction) -> Result<()> {
+                // Replacing inside the owner's partition fails with 404 for
+                // documents that belong to someone else.
+                let partition_key = PartitionKey::from(transaction.owner_id().to_string());
+                let id = transaction.id().to_string();
+                self.get_container()
+                    .replace_item(partition_key, &id, transaction, None)
+                    .await?;
+                Ok(())
+            }
+
+            async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<bool> {
+                let partition_key = PartitionKey::from(owner_id.to_string());
+                match self
+                    .get_container()
+                    .delete_item(partition_key, id.as_str(), None)
+                    .await
+                {
+                    Ok(_) => Ok(true),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(false),
+                    Err(e) => Err(color_eyre::eyre::eyre!(
+                        "Error deleting transaction {id} from Cosmos DB: {e}"
+                    )),
+                }
+            }
+
+            async fn delete_transactions_for_owner(&self, owner_id: &UserId) -> Result<u64> {
+                let container = self.get_container();
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::OWNER_ID, Comparison::Eq, owner_id.as_str())?
+                    .build()?;
+                let mut query_stream = container.query_items::<Transaction>(
+                    query,
+                    PartitionKey::from(owner_id.to_string()),
+                    None,
+                )?;
+
+                let mut ids = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    ids.extend(feed_page.into_items().into_iter().map(|transaction| transaction.id().clone()));
+                }
+
+                for id in &ids {
+                    container
+                        .delete_item(PartitionKey::from(owner_id.to_string()), id.as_str(), None)
+                        .await?;
+                }
+                Ok(ids.len() as u64)
+            }
+
+            async fn list_transactions(
+                &self,
+                owner_id: &UserId,
+                query: &TransactionQuery,
+            ) -> Result<TransactionPage> {
+                let cursor = query.cursor()?;
+                let page_size = query.page_size() as usize;
+                let cosmos_query = Self::build_list_query(owner_id, query, cursor.as_ref())?;
+                let partition_key = PartitionKey::from(owner_id.to_string());
+
+                let mut query_stream = self
+                    .get_container()
+                    .query_items::<Transaction>(cosmos_query, partition_key, None)
+                    .map_err(|e| {
+                        logging::console_error(&format!(
+                            "Error querying Cosmos DB for transactions: {e}"
+                        ));
+                        color_eyre::eyre::eyre!("Error querying Cosmos DB for transactions: {e}")
+                    })?;
+
+                let mut items = Vec::with_capacity(page_size + 1);
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    items.extend(feed_page.into_items());
+                    if items.len() > page_size {
+                        break;
+                    }
+                }
+
+                Ok(TransactionPage::new(items, page_size, query.sort))
+            }
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        mod tests {
+            use super::*;
+            use crate::domain::repositories::transaction_repository::TransactionSort;
+            use crate::domain::value_objects::{AccountName, Currency, Money};
+            use crate::infrastructure::persistence::cosmos_query::tests::{parts, paths};
+            use chrono::NaiveDate;
+
+            const INJECTION: &str = "x' OR 1=1 --";
+
+            #[test]
+            fn list_query_sends_every_request_value_as_a_parameter() {
+                let owner_id = UserId::generate();
+                let sort = TransactionSort {
+                    field: TransactionSortField::Payee,
+                    direction: SortDirection::Descending,
+                };
+                let last_seen = Transaction::new(
+                    owner_id.clone(),
+                    INJECTION,
+                    Money::from_minor(-100, Currency::USD),
+                    NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+                    None,
+                    INJECTION,
+                    None,
+                    "Food",
+                    &[],
+                )
+                .unwrap();
+                let query = TransactionQuery {
+                    account: Some(AccountName::new(INJECTION).unwrap()),
+                    search: Some(INJECTION.to_string()),
+                    sort,
+                    ..TransactionQuery::default()
+                };
+                let cursor = TransactionCursor::after(&last_seen, sort);
+
+                let (text, parameters) =
+                    parts(&CosmosDbTransactionRepository::build_list_query(&owner_id, &query, Some(&cursor)).unwrap());
+
+                assert!(!text.contains(INJECTION));
+                assert!(!text.contains('\''));
+                assert!(paths(&text).iter().all(|path| Field::ALL.iter().any(|field| field.path() == *path)));
+                assert_eq!(
+                    text,
+                    "SELECT * FROM c WHERE c.owner_id = @p0 AND c.account = @p1 \
+                     AND (CONTAINS(c.payee, @p2, true) OR CONTAINS(c.memo, @p2, true)) \
+                     AND (c.payee < @p3 OR (c.payee = @p3 AND c.id < @p4)) \
+                     ORDER BY c.payee DESC, c.id DESC OFFSET 0 LIMIT @limit"
+                );
+                let injected = parameters.iter().filter(|(_, value)| value == INJECTION).count();
+                assert_eq!(injected, 3);
+            }
+        }
+    }
+}
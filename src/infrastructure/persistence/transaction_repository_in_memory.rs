@@ -0,0 +1,399 @@
+// In-memory implementation of the TransactionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use std::collections::hash_map::Entry;
+        use color_eyre::Result;
+        use crate::domain::models::transaction::Transaction;
+        use crate::domain::repositories::transaction_repository::{
+            TransactionPage, TransactionQuery, TransactionRepository,
+        };
+        use crate::domain::value_objects::{TransactionId, UserId};
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Transactions keyed by `(owner_id, id)`, mirroring the owner
+        /// partitioning of the Cosmos container.
+        #[derive(Default)]
+        pub struct InMemoryTransactionRepository {
+            transactions: RwLock<HashMap<(String, String), Transaction>>,
+        }
+
+        impl InMemoryTransactionRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            fn key(owner_id: &UserId, id: &TransactionId) -> (String, String) {
+                (owner_id.to_string(), id.to_string())
+            }
+        }
+
+        #[async_trait]
+        impl TransactionRepository for InMemoryTransactionRepository {
+            async fn create_transaction(&self, transaction: Transaction) -> Result<()> {
+                let key = Self::key(transaction.owner_id(), transaction.id());
+                match self.transactions.write().await.entry(key) {
+                    Entry::Occupied(_) => Err(color_eyre::eyre::eyre!(
+                        "Transaction {} already exists",
+                        transaction.id()
+                    )),
+                    Entry::Vacant(entry) => {
+                        entry.insert(transaction);
+                        Ok(())
+                    }
+                }
+            }
+
+            async fn create_transactions(&self, transactions: Vec<Transaction>) -> Result<()> {
+                let mut stored = self.transactions.write().await;
+                let keys: Vec<_> = transactions
+                    .iter()
+                    .map(|transaction| Self::key(transaction.owner_id(), transaction.id()))
+                    .collect();
+                // Validate the whole batch before inserting anything.
+                let mut unique = std::collections::HashSet::new();
+                if let Some(key) = keys
+                    .iter()
+                    .find(|key| stored.contains_key(*key) || !unique.insert(*key))
+                {
+                    return Err(color_eyre::eyre::eyre!("Transaction {} already exists", key.1));
+                }
+                stored.extend(keys.into_iter().zip(transactions));
+                drop(stored);
+                Ok(())
+            }
+
+            async fn get_transaction(
+                &self,
+                owner_id: &UserId,
+                id: &TransactionId,
+            ) -> Result<Option<Transaction>> {
+                Ok(self.transactions.read().await.get(&Self::key(owner_id, id)).cloned())
+            }
+
+            async fn update_transaction(&self, transaction: Transaction) -> Result<()> {
+                let key = Self::key(transaction.owner_id(), transaction.id());
+                let mut transactions = self.transactions.write().await;
+                match transactions.get_mut(&key) {
+                    Some(existing) => {
+                        *existing = transaction;
+                        Ok(())
+                    }
+                    None => Err(color_eyre::eyre::eyre!(
+                        "Transaction {} not found",
+                        transaction.id()
+                    )),
+                }
+            }
+
+            async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<bool> {
+                Ok(self
+                    .transactions
+                    .write()
+                    .await
+                    .remove(&Self::key(owner_id, id))
+                    .is_some())
+            }
+
+            async fn delete_transactions_for_owner(&self, owner_id: &UserId) -> Result<u64> {
+                let mut transactions = self.transactions.write().await;
+                let before = transactions.len();
+                transactions.retain(|(owner, _), _| owner != owner_id.as_str());
+                let removed = before - transactions.len();
+                drop(transactions);
+                Ok(removed as u64)
+            }
+
+            async fn list_transactions(
+                &self,
+                owner_id: &UserId,
+                query: &TransactionQuery,
+            ) -> Result<TransactionPage> {
+                let cursor = query.cursor()?;
+                let page_size = query.page_size() as usize;
+
+                let mut matching: Vec<Transaction> = self
+                    .transactions
+                    .read()
+                    .await
+                    .values()
+                    .filter(|transaction| transaction.owner_id() == owner_id)
+                    .filter(|transaction| query.matches(transaction))
+                    .filter(|transaction| cursor.as_ref().is_none_or(|cursor| cursor.precedes(transaction)))
+                    .cloned()
+                    .collect();
+                matching.sort_by(|a, b| query.sort.compare(a, b));
+                matching.truncate(page_size + 1);
+
+                Ok(TransactionPage::new(matching, page_size, query.sort))
+            }
+        }
+
+        /// Contract tests shared by every transaction store; the SQL
+        /// stores run them against their own database.
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        pub(crate) mod tests {
+            use super::*;
+            use crate::domain::repositories::transaction_repository::{
+                SortDirection, TransactionSort, TransactionSortField,
+            };
+            use crate::domain::value_objects::{Currency, Money};
+            use chrono::NaiveDate;
+
+            pub(crate) fn transaction(owner_id: &UserId, day: u32, amount_minor: i64, payee: &str) -> Transaction {
+                Transaction::new(
+                    owner_id.clone(),
+                    "Checking",
+                    Money::from_minor(amount_minor, Currency::USD),
+                    NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+                    None,
+                    payee,
+                    None,
+                    "Food",
+                    &[],
+                )
+                .unwrap()
+            }
+
+            /// Stores transactions that tie on every sort field.
+            async fn store_with_ties(repository: &dyn TransactionRepository, owner_id: &UserId) -> Vec<Transaction> {
+                let mut stored = Vec::new();
+                for (day, amount_minor, payee) in [
+                    (1, -100, "Bakery"),
+                    (2, -100, "Bakery"),
+                    (2, -250, "Grocery Store"),
+                    (2, -100, "Grocery Store"),
+                    (3, -250, "Bakery"),
+                    (4, -100, "Pharmacy"),
+                    (5, 900, "Bakery"),
+                ] {
+                    let transaction = transaction(owner_id, day, amount_minor, payee);
+                    repository.create_transaction(transaction.clone()).await.unwrap();
+                    stored.push(transaction);
+                }
+                stored
+            }
+
+            fn ids(transactions: &[Transaction]) -> Vec<String> {
+                transactions.iter().map(|transaction| transaction.id().to_string()).collect()
+            }
+
+            fn query(sort: TransactionSort, continuation: Option<String>) -> TransactionQuery {
+                TransactionQuery {
+                    sort,
+                    limit: Some(3),
+                    continuation,
+                    ..TransactionQuery::default()
+                }
+            }
+
+            /// Follows continuation tokens to the last page, checking that no
+            /// page is larger than asked for.
+            async fn page_through(
+                repository: &dyn TransactionRepository,
+                owner_id: &UserId,
+                sort: TransactionSort,
+                mut continuation: Option<String>,
+            ) -> Vec<String> {
+                let mut seen = Vec::new();
+                loop {
+                    let page = repository.list_transactions(owner_id, &query(sort, continuation)).await.unwrap();
+                    assert!(page.items.len() <= 3);
+                    seen.extend(ids(&page.items));
+                    match page.continuation {
+                        Some(token) => continuation = Some(token),
+                        None => return seen,
+                    }
+                }
+            }
+
+            pub(crate) async fn pages_through_ties_without_gaps_or_duplicates(
+                repository: &dyn TransactionRepository,
+                owner_id: &UserId,
+            ) {
+                let stored = store_with_ties(repository, owner_id).await;
+
+                for field in [
+                    TransactionSortField::BookedOn,
+                    TransactionSortField::Amount,
+                    TransactionSortField::Payee,
+                    TransactionSortField::CreatedAt,
+                ] {
+                    for direction in [SortDirection::Ascending, SortDirection::Descending] {
+                        let sort = TransactionSort { field, direction };
+                        let mut expected = stored.clone();
+                        expected.sort_by(|a, b| sort.compare(a, b));
+
+                        let seen = page_through(repository, owner_id, sort, None).await;
+
+                        assert_eq!(seen, ids(&expected), "{sort:?}");
+                    }
+                }
+            }
+
+            pub(crate) async fn pages_are_stable_when_rows_are_added_between_requests(
+                repository: &dyn TransactionRepository,
+                owner_id: &UserId,
+            ) {
+                let stored = store_with_ties(repository, owner_id).await;
+                let sort = TransactionSort::default();
+                let mut expected = stored.clone();
+                expected.sort_by(|a, b| sort.compare(a, b));
+
+                let first = repository.list_transactions(owner_id, &query(sort, None)).await.unwrap();
+                // A newer booking sorts first and would shift offset pages.
+                repository
+                    .create_transaction(transaction(owner_id, 9, -100, "Bakery"))
+                    .await
+                    .unwrap();
+                let mut seen = ids(&first.items);
+                seen.extend(page_through(repository, owner_id, sort, first.continuation).await);
+
+                assert_eq!(seen, ids(&expected));
+            }
+
+            /// Compares every stored field; timestamps only to the
+            /// microsecond, the precision of the coarsest store.
+            fn assert_same(actual: &Transaction, expected: &Transaction) {
+                assert_eq!(actual.id(), expected.id());
+                assert_eq!(actual.owner_id(), expected.owner_id());
+                assert_eq!(actual.account(), expected.account());
+                assert_eq!(actual.amount(), expected.amount());
+                assert_eq!(actual.booked_on(), expected.booked_on());
+                assert_eq!(actual.value_on(), expected.value_on());
+                assert_eq!(actual.payee(), expected.payee());
+                assert_eq!(actual.memo(), expected.memo());
+                assert_eq!(actual.category(), expected.category());
+                assert_eq!(actual.tags(), expected.tags());
+                assert_eq!(actual.created_at().timestamp_micros(), expected.created_at().timestamp_micros());
+                assert_eq!(actual.updated_at().timestamp_micros(), expected.updated_at().timestamp_micros());
+            }
+
+            pub(crate) async fn round_trips_transactions(repository: &dyn TransactionRepository, owner_id: &UserId) {
+                let mut stored = Transaction::new(
+                    owner_id.clone(),
+                    "Checking",
+                    Money::from_minor(-1234, Currency::EUR),
+                    NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2025, 3, 3),
+                    "Bakery",
+                    Some("Birthday cake"),
+                    "Food",
+                    &["family".to_string(), "treats".to_string()],
+                )
+                .unwrap();
+                repository.create_transaction(stored.clone()).await.unwrap();
+
+                let loaded = repository.get_transaction(owner_id, stored.id()).await.unwrap().unwrap();
+                assert_same(&loaded, &stored);
+
+                stored
+                    .update(
+                        "Savings",
+                        Money::from_minor(5000, Currency::USD),
+                        NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(),
+                        None,
+                        "Employer",
+                        None,
+                        "Salary",
+                        &[],
+                    )
+                    .unwrap();
+                repository.update_transaction(stored.clone()).await.unwrap();
+
+                let updated = repository.get_transaction(owner_id, stored.id()).await.unwrap().unwrap();
+                assert_same(&updated, &stored);
+
+                assert!(repository.delete_transaction(owner_id, stored.id()).await.unwrap());
+                assert!(repository.get_transaction(owner_id, stored.id()).await.unwrap().is_none());
+                assert!(!repository.delete_transaction(owner_id, stored.id()).await.unwrap());
+            }
+
+            pub(crate) async fn scopes_transactions_to_their_owner(
+                repository: &dyn TransactionRepository,
+                owner_id: &UserId,
+                other_id: &UserId,
+            ) {
+                let stored = transaction(owner_id, 1, -100, "Bakery");
+                repository.create_transaction(stored.clone()).await.unwrap();
+
+                assert!(repository.get_transaction(other_id, stored.id()).await.unwrap().is_none());
+                let page = repository.list_transactions(other_id, &TransactionQuery::default()).await.unwrap();
+                assert!(page.items.is_empty());
+
+                // The same id claimed by another owner must not overwrite the row.
+                let forged = Transaction::restore(
+                    stored.id().as_str(),
+                    other_id.as_str(),
+                    "Checking",
+                    Money::from_minor(-999_999, Currency::USD),
+                    stored.booked_on(),
+                    stored.value_on(),
+                    "Mallory",
+                    None,
+                    "Food",
+                    &[],
+                    stored.created_at(),
+                    stored.updated_at(),
+                )
+                .unwrap();
+                assert!(repository.update_transaction(forged).await.is_err());
+                assert!(!repository.delete_transaction(other_id, stored.id()).await.unwrap());
+                assert_eq!(repository.delete_transactions_for_owner(other_id).await.unwrap(), 0);
+
+                let unchanged = repository.get_transaction(owner_id, stored.id()).await.unwrap().unwrap();
+                assert_same(&unchanged, &stored);
+            }
+
+            pub(crate) async fn creates_batches_all_or_nothing(repository: &dyn TransactionRepository, owner_id: &UserId) {
+                let existing = transaction(owner_id, 1, -100, "Bakery");
+                repository.create_transaction(existing.clone()).await.unwrap();
+
+                let fresh = transaction(owner_id, 2, -250, "Grocery Store");
+                let result = repository.create_transactions(vec![fresh.clone(), existing]).await;
+
+                assert!(result.is_err());
+                assert!(repository.get_transaction(owner_id, fresh.id()).await.unwrap().is_none());
+
+                let other = transaction(owner_id, 3, -100, "Pharmacy");
+                repository.create_transactions(vec![fresh.clone(), other.clone()]).await.unwrap();
+                let page = repository.list_transactions(owner_id, &TransactionQuery::default()).await.unwrap();
+                assert_eq!(page.items.len(), 3);
+                assert_eq!(repository.delete_transactions_for_owner(owner_id).await.unwrap(), 3);
+            }
+
+            #[tokio::test]
+            async fn in_memory_round_trips_transactions() {
+                round_trips_transactions(&InMemoryTransactionRepository::new(), &UserId::generate()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_scopes_transactions_to_their_owner() {
+                let repository = InMemoryTransactionRepository::new();
+                scopes_transactions_to_their_owner(&repository, &UserId::generate(), &UserId::generate()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_creates_batches_all_or_nothing() {
+                creates_batches_all_or_nothing(&InMemoryTransactionRepository::new(), &UserId::generate()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_pages_through_ties_without_gaps_or_duplicates() {
+                let repository = InMemoryTransactionRepository::new();
+                pages_through_ties_without_gaps_or_duplicates(&repository, &UserId::generate()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_pages_are_stable_when_rows_are_added_between_requests() {
+                let repository = InMemoryTransactionRepository::new();
+                pages_are_stable_when_rows_are_added_between_requests(&repository, &UserId::generate()).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,345 @@
+// PostgreSQL implementation of the TransactionRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, NaiveDate, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::{QueryBuilder, Postgres, PgPool};
+        use crate::domain::models::transaction::Transaction;
+        use crate::domain::repositories::transaction_repository::{
+            SortDirection, SortKey, TransactionCursor, TransactionPage, TransactionQuery,
+            TransactionRepository, TransactionSortField,
+        };
+        use crate::domain::value_objects::{Currency, Memo, Money, TransactionId, UserId};
+
+        const TRANSACTION_COLUMNS: &str = "id, owner_id, account, amount_minor, currency, booked_on, value_on, payee, memo, category, tags, created_at, updated_at";
+
+        #[derive(sqlx::FromRow)]
+        struct TransactionRow {
+            id: String,
+            owner_id: String,
+            account: String,
+            amount_minor: i64,
+            currency: String,
+            booked_on: NaiveDate,
+            value_on: NaiveDate,
+            payee: String,
+            memo: Option<String>,
+            category: String,
+            tags: Vec<String>,
+            created_at: DateTime<Utc>,
+            updated_at: DateTime<Utc>,
+        }
+
+        impl TryFrom<TransactionRow> for Transaction {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: TransactionRow) -> Result<Self> {
+                let invalid = |e: &dyn std::fmt::Display| eyre!("Invalid transaction row {}: {e}", row.id);
+                let currency = Currency::from_code(&row.currency).map_err(|e| invalid(&e))?;
+
+                Transaction::restore(
+                    &row.id,
+                    &row.owner_id,
+                    &row.account,
+                    Money::from_minor(row.amount_minor, currency),
+                    row.booked_on,
+                    row.value_on,
+                    &row.payee,
+                    row.memo.as_deref(),
+                    &row.category,
+                    &row.tags,
+                    row.created_at,
+                    row.updated_at,
+                )
+                .map_err(|e| invalid(&e))
+            }
+        }
+
+        fn tags(transaction: &Transaction) -> Vec<String> {
+            transaction.tags().iter().map(ToString::to_string).collect()
+        }
+
+        pub struct PostgresTransactionRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresTransactionRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+
+            async fn insert<'e, E>(executor: E, transaction: &Transaction) -> Result<()>
+            where
+                E: sqlx::Executor<'e, Database = Postgres>,
+            {
+                sqlx::query(&format!(
+                    "INSERT INTO transactions ({TRANSACTION_COLUMNS})
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+                ))
+                .bind(transaction.id().as_str())
+                .bind(transaction.owner_id().as_str())
+                .bind(transaction.account().as_str())
+                .bind(transaction.amount().amount_minor())
+                .bind(transaction.amount().currency().code())
+                .bind(transaction.booked_on())
+                .bind(transaction.value_on())
+                .bind(transaction.payee().as_str())
+                .bind(transaction.memo().map(Memo::as_str))
+                .bind(transaction.category().as_str())
+                .bind(tags(transaction))
+                .bind(transaction.created_at())
+                .bind(transaction.updated_at())
+                .execute(executor)
+                .await?;
+                Ok(())
+            }
+
+            fn build_list_query(
+                owner_id: &UserId,
+                query: &TransactionQuery,
+                cursor: Option<&TransactionCursor>,
+            ) -> QueryBuilder<'static, Postgres> {
+                let mut builder = QueryBuilder::new(format!(
+                    "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE owner_id = "
+                ));
+                builder.push_bind(owner_id.to_string());
+
+                if let Some(booked_from) = query.booked_from {
+                    builder.push(" AND booked_on >= ").push_bind(booked_from);
+                }
+                if let Some(booked_to) = query.booked_to {
+                    builder.push(" AND booked_on <= ").push_bind(booked_to);
+                }
+                if let Some(account) = &query.account {
+                    builder.push(" AND account = ").push_bind(account.to_string());
+                }
+                if let Some(category) = &query.category {
+                    builder.push(" AND category = ").push_bind(category.to_string());
+                }
+                if let Some(min_amount) = query.min_amount_minor {
+                    builder.push(" AND amount_minor >= ").push_bind(min_amount);
+                }
+                if let Some(max_amount) = query.max_amount_minor {
+                    builder.push(" AND amount_minor <= ").push_bind(max_amount);
+                }
+                if let Some(search) = &query.search {
+                    let search = search.to_lowercase();
+                    builder
+                        .push(" AND (strpos(lower(payee), ")
+                        .push_bind(search.clone())
+                        .push(") > 0 OR strpos(lower(COALESCE(memo, '')), ")
+                        .push_bind(search)
+                        .push(") > 0)");
+                }
+
+                // Sort columns come from a closed enum, never from user input.
+                let order_by = match query.sort.field {
+                    TransactionSortField::BookedOn => "booked_on",
+                    TransactionSortField::Amount => "amount_minor",
+                    TransactionSortField::Payee => "payee",
+                    TransactionSortField::CreatedAt => "created_at",
+                };
+                let (direction, after) = match query.sort.direction {
+                    SortDirection::Ascending => ("ASC", ">"),
+                    SortDirection::Descending => ("DESC", "<"),
+                };
+
+                // Seek past the last row of the previous page.
+                if let Some(cursor) = cursor {
+                    builder.push(format!(" AND ({order_by} {after} "));
+                    Self::push_sort_key(&mut builder, cursor.key());
+                    builder.push(format!(" OR ({order_by} = "));
+                    Self::push_sort_key(&mut builder, cursor.key());
+                    builder
+                        .push(format!(" AND id {after} "))
+                        .push_bind(cursor.id().to_string())
+                        .push("))");
+                }
+
+                // Ties are broken by id so paging is stable between requests.
+                builder.push(format!(" ORDER BY {order_by} {direction}, id {direction}"));
+
+                // One extra row tells us whether another page exists.
+                builder
+                    .push(" LIMIT ")
+                    .push_bind(i64::from(query.page_size()) + 1);
+
+                builder
+            }
+
+            fn push_sort_key(builder: &mut QueryBuilder<'static, Postgres>, key: &SortKey) {
+                match key {
+                    SortKey::BookedOn(booked_on) => builder.push_bind(*booked_on),
+                    SortKey::Amount(amount_minor) => builder.push_bind(*amount_minor),
+                    SortKey::Payee(payee) => builder.push_bind(payee.clone()),
+                    SortKey::CreatedAt(created_at) => builder.push_bind(*created_at),
+                };
+            }
+        }
+
+        #[async_trait]
+        impl TransactionRepository for PostgresTransactionRepository {
+            async fn create_transaction(&self, transaction: Transaction) -> Result<()> {
+                Self::insert(&self.pool, &transaction).await
+            }
+
+            async fn create_transactions(&self, transactions: Vec<Transaction>) -> Result<()> {
+                let mut db_transaction = self.pool.begin().await?;
+                for transaction in &transactions {
+                    Self::insert(&mut *db_transaction, transaction).await?;
+                }
+                db_transaction.commit().await?;
+                Ok(())
+            }
+
+            async fn get_transaction(
+                &self,
+                owner_id: &UserId,
+                id: &TransactionId,
+            ) -> Result<Option<Transaction>> {
+                sqlx::query_as::<_, TransactionRow>(&format!(
+                    "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE id = $1 AND owner_id = $2"
+                ))
+                .bind(id.as_str())
+                .bind(owner_id.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(Transaction::try_from)
+                .transpose()
+            }
+
+            async fn update_transaction(&self, transaction: Transaction) -> Result<()> {
+                // Scoping the update to the owner means it matches nothing for
+                // rows that belong to someone else.
+                let result = sqlx::query(
+                    "UPDATE transactions
+                     SET account = $1, amount_minor = $2, currency = $3, booked_on = $4, value_on = $5,
+                         payee = $6, memo = $7, category = $8, tags = $9, updated_at = $10
+                     WHERE id = $11 AND owner_id = $12",
+                )
+                .bind(transaction.account().as_str())
+                .bind(transaction.amount().amount_minor())
+                .bind(transaction.amount().currency().code())
+                .bind(transaction.booked_on())
+                .bind(transaction.value_on())
+                .bind(transaction.payee().as_str())
+                .bind(transaction.memo().map(Memo::as_str))
+                .bind(transaction.category().as_str())
+                .bind(tags(&transaction))
+                .bind(transaction.updated_at())
+                .bind(transaction.id().as_str())
+                .bind(transaction.owner_id().as_str())
+                .execute(&self.pool)
+                .await?;
+
+                if result.rows_affected() == 0 {
+                    return Err(eyre!("Transaction {} not found", transaction.id()));
+                }
+                Ok(())
+            }
+
+            async fn delete_transaction(&self, owner_id: &UserId, id: &TransactionId) -> Result<bool> {
+                let result = sqlx::query("DELETE FROM transactions WHERE id = $1 AND owner_id = $2")
+                    .bind(id.as_str())
+                    .bind(owner_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete_transactions_for_owner(&self, owner_id: &UserId) -> Result<u64> {
+                let result = sqlx::query("DELETE FROM transactions WHERE owner_id = $1")
+                    .bind(owner_id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(result.rows_affected())
+            }
+
+            async fn list_transactions(
+                &self,
+                owner_id: &UserId,
+                query: &TransactionQuery,
+            ) -> Result<TransactionPage> {
+                let cursor = query.cursor()?;
+                let items = Self::build_list_query(owner_id, query, cursor.as_ref())
+                    .build_query_as::<TransactionRow>()
+                    .fetch_all(&self.pool)
+                    .await?
+                    .into_iter()
+                    .map(Transaction::try_from)
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(TransactionPage::new(items, query.page_size() as usize, query.sort))
+            }
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        mod tests {
+            use crate::domain::models::user::User;
+            use crate::domain::value_objects::UserId;
+            use crate::infrastructure::persistence::repositories::Repositories;
+            use crate::infrastructure::persistence::transaction_repository_in_memory::tests::{
+                creates_batches_all_or_nothing, pages_are_stable_when_rows_are_added_between_requests,
+                pages_through_ties_without_gaps_or_duplicates, round_trips_transactions,
+                scopes_transactions_to_their_owner,
+            };
+
+            /// Stores a user to own transactions, which the foreign key requires.
+            async fn create_owner(repositories: &Repositories, email: &str) -> UserId {
+                let user = User::new(email, "$argon2id$test".to_string(), "Ada", "Byron").unwrap();
+                let owner_id = user.id().clone();
+                repositories.users.create_user(user).await.unwrap();
+                owner_id
+            }
+
+            /// Migrated repositories and a stored user to own transactions.
+            async fn repositories_with_owner() -> (Repositories, UserId) {
+                let repositories = Repositories::postgres_isolated().await;
+                let owner_id = create_owner(&repositories, "ada@example.com").await;
+                (repositories, owner_id)
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn round_trips_transactions_through_the_migrated_schema() {
+                let (repositories, owner_id) = repositories_with_owner().await;
+                round_trips_transactions(repositories.transactions.as_ref(), &owner_id).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn other_users_cannot_read_or_change_a_transaction() {
+                let (repositories, owner_id) = repositories_with_owner().await;
+                let other_id = create_owner(&repositories, "grace@example.com").await;
+                scopes_transactions_to_their_owner(repositories.transactions.as_ref(), &owner_id, &other_id).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn rolls_back_a_batch_that_fails_part_way() {
+                let (repositories, owner_id) = repositories_with_owner().await;
+                creates_batches_all_or_nothing(repositories.transactions.as_ref(), &owner_id).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn pages_through_ties_on_the_sort_key() {
+                let (repositories, owner_id) = repositories_with_owner().await;
+                pages_through_ties_without_gaps_or_duplicates(repositories.transactions.as_ref(), &owner_id).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn pages_are_stable_when_rows_are_added() {
+                let (repositories, owner_id) = repositories_with_owner().await;
+                pages_are_stable_when_rows_are_added_between_requests(repositories.transactions.as_ref(), &owner_id).await;
+            }
+        }
+    }
+}
@@ -4,12 +4,18 @@ use cfg_if::cfg_if;
 cfg_if! {
     if #[cfg(feature = "ssr")] {
         use azure_core::credentials::Secret;
+        use azure_core::http::StatusCode;
         use azure_data_cosmos::clients::ContainerClient;
         use azure_data_cosmos::{CosmosClient, PartitionKey};
         use std::sync::Arc;
         use color_eyre::Result;
         use crate::domain::models::user::User;
-        use crate::domain::repositories::user_repository::UserRepository;
+        use crate::domain::repositories::user_repository::{DuplicateEmail, UserRepository};
+        use crate::domain::value_objects::{Email, UserId};
+        use crate::infrastructure::persistence::cosmos_conditional::{
+            if_match, is_precondition_failed, read_item_with_etag,
+        };
+        use crate::infrastructure::persistence::cosmos_query::{Comparison, CosmosQueryBuilder, Field};
         use async_trait::async_trait;
         use leptos::leptos_dom::logging;
         use futures_util::stream::TryStreamExt;
@@ -49,55 +55,144 @@ cfg_if! {
         #[async_trait]
         impl UserRepository for CosmosDbUserRepository {
             async fn create_user(&self, user: User) -> Result<()> {
+                // The unique key policy on /email rejects a second document for
+                // the same address with 409 Conflict.
+                let email = user.email().to_string();
+                let partition_key = PartitionKey::from(email.clone());
+                match self.get_container().create_item(partition_key, user, None).await {
+                    Ok(_) => Ok(()),
+                    Err(e) if e.http_status() == Some(StatusCode::Conflict) => {
+                        Err(DuplicateEmail { email }.into())
+                    }
+                    Err(e) => Err(color_eyre::eyre::eyre!("Error creating user in Cosmos DB: {e}")),
+                }
+            }
+            async fn get_user_by_email(&self, email: &Email) -> Result<Option<User>> {
+                let partition_key = PartitionKey::from(email.to_string());
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::EMAIL, Comparison::Eq, email.as_str())?
+                    .build()?;
+
+                let mut query_stream = self
+                    .get_container()
+                    .query_items::<User>(query, partition_key, None)
+                    .map_err(|e| {
+                        logging::console_error(&format!("Error querying Cosmos DB for users: {e}"));
+                        color_eyre::eyre::eyre!("Error querying Cosmos DB for users: {e}")
+                    })?;
+
+                let mut users = Vec::new();
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    users.extend(feed_page.into_items());
+                }
+
+                if users.len() > 1 {
+                    logging::console_error(&format!("Multiple users found for email: {email}"));
+                    return Err(color_eyre::eyre::eyre!("Multiple users found for email: {email}"));
+                }
+                Ok(users.pop())
+            }
+
+            async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>> {
+                // Users are partitioned by email, so looking one up by id has
+                // to fan out across partitions.
+                let query = CosmosQueryBuilder::new()
+                    .filter(Field::ID, Comparison::Eq, id.as_str())?
+                    .build()?;
+                let mut query_stream = self.get_container().query_items::<User>(query, (), None)?;
+
+                while let Some(feed_page) = query_stream.try_next().await? {
+                    if let Some(user) = feed_page.into_items().into_iter().next() {
+                        return Ok(Some(user));
+                    }
+                }
+                Ok(None)
+            }
+
+            async fn update_user(&self, user: User) -> Result<()> {
+                let previous = self
+                    .get_user_by_id(user.id())
+                    .await?
+                    .ok_or_else(|| color_eyre::eyre::eyre!("User {} not found", user.id()))?;
+
                 let container = self.get_container();
-                let partition_key = PartitionKey::from(user.email().to_string());
-                container.create_item(partition_key, user, None).await?;
+                let id = user.id().to_string();
+                if previous.email() == user.email() {
+                    container
+                        .replace_item(PartitionKey::from(user.email().to_string()), &id, user, None)
+                        .await?;
+                    return Ok(());
+                }
+
+                // The partition key cannot change in place: write the user
+                // under the new email first, so the unique key rejects a taken
+                // address, then remove the old document.
+                let email = user.email().to_string();
+                match container.create_item(PartitionKey::from(email.clone()), user, None).await {
+                    Ok(_) => {}
+                    Err(e) if e.http_status() == Some(StatusCode::Conflict) => {
+                        return Err(DuplicateEmail { email }.into());
+                    }
+                    Err(e) => return Err(color_eyre::eyre::eyre!("Error updating user in Cosmos DB: {e}")),
+                }
+
+                if let Err(e) = container
+                    .delete_item(PartitionKey::from(previous.email().to_string()), &id, None)
+                    .await
+                {
+                    if let Err(cleanup_error) = container.delete_item(PartitionKey::from(email), &id, None).await {
+                        logging::console_error(&format!(
+                            "Failed to roll back email change for user {id}: {cleanup_error}"
+                        ));
+                    }
+                    return Err(color_eyre::eyre::eyre!("Error updating user in Cosmos DB: {e}"));
+                }
                 Ok(())
             }
-            async fn get_user_by_email(&self, email: &str) -> Result<Option<User>> {
-                let partition_key = PartitionKey::from(email.to_string().clone());
 
-                let query = format!("SELECT * FROM c WHERE c.email = '{email}'");
-
-                logging::console_log(&format!("Querying for user by email: {email}"));
-                let query_result = self
+            async fn delete_user(&self, id: &UserId) -> Result<bool> {
+                let Some(user) = self.get_user_by_id(id).await? else {
+                    return Ok(false);
+                };
+                match self
                     .get_container()
-                    .query_items::<User>(query, partition_key, None);
-
-                match query_result {
-                    Ok(mut query_stream) => {
-                        let mut user = None;
-                        if let Some(feed_page) = query_stream.try_next().await? {
-                            logging::console_log(&format!(
-                                "Received feed page with {} items",
-                                feed_page.items().len()
-                            ));
-
-                            match feed_page.items().len() {
-                                0 => {
-                                    logging::console_warn(&format!("No user found for email: {email}"));
-                                }
-                                1 => {
-                                    user = feed_page.items().first().cloned();
-                                    logging::console_log(&format!("User found for email: {email}"));
-                                }
-                                _ => {
-                                    logging::console_error(&format!(
-                                        "Multiple users found for email: {email}"
-                                    ));
-                                }
-                            }
-                        }
+                    .delete_item(PartitionKey::from(user.email().to_string()), id.as_str(), None)
+                    .await
+                {
+                    Ok(_) => Ok(true),
+                    Err(e) if e.http_status() == Some(StatusCode::NotFound) => Ok(false),
+                    Err(e) => Err(color_eyre::eyre::eyre!("Error deleting user {id} from Cosmos DB: {e}")),
+                }
+            }
 
-                        logging::console_log(&format!("Retrieved user: {:#?}", &user));
-                        Ok(user)
+            async fn record_totp_step(&self, user: &User, step: i64) -> Result<bool> {
+                let container = self.get_container();
+                let partition_key = PartitionKey::from(user.email().to_string());
+                let id = user.id().as_str();
+                // An unrelated write in between only means reading again;
+                // a stored step at or after `step` means the code was used.
+                loop {
+                    let Some((mut stored, etag)) =
+                        read_item_with_etag::<User>(&container, partition_key.clone(), id).await?
+                    else {
+                        return Ok(false);
+                    };
+                    if !stored.totp().is_some_and(|totp| totp.is_unused_step(step)) {
+                        return Ok(false);
                     }
-                    Err(e) => {
-                        logging::console_log(&format!("Error querying Cosmos DB for users: {e}"));
-                        Err(color_eyre::eyre::eyre!(
-                            "Error querying Cosmos DB for books: {}",
-                            e
-                        ))
+
+                    stored.record_totp_step(step);
+                    match container
+                        .replace_item(partition_key.clone(), id, stored, Some(if_match(etag)))
+                        .await
+                    {
+                        Ok(_) => return Ok(true),
+                        Err(e) if is_precondition_failed(&e) => {}
+                        Err(e) => {
+                            return Err(color_eyre::eyre::eyre!(
+                                "Error recording TOTP step for user {id} in Cosmos DB: {e}"
+                            ));
+                        }
                     }
                 }
             }
@@ -0,0 +1,202 @@
+// In-memory implementation of the UserRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use std::collections::HashMap;
+        use std::collections::hash_map::Entry;
+        use color_eyre::Result;
+        use crate::domain::models::user::User;
+        use crate::domain::repositories::user_repository::{DuplicateEmail, UserRepository};
+        use crate::domain::value_objects::{Email, UserId};
+        use async_trait::async_trait;
+        use tokio::sync::RwLock;
+
+        /// Users keyed by email, so an email can belong to at most one user.
+        #[derive(Default)]
+        pub struct InMemoryUserRepository {
+            users: RwLock<HashMap<String, User>>,
+        }
+
+        impl InMemoryUserRepository {
+            #[must_use]
+            pub fn new() -> Self {
+                Self::default()
+            }
+        }
+
+        #[async_trait]
+        impl UserRepository for InMemoryUserRepository {
+            async fn create_user(&self, user: User) -> Result<()> {
+                match self.users.write().await.entry(user.email().to_string()) {
+                    Entry::Occupied(entry) => Err(DuplicateEmail {
+                        email: entry.key().clone(),
+                    }
+                    .into()),
+                    Entry::Vacant(entry) => {
+                        entry.insert(user);
+                        Ok(())
+                    }
+                }
+            }
+
+            async fn get_user_by_email(&self, email: &Email) -> Result<Option<User>> {
+                Ok(self.users.read().await.get(email.as_str()).cloned())
+            }
+
+            async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>> {
+                Ok(self
+                    .users
+                    .read()
+                    .await
+                    .values()
+                    .find(|user| user.id() == id)
+                    .cloned())
+            }
+
+            async fn update_user(&self, user: User) -> Result<()> {
+                let mut users = self.users.write().await;
+                let Some(previous_email) = users
+                    .values()
+                    .find(|stored| stored.id() == user.id())
+                    .map(|stored| stored.email().to_string())
+                else {
+                    return Err(color_eyre::eyre::eyre!("User {} not found", user.id()));
+                };
+
+                // Re-keying under the same lock keeps the email change atomic
+                let email = user.email().to_string();
+                if email != previous_email && users.contains_key(&email) {
+                    return Err(DuplicateEmail { email }.into());
+                }
+                users.remove(&previous_email);
+                users.insert(email, user);
+                drop(users);
+                Ok(())
+            }
+
+            async fn delete_user(&self, id: &UserId) -> Result<bool> {
+                let mut users = self.users.write().await;
+                let before = users.len();
+                users.retain(|_, user| user.id() != id);
+                let deleted = users.len() < before;
+                drop(users);
+                Ok(deleted)
+            }
+
+            async fn record_totp_step(&self, user: &User, step: i64) -> Result<bool> {
+                let mut users = self.users.write().await;
+                let Some(stored) = users
+                    .get_mut(user.email().as_str())
+                    .filter(|stored| stored.id() == user.id())
+                    .filter(|stored| stored.totp().is_some_and(|totp| totp.is_unused_step(step)))
+                else {
+                    return Ok(false);
+                };
+                stored.record_totp_step(step);
+                drop(users);
+                Ok(true)
+            }
+        }
+
+        /// Contract tests shared by every user store; the SQL stores run
+        /// them against their own database.
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        pub(crate) mod tests {
+            use super::*;
+
+            pub(crate) fn user(email: &str) -> User {
+                User::new(email, "$argon2id$test".to_string(), "Ada", "Byron").unwrap()
+            }
+
+            fn duplicate_email(error: &color_eyre::Report) -> Option<&str> {
+                error.downcast_ref::<DuplicateEmail>().map(|duplicate| duplicate.email.as_str())
+            }
+
+            pub(crate) async fn round_trips_users(repository: &dyn UserRepository) {
+                let mut stored = user("ada@example.com");
+                repository.create_user(stored.clone()).await.unwrap();
+
+                let by_id = repository.get_user_by_id(stored.id()).await.unwrap().unwrap();
+                assert_eq!(by_id.email(), stored.email());
+                assert_eq!(by_id.password_hash().as_str(), stored.password_hash().as_str());
+                let by_email = repository.get_user_by_email(stored.email()).await.unwrap().unwrap();
+                assert_eq!(by_email.id(), stored.id());
+
+                stored.update_name("Augusta", "King").unwrap();
+                stored.change_email("augusta@example.com").unwrap();
+                stored.mark_email_verified();
+                repository.update_user(stored.clone()).await.unwrap();
+
+                let updated = repository.get_user_by_id(stored.id()).await.unwrap().unwrap();
+                assert_eq!(updated.first_name().as_str(), "Augusta");
+                assert_eq!(updated.last_name().as_str(), "King");
+                assert_eq!(updated.email().as_str(), "augusta@example.com");
+                assert!(updated.is_email_verified());
+                let old_email = Email::new("ada@example.com").unwrap();
+                assert!(repository.get_user_by_email(&old_email).await.unwrap().is_none());
+
+                assert!(repository.delete_user(stored.id()).await.unwrap());
+                assert!(repository.get_user_by_id(stored.id()).await.unwrap().is_none());
+                assert!(repository.get_user_by_email(stored.email()).await.unwrap().is_none());
+                assert!(!repository.delete_user(stored.id()).await.unwrap());
+            }
+
+            pub(crate) async fn rejects_duplicate_emails(repository: &dyn UserRepository) {
+                let first = user("ada@example.com");
+                repository.create_user(first.clone()).await.unwrap();
+
+                let error = repository.create_user(user("ada@example.com")).await.unwrap_err();
+                assert_eq!(duplicate_email(&error), Some("ada@example.com"));
+
+                let mut second = user("grace@example.com");
+                repository.create_user(second.clone()).await.unwrap();
+                second.change_email("ada@example.com").unwrap();
+                let error = repository.update_user(second.clone()).await.unwrap_err();
+                assert_eq!(duplicate_email(&error), Some("ada@example.com"));
+
+                // Neither user was touched by the rejected writes.
+                let stored = repository.get_user_by_email(first.email()).await.unwrap().unwrap();
+                assert_eq!(stored.id(), first.id());
+                let unchanged = repository.get_user_by_id(second.id()).await.unwrap().unwrap();
+                assert_eq!(unchanged.email().as_str(), "grace@example.com");
+            }
+
+            pub(crate) async fn records_each_totp_step_once(repository: &dyn UserRepository) {
+                let mut enrolled = user("ada@example.com");
+                enrolled.begin_totp_enrollment("JBSWY3DPEHPK3PXP".to_string());
+                enrolled.confirm_totp(10);
+                repository.create_user(enrolled.clone()).await.unwrap();
+                let without_totp = user("grace@example.com");
+                repository.create_user(without_totp.clone()).await.unwrap();
+
+                assert!(!repository.record_totp_step(&enrolled, 10).await.unwrap());
+                assert!(repository.record_totp_step(&enrolled, 11).await.unwrap());
+                // `enrolled` is now stale; the store still refuses the step.
+                assert!(!repository.record_totp_step(&enrolled, 11).await.unwrap());
+                assert!(!repository.record_totp_step(&enrolled, 9).await.unwrap());
+                assert!(!repository.record_totp_step(&without_totp, 11).await.unwrap());
+
+                let stored = repository.get_user_by_id(enrolled.id()).await.unwrap().unwrap();
+                assert_eq!(stored.totp().unwrap().last_used_step(), Some(11));
+                assert!(stored.is_mfa_enabled());
+            }
+
+            #[tokio::test]
+            async fn in_memory_records_each_totp_step_once() {
+                records_each_totp_step_once(&InMemoryUserRepository::new()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_round_trips_users() {
+                round_trips_users(&InMemoryUserRepository::new()).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory_rejects_duplicate_emails() {
+                rejects_duplicate_emails(&InMemoryUserRepository::new()).await;
+            }
+        }
+    }
+}
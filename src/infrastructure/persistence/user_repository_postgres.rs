@@ -0,0 +1,220 @@
+// PostgreSQL implementation of the UserRepository trait
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "postgres")] {
+        use async_trait::async_trait;
+        use chrono::{DateTime, Utc};
+        use color_eyre::Result;
+        use color_eyre::eyre::eyre;
+        use sqlx::PgPool;
+        use crate::domain::models::mfa::TotpCredential;
+        use crate::domain::models::user::User;
+        use crate::domain::repositories::user_repository::{DuplicateEmail, UserRepository};
+        use crate::domain::value_objects::{Email, UserId};
+
+        #[derive(sqlx::FromRow)]
+        struct UserRow {
+            id: String,
+            email: String,
+            password_hash: String,
+            first_name: String,
+            last_name: String,
+            email_verified: bool,
+            totp_secret: Option<String>,
+            totp_enabled: bool,
+            totp_last_step: Option<i64>,
+            created_at: DateTime<Utc>,
+            updated_at: DateTime<Utc>,
+        }
+
+        impl TryFrom<UserRow> for User {
+            type Error = color_eyre::Report;
+
+            fn try_from(row: UserRow) -> Result<Self> {
+                let totp = row.totp_secret.map(|secret| {
+                    TotpCredential::restore(secret, row.totp_enabled, row.totp_last_step)
+                });
+                User::restore(
+                    &row.id,
+                    &row.email,
+                    row.password_hash,
+                    &row.first_name,
+                    &row.last_name,
+                    row.email_verified,
+                    totp,
+                    row.created_at,
+                    row.updated_at,
+                )
+                .map_err(|e| eyre!("Invalid user row {}: {e}", row.id))
+            }
+        }
+
+        pub struct PostgresUserRepository {
+            pool: PgPool,
+        }
+
+        impl PostgresUserRepository {
+            #[must_use]
+            pub fn new(pool: PgPool) -> Self {
+                Self { pool }
+            }
+        }
+
+        fn is_duplicate_email(error: &sqlx::Error) -> bool {
+            match error {
+                sqlx::Error::Database(db) => db.constraint() == Some("users_email_key"),
+                _ => false,
+            }
+        }
+
+        #[async_trait]
+        impl UserRepository for PostgresUserRepository {
+            async fn create_user(&self, user: User) -> Result<()> {
+                sqlx::query(
+                    "INSERT INTO users
+                        (id, email, password_hash, first_name, last_name, email_verified,
+                         totp_secret, totp_enabled, totp_last_step, created_at, updated_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+                )
+                .bind(user.id().as_str())
+                .bind(user.email().as_str())
+                .bind(user.password_hash().as_str())
+                .bind(user.first_name().as_str())
+                .bind(user.last_name().as_str())
+                .bind(user.is_email_verified())
+                .bind(user.totp().map(TotpCredential::secret))
+                .bind(user.totp().is_some_and(TotpCredential::is_enabled))
+                .bind(user.totp().and_then(TotpCredential::last_used_step))
+                .bind(user.created_at())
+                .bind(user.updated_at())
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    if is_duplicate_email(&e) {
+                        DuplicateEmail {
+                            email: user.email().to_string(),
+                        }
+                        .into()
+                    } else {
+                        color_eyre::Report::from(e)
+                    }
+                })?;
+                Ok(())
+            }
+
+            async fn get_user_by_email(&self, email: &Email) -> Result<Option<User>> {
+                sqlx::query_as::<_, UserRow>(
+                    "SELECT id, email, password_hash, first_name, last_name, email_verified,
+                            totp_secret, totp_enabled, totp_last_step, created_at, updated_at
+                     FROM users WHERE email = $1",
+                )
+                .bind(email.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(User::try_from)
+                .transpose()
+            }
+
+            async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>> {
+                sqlx::query_as::<_, UserRow>(
+                    "SELECT id, email, password_hash, first_name, last_name, email_verified,
+                            totp_secret, totp_enabled, totp_last_step, created_at, updated_at
+                     FROM users WHERE id = $1",
+                )
+                .bind(id.as_str())
+                .fetch_optional(&self.pool)
+                .await?
+                .map(User::try_from)
+                .transpose()
+            }
+
+            async fn update_user(&self, user: User) -> Result<()> {
+                let result = sqlx::query(
+                    "UPDATE users
+                     SET email = $1, password_hash = $2, first_name = $3, last_name = $4, email_verified = $5,
+                         totp_secret = $6, totp_enabled = $7, totp_last_step = $8, updated_at = $9
+                     WHERE id = $10",
+                )
+                .bind(user.email().as_str())
+                .bind(user.password_hash().as_str())
+                .bind(user.first_name().as_str())
+                .bind(user.last_name().as_str())
+                .bind(user.is_email_verified())
+                .bind(user.totp().map(TotpCredential::secret))
+                .bind(user.totp().is_some_and(TotpCredential::is_enabled))
+                .bind(user.totp().and_then(TotpCredential::last_used_step))
+                .bind(user.updated_at())
+                .bind(user.id().as_str())
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    if is_duplicate_email(&e) {
+                        DuplicateEmail {
+                            email: user.email().to_string(),
+                        }
+                        .into()
+                    } else {
+                        color_eyre::Report::from(e)
+                    }
+                })?;
+
+                if result.rows_affected() == 0 {
+                    return Err(eyre!("User {} not found", user.id()));
+                }
+                Ok(())
+            }
+
+            async fn delete_user(&self, id: &UserId) -> Result<bool> {
+                let result = sqlx::query("DELETE FROM users WHERE id = $1")
+                    .bind(id.as_str())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn record_totp_step(&self, user: &User, step: i64) -> Result<bool> {
+                let result = sqlx::query(
+                    "UPDATE users SET totp_last_step = $1, updated_at = $2
+                     WHERE id = $3 AND totp_secret IS NOT NULL
+                       AND (totp_last_step IS NULL OR totp_last_step < $1)",
+                )
+                .bind(step)
+                .bind(Utc::now())
+                .bind(user.id().as_str())
+                .execute(&self.pool)
+                .await?;
+                Ok(result.rows_affected() > 0)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use crate::infrastructure::persistence::repositories::Repositories;
+            use crate::infrastructure::persistence::user_repository_in_memory::tests::{
+                records_each_totp_step_once, rejects_duplicate_emails, round_trips_users,
+            };
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn round_trips_users_through_the_migrated_schema() {
+                let repositories = Repositories::postgres_isolated().await;
+                round_trips_users(repositories.users.as_ref()).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn maps_the_unique_email_constraint_to_duplicate_email() {
+                let repositories = Repositories::postgres_isolated().await;
+                rejects_duplicate_emails(repositories.users.as_ref()).await;
+            }
+
+            #[tokio::test]
+            #[ignore = "needs a throwaway Postgres database at TEST_POSTGRES_URL"]
+            async fn records_each_totp_step_once_with_a_conditional_update() {
+                let repositories = Repositories::postgres_isolated().await;
+                records_each_totp_step_once(repositories.users.as_ref()).await;
+            }
+        }
+    }
+}
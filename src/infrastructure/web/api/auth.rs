@@ -3,17 +3,28 @@ use cfg_if::cfg_if;
 cfg_if! {
     if #[cfg(feature = "ssr")] {
         use crate::{domain::models::app_state::AppState};
-        use axum::{extract::State, http::StatusCode, response::Json, routing::post, Router};
+        use axum::{
+            extract::{rejection::JsonRejection, State},
+            response::Json,
+            routing::post,
+            Router,
+        };
         use crate::application::user_service::UserService;
         use crate::domain::models::user::CreateUserRequest;
         use crate::domain::models::user::AuthResponse;
-        use crate::domain::models::user::LoginRequest;
+        use crate::domain::models::user::{LoginRequest, LoginResponse};
+        use crate::domain::models::mfa::MfaLoginRequest;
         use std::sync::Arc;
         use crate::domain::models::user::RefreshTokenRequest;
         use crate::domain::models::user::RefreshTokenResponse;
-        use crate::infrastructure::auth::jwt_service::AuthService;
+        use crate::domain::models::user::LogoutRequest;
+        use crate::domain::models::user::{
+            ForgotPasswordRequest, RegisterResponse, ResendVerificationRequest, ResetPasswordRequest,
+            UserProfile, VerifyEmailRequest,
+        };
+        use serde_json::{json, Value};
         use crate::infrastructure::errors::web_errors::WebError;
-        use crate::application::errors::user_service_errors::UserServiceError;
+        use crate::infrastructure::web::middleware::client_info::ClientInfo;
 
         pub fn auth_routes<T>(app_state: Arc<AppState<T>>) -> Router
         where
@@ -22,62 +33,85 @@ cfg_if! {
             Router::new()
                 .route("/register", post(register_handler))
                 .route("/login", post(login_handler))
+                .route("/login/mfa", post(mfa_login_handler::<T>))
                 .route("/refresh", post(refresh_token_handler::<T>))
+                .route("/logout", post(logout_handler::<T>))
+                .route("/verify-email", post(verify_email_handler::<T>))
+                .route("/resend-verification", post(resend_verification_handler::<T>))
+                .route("/forgot-password", post(forgot_password_handler::<T>))
+                .route("/reset-password", post(reset_password_handler::<T>))
                 .with_state(app_state)
         }
         async fn register_handler<T>(
             State(state): State<Arc<AppState<T>>>,
-            Json(request): Json<CreateUserRequest>,
-        ) -> Result<Json<AuthResponse>, StatusCode>
+            client: ClientInfo,
+            payload: Result<Json<CreateUserRequest>, JsonRejection>,
+        ) -> Result<Json<RegisterResponse>, WebError>
         where
             T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
         {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
 
-            // Your service should return Result<AuthResponse, Error>, not Result<Json<AuthResponse>, Error>
-            let auth_response: Json<AuthResponse> = state
+            let auth_response = state
                 .user_service()
-                .register_user(request, &state.app_config().auth.jwt_secret)
-                .await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .register_user(request, &client, state.auth_service())
+                .await?;
 
-            // Wrap the response in Json at the handler level
             Ok(auth_response)
         }
         async fn login_handler<T>(
             State(state): State<Arc<AppState<T>>>,
-            Json(request): Json<LoginRequest>,
-        ) -> Result<Json<AuthResponse>, StatusCode>
+            client: ClientInfo,
+            payload: Result<Json<LoginRequest>, JsonRejection>,
+        ) -> Result<Json<LoginResponse>, WebError>
         where
             T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
-
          {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let result = state
+                .user_service()
+                .authenticate_user(
+                    &request.email,
+                    &request.password,
+                    &client,
+                    state.auth_service(),
+                )
+                .await?;
+            Ok(result)
+        }
+
+        async fn mfa_login_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            client: ClientInfo,
+            payload: Result<Json<MfaLoginRequest>, JsonRejection>,
+        ) -> Result<Json<AuthResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
             let result = state
                 .user_service()
-                .authenticate_user(&request.email, &request.password, &state.app_config().auth.jwt_secret)
-                .await
-                .map_err(|_| StatusCode::UNAUTHORIZED)?;
+                .complete_mfa_login(request, &client, state.auth_service())
+                .await?;
             Ok(result)
         }
 
         async fn refresh_token_handler<T>(
             State(state): State<Arc<AppState<T>>>,
-            Json(request): Json<RefreshTokenRequest>,
+            client: ClientInfo,
+            payload: Result<Json<RefreshTokenRequest>, JsonRejection>,
         ) -> Result<Json<RefreshTokenResponse>, WebError>
         where
             T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
         {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
 
-            let token_pair = AuthService::refresh_access_token(
-                &request.refresh_token,
-                &state.app_config().auth.jwt_secret,
-                state.user_service(),
-            )
-            .await
-            .map_err(|e| WebError::UserService {
-                source: UserServiceError::AuthServiceError {
-                    source: Box::new(e),
-                },
-            })?;
+            let token_pair = state
+                .auth_service()
+                .refresh_access_token(&request.refresh_token, &client, state.user_service())
+                .await?;
 
             Ok(Json(RefreshTokenResponse {
                 access_token: token_pair.access_token,
@@ -86,5 +120,94 @@ cfg_if! {
                 expires_in: token_pair.expires_in,
             }))
         }
+
+        async fn logout_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            payload: Result<Json<LogoutRequest>, JsonRejection>,
+        ) -> Result<Json<Value>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            // Revoking the whole family also invalidates any rotated copies of
+            // the token that may still be held by the client.
+            state
+                .auth_service()
+                .revoke_refresh_token(&request.refresh_token, state.user_service())
+                .await?;
+
+            Ok(Json(json!({ "message": "Logged out" })))
+        }
+
+        async fn verify_email_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            payload: Result<Json<VerifyEmailRequest>, JsonRejection>,
+        ) -> Result<Json<UserProfile>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let profile = state
+                .user_service()
+                .verify_email(&request.token, state.auth_service())
+                .await?;
+            Ok(Json(profile))
+        }
+
+        async fn resend_verification_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            payload: Result<Json<ResendVerificationRequest>, JsonRejection>,
+        ) -> Result<Json<Value>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state
+                .user_service()
+                .resend_verification_email(&request.email, state.auth_service())
+                .await?;
+
+            // Same answer whether or not the address has an account
+            Ok(Json(json!({
+                "message": "If an unverified account exists for this address, a new verification email has been sent"
+            })))
+        }
+
+        async fn forgot_password_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            payload: Result<Json<ForgotPasswordRequest>, JsonRejection>,
+        ) -> Result<Json<Value>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state.user_service().request_password_reset(&request.email).await;
+
+            // Same answer, returned before any lookup, whether or not the
+            // address has an account
+            Ok(Json(json!({
+                "message": "If an account exists for this address, a password reset email has been sent"
+            })))
+        }
+
+        async fn reset_password_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            payload: Result<Json<ResetPasswordRequest>, JsonRejection>,
+        ) -> Result<Json<Value>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state.user_service().reset_password(request).await?;
+
+            Ok(Json(json!({
+                "message": "Your password has been reset. Please sign in with your new password"
+            })))
+        }
     }
 }
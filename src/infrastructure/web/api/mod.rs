@@ -1 +1,4 @@
 pub mod auth;
+pub mod transactions;
+pub mod users;
+pub mod well_known;
@@ -0,0 +1,225 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::application::transaction_service::TransactionService;
+        use crate::application::user_service::UserService;
+        use crate::domain::models::app_state::AppState;
+        use crate::domain::models::personal_access_token::TokenScope;
+        use crate::domain::models::transaction::{
+            CreateTransactionRequest, TransactionListResponse, TransactionResponse,
+        };
+        use crate::domain::repositories::transaction_repository::{TransactionQuery, TransactionSort};
+        use crate::domain::value_objects::{AccountName, Category, TransactionId};
+        use crate::infrastructure::errors::web_errors::WebError;
+        use crate::infrastructure::web::middleware::authentication::{require_auth, AuthenticatedUser};
+        use axum::{
+            extract::{
+                rejection::{JsonRejection, PathRejection, QueryRejection},
+                Path, Query, State,
+            },
+            http::StatusCode,
+            middleware,
+            response::Json,
+            routing::get,
+            Router,
+        };
+        use chrono::NaiveDate;
+        use serde::Deserialize;
+        use std::sync::Arc;
+
+        const MAX_SEARCH_LENGTH: usize = 100;
+
+        pub fn transaction_routes<T>(app_state: Arc<AppState<T>>) -> Router
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            Router::new()
+                .route(
+                    "/",
+                    get(list_transactions_handler::<T>).post(create_transaction_handler::<T>),
+                )
+                .route(
+                    "/{id}",
+                    get(get_transaction_handler::<T>)
+                        .put(update_transaction_handler::<T>)
+                        .delete(delete_transaction_handler::<T>),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    require_auth::<T>,
+                ))
+                .with_state(app_state)
+        }
+
+        /// Query string accepted by `GET /api/transactions`.
+        #[derive(Debug, Default, Deserialize)]
+        pub struct ListTransactionsParams {
+            pub from: Option<NaiveDate>,
+            pub to: Option<NaiveDate>,
+            pub account: Option<String>,
+            pub category: Option<String>,
+            pub min_amount: Option<i64>,
+            pub max_amount: Option<i64>,
+            pub q: Option<String>,
+            pub sort: Option<String>,
+            pub limit: Option<u32>,
+            pub cursor: Option<String>,
+        }
+
+        impl ListTransactionsParams {
+            fn into_query(self) -> Result<TransactionQuery, WebError> {
+                let account = self
+                    .account
+                    .as_deref()
+                    .map(AccountName::new)
+                    .transpose()
+                    .map_err(|e| WebError::InvalidRequest { message: e.to_string() })?;
+                let category = self
+                    .category
+                    .as_deref()
+                    .map(Category::new)
+                    .transpose()
+                    .map_err(|e| WebError::InvalidRequest { message: e.to_string() })?;
+                let sort = self
+                    .sort
+                    .as_deref()
+                    .map(str::parse::<TransactionSort>)
+                    .transpose()
+                    .map_err(|e| WebError::InvalidRequest { message: e.to_string() })?
+                    .unwrap_or_default();
+
+                let search = self
+                    .q
+                    .map(|q| q.trim().to_string())
+                    .filter(|q| !q.is_empty());
+                if search
+                    .as_ref()
+                    .is_some_and(|q| q.chars().count() > MAX_SEARCH_LENGTH)
+                {
+                    return Err(WebError::InvalidRequest {
+                        message: format!("Search text is too long (max {MAX_SEARCH_LENGTH} characters)"),
+                    });
+                }
+
+                Ok(TransactionQuery {
+                    booked_from: self.from,
+                    booked_to: self.to,
+                    account,
+                    category,
+                    min_amount_minor: self.min_amount,
+                    max_amount_minor: self.max_amount,
+                    search,
+                    sort,
+                    limit: self.limit,
+                    continuation: self.cursor,
+                })
+            }
+        }
+
+        fn parse_transaction_id(path: Result<Path<String>, PathRejection>) -> Result<TransactionId, WebError> {
+            let Path(id) = path.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+            TransactionId::new(id).map_err(|e| WebError::InvalidRequest { message: e.to_string() })
+        }
+
+        async fn list_transactions_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            params: Result<Query<ListTransactionsParams>, QueryRejection>,
+        ) -> Result<Json<TransactionListResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            user.require_scope(TokenScope::TransactionsRead)?;
+            let Query(params) = params.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let page = state
+                .transaction_service()
+                .list_transactions(&user.user_id, params.into_query()?)
+                .await?;
+
+            Ok(Json(TransactionListResponse {
+                items: page.items.into_iter().map(TransactionResponse::from).collect(),
+                continuation: page.continuation,
+            }))
+        }
+
+        async fn create_transaction_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<CreateTransactionRequest>, JsonRejection>,
+        ) -> Result<(StatusCode, Json<TransactionResponse>), WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            user.require_scope(TokenScope::TransactionsWrite)?;
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let transaction = state
+                .transaction_service()
+                .create_transaction(&user.user_id, request)
+                .await?;
+
+            Ok((StatusCode::CREATED, Json(transaction.into())))
+        }
+
+        async fn get_transaction_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            path: Result<Path<String>, PathRejection>,
+        ) -> Result<Json<TransactionResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            user.require_scope(TokenScope::TransactionsRead)?;
+            let id = parse_transaction_id(path)?;
+
+            let transaction = state
+                .transaction_service()
+                .get_transaction(&user.user_id, &id)
+                .await?;
+
+            Ok(Json(transaction.into()))
+        }
+
+        async fn update_transaction_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            path: Result<Path<String>, PathRejection>,
+            payload: Result<Json<CreateTransactionRequest>, JsonRejection>,
+        ) -> Result<Json<TransactionResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            user.require_scope(TokenScope::TransactionsWrite)?;
+            let id = parse_transaction_id(path)?;
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let transaction = state
+                .transaction_service()
+                .update_transaction(&user.user_id, &id, request)
+                .await?;
+
+            Ok(Json(transaction.into()))
+        }
+
+        async fn delete_transaction_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            path: Result<Path<String>, PathRejection>,
+        ) -> Result<StatusCode, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            user.require_scope(TokenScope::TransactionsWrite)?;
+            let id = parse_transaction_id(path)?;
+
+            state
+                .transaction_service()
+                .delete_transaction(&user.user_id, &id)
+                .await?;
+
+            Ok(StatusCode::NO_CONTENT)
+        }
+    }
+}
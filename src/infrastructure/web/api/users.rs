@@ -0,0 +1,301 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::application::user_service::UserService;
+        use crate::domain::models::app_state::AppState;
+        use crate::domain::models::mfa::{
+            ConfirmTotpRequest, DisableMfaRequest, RecoveryCodesResponse,
+            RegenerateRecoveryCodesRequest, StartTotpEnrollmentRequest, TotpEnrollmentResponse,
+        };
+        use crate::domain::models::personal_access_token::{
+            AccessTokenSummary, CreateAccessTokenRequest, CreatedAccessTokenResponse,
+        };
+        use crate::domain::models::session::SessionSummary;
+        use crate::domain::models::user::{
+            AuthResponse, ChangeEmailRequest, ChangePasswordRequest, DeleteAccountRequest,
+            UpdateProfileRequest, UserProfile,
+        };
+        use crate::infrastructure::errors::web_errors::WebError;
+        use crate::infrastructure::web::middleware::authentication::{require_session, AuthenticatedUser};
+        use crate::infrastructure::web::middleware::client_info::ClientInfo;
+        use axum::{
+            extract::{
+                rejection::{JsonRejection, PathRejection},
+                Path, State,
+            },
+            http::StatusCode,
+            middleware,
+            response::Json,
+            routing::{delete, get, post, put},
+            Router,
+        };
+        use std::sync::Arc;
+
+        /// Account management for the signed-in user, mounted at `/api/users`.
+        /// Personal access tokens are refused here, so a leaked token cannot
+        /// take over the account or mint more tokens.
+        pub fn user_routes<T>(app_state: Arc<AppState<T>>) -> Router
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            Router::new()
+                .route(
+                    "/me",
+                    get(get_profile_handler::<T>)
+                        .patch(update_profile_handler::<T>)
+                        .delete(delete_account_handler::<T>),
+                )
+                .route("/me/password", put(change_password_handler::<T>))
+                .route("/me/email", put(change_email_handler::<T>))
+                .route("/me/mfa", delete(disable_mfa_handler::<T>))
+                .route("/me/mfa/totp", post(start_totp_enrollment_handler::<T>))
+                .route("/me/mfa/totp/confirm", post(confirm_totp_enrollment_handler::<T>))
+                .route("/me/mfa/recovery-codes", post(regenerate_recovery_codes_handler::<T>))
+                .route(
+                    "/me/tokens",
+                    get(list_access_tokens_handler::<T>).post(create_access_token_handler::<T>),
+                )
+                .route("/me/tokens/{id}", delete(revoke_access_token_handler::<T>))
+                .route("/me/sessions", get(list_sessions_handler::<T>))
+                .route("/me/sessions/{id}", delete(revoke_session_handler::<T>))
+                .route_layer(middleware::from_fn_with_state(
+                    Arc::clone(&app_state),
+                    require_session::<T>,
+                ))
+                .with_state(app_state)
+        }
+
+        async fn get_profile_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+        ) -> Result<Json<UserProfile>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let profile = state.user_service().get_profile(&user.user_id).await?;
+            Ok(Json(profile))
+        }
+
+        async fn update_profile_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<UpdateProfileRequest>, JsonRejection>,
+        ) -> Result<Json<UserProfile>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let profile = state
+                .user_service()
+                .update_profile(&user.user_id, request)
+                .await?;
+            Ok(Json(profile))
+        }
+
+        async fn change_password_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            client: ClientInfo,
+            payload: Result<Json<ChangePasswordRequest>, JsonRejection>,
+        ) -> Result<Json<AuthResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let response = state
+                .user_service()
+                .change_password(&user.user_id, request, &client, state.auth_service())
+                .await?;
+            Ok(response)
+        }
+
+        async fn change_email_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            client: ClientInfo,
+            payload: Result<Json<ChangeEmailRequest>, JsonRejection>,
+        ) -> Result<Json<AuthResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let response = state
+                .user_service()
+                .change_email(&user.user_id, request, &client, state.auth_service())
+                .await?;
+            Ok(response)
+        }
+
+        async fn delete_account_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<DeleteAccountRequest>, JsonRejection>,
+        ) -> Result<StatusCode, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state
+                .user_service()
+                .delete_account(&user.user_id, request)
+                .await?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+
+        async fn start_totp_enrollment_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<StartTotpEnrollmentRequest>, JsonRejection>,
+        ) -> Result<Json<TotpEnrollmentResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let enrollment = state
+                .user_service()
+                .start_totp_enrollment(&user.user_id, request)
+                .await?;
+            Ok(Json(enrollment))
+        }
+
+        async fn confirm_totp_enrollment_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<ConfirmTotpRequest>, JsonRejection>,
+        ) -> Result<Json<RecoveryCodesResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let recovery_codes = state
+                .user_service()
+                .confirm_totp_enrollment(&user.user_id, request)
+                .await?;
+            Ok(Json(recovery_codes))
+        }
+
+        async fn regenerate_recovery_codes_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<RegenerateRecoveryCodesRequest>, JsonRejection>,
+        ) -> Result<Json<RecoveryCodesResponse>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let recovery_codes = state
+                .user_service()
+                .regenerate_recovery_codes(&user.user_id, request)
+                .await?;
+            Ok(Json(recovery_codes))
+        }
+
+        async fn disable_mfa_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<DisableMfaRequest>, JsonRejection>,
+        ) -> Result<StatusCode, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state
+                .user_service()
+                .disable_mfa(&user.user_id, request)
+                .await?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+
+        async fn list_access_tokens_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+        ) -> Result<Json<Vec<AccessTokenSummary>>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let tokens = state.user_service().list_access_tokens(&user.user_id).await?;
+            Ok(Json(tokens))
+        }
+
+        async fn create_access_token_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            payload: Result<Json<CreateAccessTokenRequest>, JsonRejection>,
+        ) -> Result<(StatusCode, Json<CreatedAccessTokenResponse>), WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Json(request) = payload.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            let created = state
+                .user_service()
+                .create_access_token(&user.user_id, request)
+                .await?;
+            Ok((StatusCode::CREATED, Json(created)))
+        }
+
+        async fn revoke_access_token_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            path: Result<Path<String>, PathRejection>,
+        ) -> Result<StatusCode, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Path(id) = path.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state
+                .user_service()
+                .revoke_access_token(&user.user_id, &id)
+                .await?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+
+        async fn list_sessions_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+        ) -> Result<Json<Vec<SessionSummary>>, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let sessions = state
+                .user_service()
+                .list_active_sessions(&user.user_id, user.session_id.as_deref())
+                .await?;
+            Ok(Json(sessions))
+        }
+
+        /// Signs the session out and tells its open WebSocket connections to
+        /// drop their tokens. Access tokens it already holds are refused from
+        /// then on.
+        async fn revoke_session_handler<T>(
+            State(state): State<Arc<AppState<T>>>,
+            user: AuthenticatedUser,
+            path: Result<Path<String>, PathRejection>,
+        ) -> Result<StatusCode, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let Path(id) = path.map_err(|e| WebError::InvalidRequest { message: e.body_text() })?;
+
+            state
+                .user_service()
+                .revoke_session(&user.user_id, &id)
+                .await?;
+            state
+                .connection_manager()
+                .force_logout(user.user_id.as_str(), &id)
+                .await;
+            Ok(StatusCode::NO_CONTENT)
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::infrastructure::auth::jwt_service::AuthService;
+        use axum::{
+            extract::State,
+            http::header::CACHE_CONTROL,
+            response::{IntoResponse, Json},
+            routing::get,
+            Router,
+        };
+        use jsonwebtoken::jwk::JwkSet;
+
+        /// Public discovery documents, mounted at `/.well-known`.
+        pub fn well_known_routes(auth_service: AuthService) -> Router {
+            Router::new()
+                .route("/jwks.json", get(jwks_handler))
+                .with_state(auth_service)
+        }
+
+        /// The public keys access tokens can be verified with. Verifiers
+        /// should refetch it when they see an unknown `kid`, so the cache
+        /// lifetime only delays picking up a newly rotated key.
+        async fn jwks_handler(State(auth_service): State<AuthService>) -> impl IntoResponse {
+            let jwks: JwkSet = auth_service.jwks();
+            ([(CACHE_CONTROL, "public, max-age=300")], Json(jwks))
+        }
+    }
+}
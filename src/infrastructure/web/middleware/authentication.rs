@@ -0,0 +1,242 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::application::errors::user_service_errors::UserServiceError;
+        use crate::application::user_service::UserService;
+        use crate::domain::models::app_state::AppState;
+        use crate::domain::models::personal_access_token::TokenScope;
+        use crate::domain::value_objects::{Email, UserId};
+        use crate::infrastructure::auth::jwt_service::AuthService;
+        use crate::infrastructure::errors::web_errors::WebError;
+        use axum::{
+            extract::{FromRequestParts, Request, State},
+            http::{header::AUTHORIZATION, request::Parts, HeaderMap},
+            middleware::Next,
+            response::Response,
+        };
+        use std::sync::Arc;
+
+        /// The caller identity resolved from a verified access token or
+        /// personal access token.
+        ///
+        /// Use it as a handler argument to require authentication:
+        ///
+        /// ```ignore
+        /// async fn handler(user: AuthenticatedUser) -> String {
+        ///     user.user_id.to_string()
+        /// }
+        /// ```
+        #[derive(Debug, Clone)]
+        pub struct AuthenticatedUser {
+            pub user_id: UserId,
+            pub email: Email,
+            /// Scopes of the personal access token the request carried, or
+            /// `None` for a signed-in session, which is not restricted.
+            pub token_scopes: Option<Vec<TokenScope>>,
+            /// The signed-in session the access token was issued to. `None`
+            /// for personal access tokens.
+            pub session_id: Option<String>,
+        }
+
+        impl AuthenticatedUser {
+            /// Verifies the `Authorization: Bearer <token>` header. Tokens
+            /// starting with `ftpat_` are looked up as personal access
+            /// tokens; anything else must be a signed access token.
+            ///
+            /// # Errors
+            ///
+            /// Returns `WebError::MissingAuth` if the header is absent and
+            /// `WebError::InvalidToken` if it is malformed, expired, revoked,
+            /// not an access token, carries invalid claims or belongs to a
+            /// session that has been signed out.
+            pub async fn from_headers<T>(headers: &HeaderMap, state: &AppState<T>) -> Result<Self, WebError>
+            where
+                T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+            {
+                let header = headers
+                    .get(AUTHORIZATION)
+                    .ok_or(WebError::MissingAuth)?
+                    .to_str()
+                    .map_err(|_| WebError::InvalidToken)?;
+
+                let token = header
+                    .strip_prefix("Bearer ")
+                    .or_else(|| header.strip_prefix("bearer "))
+                    .map(str::trim)
+                    .filter(|token| !token.is_empty())
+                    .ok_or(WebError::InvalidToken)?;
+
+                if token.starts_with(AuthService::PERSONAL_ACCESS_TOKEN_PREFIX) {
+                    let (user, record) = state
+                        .user_service()
+                        .authenticate_access_token(token)
+                        .await
+                        .map_err(|e| match e {
+                            UserServiceError::InvalidAccessToken => WebError::InvalidToken,
+                            other => WebError::from(other),
+                        })?;
+                    return Ok(Self {
+                        user_id: user.id().clone(),
+                        email: user.email().clone(),
+                        token_scopes: Some(record.scopes().to_vec()),
+                        session_id: None,
+                    });
+                }
+
+                let claims = state.auth_service().verify_access_token(token)
+                    .map_err(|_| WebError::InvalidToken)?;
+                let user_id = UserId::new(claims.sub).map_err(|_| WebError::InvalidToken)?;
+
+                // Revoking a session must also end its unexpired access tokens
+                if let Some(session_id) = &claims.family_id
+                    && !state.user_service().is_session_active(&user_id, session_id).await?
+                {
+                    return Err(WebError::InvalidToken);
+                }
+
+                Ok(Self {
+                    user_id,
+                    email: Email::new(&claims.email).map_err(|_| WebError::InvalidToken)?,
+                    token_scopes: None,
+                    session_id: claims.family_id,
+                })
+            }
+
+            /// Whether the request came from a signed-in session rather than
+            /// a personal access token.
+            #[must_use]
+            pub fn is_session(&self) -> bool {
+                self.token_scopes.is_none()
+            }
+
+            /// Sessions pass; personal access tokens need `scope`.
+            ///
+            /// # Errors
+            ///
+            /// Returns `WebError::InsufficientScope` for a token without it.
+            pub fn require_scope(&self, scope: TokenScope) -> Result<(), WebError> {
+                match &self.token_scopes {
+                    Some(scopes) if !scopes.contains(&scope) => {
+                        Err(WebError::InsufficientScope { scope })
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        impl<T> FromRequestParts<Arc<AppState<T>>> for AuthenticatedUser
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            type Rejection = WebError;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                state: &Arc<AppState<T>>,
+            ) -> Result<Self, Self::Rejection> {
+                // Routers guarded by `require_auth` have already verified the token.
+                if let Some(user) = parts.extensions.get::<AuthenticatedUser>() {
+                    return Ok(user.clone());
+                }
+
+                Self::from_headers(&parts.headers, state).await
+            }
+        }
+
+        /// Middleware that rejects unauthenticated requests for a whole router.
+        ///
+        /// ```ignore
+        /// Router::new()
+        ///     .route("/", get(handler))
+        ///     .route_layer(axum::middleware::from_fn_with_state(state.clone(), require_auth::<T>))
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns the same errors as [`AuthenticatedUser::from_headers`].
+        pub async fn require_auth<T>(
+            State(state): State<Arc<AppState<T>>>,
+            mut request: Request,
+            next: Next,
+        ) -> Result<Response, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let user = AuthenticatedUser::from_headers(request.headers(), &state).await?;
+            request.extensions_mut().insert(user);
+
+            Ok(next.run(request).await)
+        }
+
+        /// Like [`require_auth`], but also rejects personal access tokens,
+        /// for routers that manage the account itself.
+        ///
+        /// # Errors
+        ///
+        /// Returns the same errors as [`AuthenticatedUser::from_headers`],
+        /// and `WebError::SessionRequired` for a personal access token.
+        pub async fn require_session<T>(
+            State(state): State<Arc<AppState<T>>>,
+            mut request: Request,
+            next: Next,
+        ) -> Result<Response, WebError>
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            let user = AuthenticatedUser::from_headers(request.headers(), &state).await?;
+            if !user.is_session() {
+                return Err(WebError::SessionRequired);
+            }
+            request.extensions_mut().insert(user);
+
+            Ok(next.run(request).await)
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        mod tests {
+            use super::*;
+
+            fn user(token_scopes: Option<Vec<TokenScope>>) -> AuthenticatedUser {
+                AuthenticatedUser {
+                    user_id: UserId::generate(),
+                    email: Email::new("ada@example.com").unwrap(),
+                    token_scopes,
+                    session_id: None,
+                }
+            }
+
+            #[test]
+            fn sessions_pass_every_scope_check() {
+                let session = user(None);
+
+                assert!(session.is_session());
+                for scope in TokenScope::ALL {
+                    assert!(session.require_scope(scope).is_ok());
+                }
+            }
+
+            #[test]
+            fn access_tokens_need_the_requested_scope() {
+                let read_only = user(Some(vec![TokenScope::TransactionsRead]));
+
+                assert!(!read_only.is_session());
+                assert!(read_only.require_scope(TokenScope::TransactionsRead).is_ok());
+                assert!(matches!(
+                    read_only.require_scope(TokenScope::TransactionsWrite),
+                    Err(WebError::InsufficientScope { scope: TokenScope::TransactionsWrite })
+                ));
+            }
+
+            #[test]
+            fn access_tokens_without_scopes_are_refused_everything() {
+                let unscoped = user(Some(Vec::new()));
+
+                for scope in TokenScope::ALL {
+                    assert!(unscoped.require_scope(scope).is_err());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use crate::application::user_service::UserService;
+        use crate::domain::models::app_state::AppState;
+        use axum::extract::{ConnectInfo, FromRequestParts};
+        use axum::http::{header::USER_AGENT, request::Parts};
+        use std::convert::Infallible;
+        use std::net::{IpAddr, SocketAddr};
+        use std::sync::Arc;
+
+        /// Details about the client making the request.
+        ///
+        /// The address comes from the TCP connection, so the server must be
+        /// started with `into_make_service_with_connect_info::<SocketAddr>()`.
+        /// Behind a reverse proxy, set `ServerConfig::trust_forwarded_for` to
+        /// take the last `X-Forwarded-For` entry instead, which is the
+        /// address the proxy itself saw.
+        #[derive(Debug, Clone, Default)]
+        pub struct ClientInfo {
+            pub ip: Option<IpAddr>,
+            /// The `User-Agent` header, shown to the user in their session list.
+            pub user_agent: Option<String>,
+        }
+
+        impl ClientInfo {
+            /// Earlier entries are whatever the client chose to send, so only
+            /// the one appended by our proxy can be trusted.
+            fn forwarded_for(parts: &Parts) -> Option<IpAddr> {
+                parts
+                    .headers
+                    .get("x-forwarded-for")?
+                    .to_str()
+                    .ok()?
+                    .rsplit(',')
+                    .next()?
+                    .trim()
+                    .parse()
+                    .ok()
+            }
+        }
+
+        impl<T> FromRequestParts<Arc<AppState<T>>> for ClientInfo
+        where
+            T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+        {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                state: &Arc<AppState<T>>,
+            ) -> Result<Self, Self::Rejection> {
+                let forwarded = state
+                    .app_config()
+                    .server
+                    .trust_forwarded_for
+                    .then(|| Self::forwarded_for(parts))
+                    .flatten();
+                let connected = parts
+                    .extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip());
+
+                let user_agent = parts
+                    .headers
+                    .get(USER_AGENT)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string);
+
+                Ok(Self {
+                    ip: forwarded.or(connected),
+                    user_agent,
+                })
+            }
+        }
+
+        #[cfg(test)]
+        #[allow(clippy::unwrap_used)]
+        mod tests {
+            use super::*;
+            use axum::http::Request;
+
+            fn parts(forwarded_for: &str) -> Parts {
+                Request::builder()
+                    .header("x-forwarded-for", forwarded_for)
+                    .body(())
+                    .unwrap()
+                    .into_parts()
+                    .0
+            }
+
+            #[test]
+            fn takes_the_address_our_proxy_appended() {
+                let ip = ClientInfo::forwarded_for(&parts("203.0.113.7"));
+
+                assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+            }
+
+            #[test]
+            fn ignores_addresses_the_client_sent() {
+                // A client dodging the per-IP throttle prepends a fresh
+                // address to every request; the proxy appends the real one.
+                let ip = ClientInfo::forwarded_for(&parts("198.51.100.1, 203.0.113.7"));
+
+                assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+            }
+
+            #[test]
+            fn rejects_a_malformed_last_entry() {
+                assert_eq!(ClientInfo::forwarded_for(&parts("203.0.113.7, unknown")), None);
+            }
+        }
+    }
+}
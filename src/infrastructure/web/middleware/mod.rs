@@ -0,0 +1,2 @@
+pub mod authentication;
+pub mod client_info;
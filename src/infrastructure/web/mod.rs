@@ -1,3 +1,4 @@
 pub mod api;
+pub mod middleware;
 pub mod routing;
 pub mod websocket;
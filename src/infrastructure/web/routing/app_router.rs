@@ -4,7 +4,9 @@ cfg_if! {
     if #[cfg(feature = "ssr")] {
         use crate::{
             application::user_service::UserService, domain::models::app_state::AppState,
-            infrastructure::web::api::auth::auth_routes,
+            infrastructure::web::api::{
+                auth::auth_routes, transactions::transaction_routes, users::user_routes,
+            },
         };
         use axum::{routing::get, Router};
         use std::sync::Arc;
@@ -18,6 +20,8 @@ cfg_if! {
 
             Router::new()
                 .nest("/auth", auth_routes(Arc::clone(&shared_state)))
+                .nest("/transactions", transaction_routes(Arc::clone(&shared_state)))
+                .nest("/users", user_routes(Arc::clone(&shared_state)))
                 .route("/health", get(health_check))
         }
 
@@ -27,6 +27,14 @@ pub enum WebSocketMessage {
         message: String,
     },
     Pong,
+    /// Sent by the client to tie the connection to its signed-in session.
+    Authenticate {
+        access_token: String,
+    },
+    /// The session was revoked; the client must discard its tokens.
+    ForceLogout {
+        session_id: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +43,7 @@ pub struct ConnectionInfo {
     pub connected_at: Instant,
     pub last_heartbeat: Instant,
     pub user_id: Option<String>, // For authenticated connections
+    pub session_id: Option<String>,
 }
 
 pub type SharedConnectionManager = Arc<ConnectionManager>;
@@ -68,6 +77,7 @@ impl ConnectionManager {
             connected_at: Instant::now(),
             last_heartbeat: Instant::now(),
             user_id,
+            session_id: None,
         };
 
         {
@@ -158,6 +168,56 @@ impl ConnectionManager {
         }
     }
 
+    /// Ties the connection to a verified user and session, so it can be
+    /// told when that session is revoked.
+    pub async fn authenticate_connection(
+        &self,
+        connection_id: &Uuid,
+        user_id: String,
+        session_id: Option<String>,
+    ) {
+        let mut info_map = self.connection_info.write().await;
+        if let Some(info) = info_map.get_mut(connection_id) {
+            info.user_id = Some(user_id);
+            info.session_id = session_id;
+        }
+    }
+
+    /// Sends `ForceLogout` to every connection of the user's session.
+    pub async fn force_logout(&self, user_id: &str, session_id: &str) {
+        let targets: Vec<Uuid> = {
+            let info_map = self.connection_info.read().await;
+            info_map
+                .values()
+                .filter(|info| {
+                    info.user_id.as_deref() == Some(user_id)
+                        && info.session_id.as_deref() == Some(session_id)
+                })
+                .map(|info| info.id)
+                .collect()
+        };
+
+        let message = WebSocketMessage::ForceLogout {
+            session_id: session_id.to_string(),
+        };
+        let connections = self.connections.read().await;
+        for connection_id in &targets {
+            if let Some(sender) = connections.get(connection_id) {
+                // A closed channel is cleaned up by the next broadcast
+                let _ = sender.send(message.clone());
+            }
+        }
+        drop(connections);
+
+        if !targets.is_empty() {
+            logging::log!(
+                "Sent forced logout for session {} to {} connection(s)",
+                session_id,
+                targets.len()
+            );
+        }
+    }
+
     pub fn get_connection_count(&self) -> usize {
         self.connected_count.load(Ordering::Relaxed)
     }
@@ -171,7 +231,7 @@ impl ConnectionManager {
 
     // Clean up stale connections
     pub async fn cleanup_stale_connections(&self) {
-        let stale_threshold = Duration::from_secs(60); // 1 minute
+        let stale_threshold = Duration::from_mins(1);
         let now = Instant::now();
         let mut stale_connections = Vec::new();
 
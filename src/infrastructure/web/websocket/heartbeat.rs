@@ -1,5 +1,8 @@
 // src/infrastructure/websocket/heartbeat.rs
 use super::{ConnectionManager, WebSocketMessage};
+use crate::application::user_service::UserService;
+use crate::domain::models::app_state::AppState;
+use crate::infrastructure::auth::jwt_service::AuthService;
 use axum::{
     extract::{
         State,
@@ -13,15 +16,27 @@ use std::{sync::Arc, time::Duration};
 use tokio::time::interval;
 use uuid::Uuid;
 
+/// Connections start anonymous. A client that sends `Authenticate` with its
+/// access token is tied to its session and receives `ForceLogout` when that
+/// session is revoked.
 #[allow(clippy::unused_async)]
-pub async fn websocket_handler(
+pub async fn websocket_handler<T>(
     ws: WebSocketUpgrade,
-    State(connection_manager): State<Arc<ConnectionManager>>,
-) -> Response {
-    ws.on_upgrade(move |socket| handle_websocket(socket, connection_manager))
+    State(state): State<Arc<AppState<T>>>,
+) -> Response
+where
+    T: for<'a> UserService<'a> + Clone + Send + Sync + 'static,
+{
+    let connection_manager = Arc::clone(state.connection_manager());
+    let auth_service = state.auth_service().clone();
+    ws.on_upgrade(move |socket| handle_websocket(socket, connection_manager, auth_service))
 }
 
-async fn handle_websocket(socket: WebSocket, connection_manager: Arc<ConnectionManager>) {
+async fn handle_websocket(
+    socket: WebSocket,
+    connection_manager: Arc<ConnectionManager>,
+    auth_service: AuthService,
+) {
     let connection_id = Uuid::new_v4();
     let (mut sender, mut receiver) = socket.split();
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<WebSocketMessage>();
@@ -59,9 +74,13 @@ async fn handle_websocket(socket: WebSocket, connection_manager: Arc<ConnectionM
         while let Some(message) = receiver.next().await {
             match message {
                 Ok(Message::Text(text)) => {
-                    if let Err(e) =
-                        handle_client_message(&text, &connection_id, &connection_manager_clone)
-                            .await
+                    if let Err(e) = handle_client_message(
+                        &text,
+                        &connection_id,
+                        &connection_manager_clone,
+                        &auth_service,
+                    )
+                    .await
                     {
                         logging::warn!("Error handling client message: {}", e);
                     }
@@ -102,6 +121,7 @@ async fn handle_client_message(
     text: &str,
     connection_id: &Uuid,
     connection_manager: &Arc<ConnectionManager>,
+    auth_service: &AuthService,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let message: WebSocketMessage = serde_json::from_str(text)?;
 
@@ -112,6 +132,12 @@ async fn handle_client_message(
                 .update_last_heartbeat(connection_id)
                 .await;
         }
+        WebSocketMessage::Authenticate { access_token } => {
+            let claims = auth_service.verify_access_token(&access_token)?;
+            connection_manager
+                .authenticate_connection(connection_id, claims.sub, claims.family_id)
+                .await;
+        }
         _ => {
             // Handle other message types as needed
             logging::log!("Received message from {}: {:?}", connection_id, message);
@@ -124,7 +150,7 @@ async fn handle_client_message(
 // Background task to send periodic heartbeats
 pub async fn start_heartbeat_service(connection_manager: Arc<ConnectionManager>) {
     let mut heartbeat_interval = interval(Duration::from_secs(30)); // Send heartbeat every 30 seconds
-    let mut cleanup_interval = interval(Duration::from_secs(60)); // Cleanup every minute
+    let mut cleanup_interval = interval(Duration::from_mins(1)); // Cleanup every minute
 
     loop {
         tokio::select! {
@@ -3,7 +3,6 @@ pub mod application;
 pub mod components;
 pub mod domain;
 pub mod infrastructure;
-pub mod models;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -3,21 +3,28 @@
 use color_eyre::Result;
 #[cfg(feature = "ssr")]
 #[tokio::main]
+#[allow(clippy::too_many_lines)]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     use axum;
     use axum::Router;
     use axum::routing::get;
     use finance_tracker::app::{App, shell};
-    use finance_tracker::application::user_service::UserServiceImpl;
+    use finance_tracker::application::login_throttle::LoginThrottle;
+    use finance_tracker::application::transaction_service::TransactionServiceImpl;
+    use finance_tracker::application::user_service::{AccountMail, UserServiceImpl};
     use finance_tracker::domain::models::app_state::AppState;
+    use finance_tracker::infrastructure::auth::jwt_service::AuthService;
     use finance_tracker::infrastructure::config::app_config::get_config;
-    use finance_tracker::infrastructure::persistence::user_repository_cosmosdb::CosmosDbUserRepository;
+    use finance_tracker::infrastructure::mail::mail_sender::mail_sender_from_config;
+    use finance_tracker::infrastructure::persistence::repositories::Repositories;
+    use finance_tracker::infrastructure::web::api::well_known::well_known_routes;
     use finance_tracker::infrastructure::web::routing::app_router::create_api_router;
     use finance_tracker::infrastructure::web::websocket::{ConnectionManager, websocket_handler};
     use leptos::logging;
     use leptos::prelude::*;
     use leptos_axum;
     use leptos_axum::{LeptosRoutes, generate_route_list};
+    use std::net::SocketAddr;
     use std::sync::Arc;
     use tokio;
 
@@ -40,26 +47,67 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let app_config = get_config();
 
-    let user_repo = CosmosDbUserRepository::new(
-        app_config.cosmos.database_name.clone(),
-        app_config
-            .get_container_config("users")
-            .expect("Container config for 'users' not found")
-            .name
-            .clone(),
-        &app_config.cosmos.uri,
-        app_config.cosmos.primary_key.clone(),
+    let repositories = Repositories::from_config(app_config)
+        .await
+        .expect("Failed to initialise repositories");
+    logging::log!("Using {:?} storage backend", app_config.storage.backend);
+
+    let mail_sender =
+        mail_sender_from_config(&app_config.mail).expect("Failed to initialise mail sender");
+    logging::log!("Using {:?} mail transport", app_config.mail.transport);
+
+    let login_throttle = LoginThrottle::new(
+        repositories.login_attempts,
+        app_config.auth.login_throttle.clone(),
+    );
+    logging::log!(
+        "Counting failed sign-ins in {:?} store",
+        app_config.auth.login_throttle_store
     );
 
-    let user_service = UserServiceImpl::new(Arc::new(user_repo));
+    AuthService::validate_password_hashing(&app_config.auth.password_hashing)
+        .expect("Invalid password hashing parameters");
+    logging::log!(
+        "Hashing new passwords with {:?}",
+        app_config.auth.password_hashing.algorithm
+    );
 
-    let app_state = AppState::new(user_service, app_config.clone());
+    let auth_service =
+        AuthService::from_config(&app_config.auth).expect("Failed to load token signing keys");
+    logging::log!("Signing tokens with {:?}", auth_service);
 
-    let api_router = create_api_router(app_state);
+    let user_service = UserServiceImpl::new(
+        repositories.users,
+        repositories.refresh_tokens,
+        repositories.sessions,
+        Arc::clone(&repositories.transactions),
+        repositories.one_time_tokens,
+        repositories.access_tokens,
+        app_config.auth.password_policy.clone(),
+        app_config.auth.password_hashing.clone(),
+        AccountMail {
+            mail_sender,
+            public_url: app_config.server.public_url.clone(),
+            require_verification: app_config.auth.require_email_verification,
+        },
+        login_throttle,
+        Arc::clone(&connection_manager),
+    );
+    let transaction_service = TransactionServiceImpl::new(repositories.transactions);
+
+    let app_state = AppState::new(
+        user_service,
+        transaction_service,
+        auth_service.clone(),
+        app_config.clone(),
+        connection_manager,
+    );
 
     let ws_router = Router::new()
-        .route("/ws", get(websocket_handler))
-        .with_state(connection_manager);
+        .route("/ws", get(websocket_handler::<UserServiceImpl>))
+        .with_state(Arc::new(app_state.clone()));
+
+    let api_router = create_api_router(app_state);
 
     let app = Router::new()
         .leptos_routes(&leptos_options, routes, {
@@ -69,6 +117,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptos_options)
         .merge(Router::new().nest("/api", api_router))
+        .merge(Router::new().nest("/.well-known", well_known_routes(auth_service)))
         .merge(ws_router);
     // run our app with hyper
     // `axum::Server` is a re-export of `hyper::Server`
@@ -76,9 +125,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(&addr)
         .await
         .expect("Failed to bind TCP listener");
-    axum::serve(listener, app.into_make_service())
-        .await
-        .expect("Failed to start server");
+    // Connection info gives handlers the client address for sign-in throttling
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .expect("Failed to start server");
     Ok(())
 }
 